@@ -0,0 +1,14 @@
+-- Append-only field-level change log for plants, locations and care events.
+-- No foreign keys: history outlives the rows it describes.
+CREATE TABLE change_history (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT    NOT NULL,
+    entity_id   INTEGER NOT NULL,
+    plant_id    INTEGER,
+    action      TEXT    NOT NULL,
+    changes     TEXT    NOT NULL,
+    changed_at  TEXT    NOT NULL
+);
+
+CREATE INDEX idx_change_history_plant ON change_history (plant_id, id);
+CREATE INDEX idx_change_history_entity ON change_history (entity_type, entity_id, id);
@@ -0,0 +1,14 @@
+-- Soft delete: trashed rows keep their data and photos until restored or purged.
+ALTER TABLE plants ADD COLUMN deleted_at TEXT;
+ALTER TABLE care_events ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_plants_deleted_at ON plants (deleted_at);
+CREATE INDEX idx_care_events_deleted_at ON care_events (deleted_at);
+
+-- Trashed watering events no longer count towards last_watered.
+DROP VIEW plant_last_watered;
+CREATE VIEW plant_last_watered AS
+SELECT plant_id, MAX(occurred_at) AS last_watered
+FROM care_events
+WHERE event_type = 'watered' AND deleted_at IS NULL
+GROUP BY plant_id;
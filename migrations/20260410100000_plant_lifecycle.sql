@@ -0,0 +1,6 @@
+-- Lifecycle status: active plants are watered and published, others are kept for their history.
+ALTER TABLE plants ADD COLUMN lifecycle_status TEXT NOT NULL DEFAULT 'active';
+ALTER TABLE plants ADD COLUMN lifecycle_date TEXT;
+ALTER TABLE plants ADD COLUMN lifecycle_reason TEXT;
+
+CREATE INDEX idx_plants_lifecycle_status ON plants (lifecycle_status);
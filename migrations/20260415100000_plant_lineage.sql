@@ -0,0 +1,6 @@
+-- Propagation lineage: a plant may descend from a parent plant, and a `propagated` care event
+-- on the parent links to the child plant it produced.
+ALTER TABLE plants ADD COLUMN parent_id INTEGER REFERENCES plants(id) ON DELETE SET NULL;
+ALTER TABLE care_events ADD COLUMN child_plant_id INTEGER REFERENCES plants(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_plants_parent_id ON plants (parent_id);
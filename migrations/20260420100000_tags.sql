@@ -0,0 +1,13 @@
+-- Free-form labels that group plants across locations.
+CREATE TABLE tags (
+    id   INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT    NOT NULL UNIQUE
+);
+
+CREATE TABLE plant_tags (
+    plant_id INTEGER NOT NULL REFERENCES plants(id) ON DELETE CASCADE,
+    tag_id   INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (plant_id, tag_id)
+);
+
+CREATE INDEX idx_plant_tags_tag ON plant_tags (tag_id);
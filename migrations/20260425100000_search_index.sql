@@ -0,0 +1,45 @@
+-- Full-text indexes over plant and care journal text, kept in sync with their tables by triggers.
+CREATE VIRTUAL TABLE plants_fts USING fts5 (
+    name, species, notes,
+    content = 'plants', content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE care_events_fts USING fts5 (
+    notes,
+    content = 'care_events', content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER plants_fts_insert AFTER INSERT ON plants BEGIN
+    INSERT INTO plants_fts (rowid, name, species, notes)
+    VALUES (new.id, new.name, new.species, new.notes);
+END;
+
+CREATE TRIGGER plants_fts_delete AFTER DELETE ON plants BEGIN
+    INSERT INTO plants_fts (plants_fts, rowid, name, species, notes)
+    VALUES ('delete', old.id, old.name, old.species, old.notes);
+END;
+
+CREATE TRIGGER plants_fts_update AFTER UPDATE OF name, species, notes ON plants BEGIN
+    INSERT INTO plants_fts (plants_fts, rowid, name, species, notes)
+    VALUES ('delete', old.id, old.name, old.species, old.notes);
+    INSERT INTO plants_fts (rowid, name, species, notes)
+    VALUES (new.id, new.name, new.species, new.notes);
+END;
+
+CREATE TRIGGER care_events_fts_insert AFTER INSERT ON care_events BEGIN
+    INSERT INTO care_events_fts (rowid, notes) VALUES (new.id, new.notes);
+END;
+
+CREATE TRIGGER care_events_fts_delete AFTER DELETE ON care_events BEGIN
+    INSERT INTO care_events_fts (care_events_fts, rowid, notes) VALUES ('delete', old.id, old.notes);
+END;
+
+CREATE TRIGGER care_events_fts_update AFTER UPDATE OF notes ON care_events BEGIN
+    INSERT INTO care_events_fts (care_events_fts, rowid, notes) VALUES ('delete', old.id, old.notes);
+    INSERT INTO care_events_fts (rowid, notes) VALUES (new.id, new.notes);
+END;
+
+INSERT INTO plants_fts (plants_fts) VALUES ('rebuild');
+INSERT INTO care_events_fts (care_events_fts) VALUES ('rebuild');
@@ -0,0 +1,3 @@
+-- Keyset pagination walks the journal in (occurred_at, id) order, globally and per plant.
+CREATE INDEX idx_care_events_occurred ON care_events (occurred_at, id);
+CREATE INDEX idx_care_events_plant_occurred ON care_events (plant_id, occurred_at, id);
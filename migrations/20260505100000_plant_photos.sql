@@ -0,0 +1,17 @@
+-- Photo gallery per plant. The cover photo stays in plants.photo_path so existing
+-- consumers of photo_url keep working.
+CREATE TABLE plant_photos (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    plant_id   INTEGER NOT NULL REFERENCES plants(id) ON DELETE CASCADE,
+    filename   TEXT    NOT NULL UNIQUE,
+    caption    TEXT,
+    taken_at   TEXT,
+    position   INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT    NOT NULL
+);
+
+CREATE INDEX idx_plant_photos_plant ON plant_photos (plant_id, position);
+
+INSERT INTO plant_photos (plant_id, filename, taken_at, created_at)
+SELECT id, photo_path, substr(updated_at, 1, 10), updated_at
+FROM plants WHERE photo_path IS NOT NULL;
@@ -0,0 +1,14 @@
+-- Ordered photos per care event. care_events.photo_path keeps the first photo so
+-- photo_url and the has_photo filter keep working.
+CREATE TABLE care_event_photos (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    care_event_id INTEGER NOT NULL REFERENCES care_events(id) ON DELETE CASCADE,
+    filename      TEXT    NOT NULL UNIQUE,
+    position      INTEGER NOT NULL DEFAULT 0,
+    created_at    TEXT    NOT NULL
+);
+
+CREATE INDEX idx_care_event_photos_event ON care_event_photos (care_event_id, position);
+
+INSERT INTO care_event_photos (care_event_id, filename, created_at)
+SELECT id, photo_path, created_at FROM care_events WHERE photo_path IS NOT NULL;
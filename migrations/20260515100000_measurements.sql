@@ -0,0 +1,14 @@
+-- Numeric plant readings over time (height, leaf count, ...), optionally taken as part of
+-- a care event. Units are fixed per metric, see api::measurements::METRICS.
+CREATE TABLE measurements (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    plant_id      INTEGER NOT NULL REFERENCES plants(id) ON DELETE CASCADE,
+    care_event_id INTEGER REFERENCES care_events(id) ON DELETE SET NULL,
+    metric        TEXT    NOT NULL,
+    value         REAL    NOT NULL,
+    notes         TEXT,
+    measured_at   TEXT    NOT NULL,
+    created_at    TEXT    NOT NULL
+);
+
+CREATE INDEX idx_measurements_plant_metric ON measurements (plant_id, metric, measured_at);
@@ -0,0 +1 @@
+ALTER TABLE user_settings ADD COLUMN utc_offset TEXT NOT NULL DEFAULT '+00:00';
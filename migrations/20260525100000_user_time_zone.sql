@@ -0,0 +1,8 @@
+-- Days are bucketed in an IANA time zone so they follow daylight saving time. Whole-hour
+-- offsets become the matching fixed Etc/GMT zone (whose sign is inverted), others UTC.
+ALTER TABLE user_settings ADD COLUMN time_zone TEXT NOT NULL DEFAULT 'UTC';
+UPDATE user_settings SET time_zone = 'Etc/GMT'
+    || CASE substr(utc_offset, 1, 1) WHEN '+' THEN '-' ELSE '+' END
+    || CAST(substr(utc_offset, 2, 2) AS INTEGER)
+    WHERE utc_offset != '+00:00' AND substr(utc_offset, 5, 2) = '00';
+ALTER TABLE user_settings DROP COLUMN utc_offset;
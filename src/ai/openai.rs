@@ -543,7 +543,7 @@ mod tests {
 
     // --- Prompt caching tests ---
 
-    /// OpenAI automatic prompt caching reuses cached prefixes when the beginning
+    /// `OpenAI` automatic prompt caching reuses cached prefixes when the beginning
     /// of the messages array is byte-identical across requests.  This test
     /// simulates two consecutive chat turns and asserts that the message prefix
     /// from the first request is preserved exactly in the second request, so
@@ -1,7 +1,8 @@
 use serde::Serialize;
 use sqlx::SqlitePool;
 
-use crate::api::error::ApiError;
+use crate::api::error::{ApiError, db_error};
+use crate::api::measurements::metric;
 
 // --- Context structs ---
 
@@ -12,6 +13,8 @@ pub struct PlantContext {
     pub species: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     location_name: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     notes: Option<String>,
     current_state: CurrentState,
@@ -20,6 +23,8 @@ pub struct PlantContext {
     watering_dates: Vec<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     care_events: Vec<CareEventContext>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    measurements: Vec<MeasurementContext>,
 }
 
 #[derive(Serialize)]
@@ -53,6 +58,14 @@ pub struct CareEventContext {
     notes: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct MeasurementContext {
+    metric: String,
+    value: f64,
+    unit: &'static str,
+    date: String,
+}
+
 // --- Query structs ---
 
 #[derive(sqlx::FromRow)]
@@ -78,6 +91,13 @@ struct CareEventRow {
     notes: Option<String>,
 }
 
+#[derive(sqlx::FromRow)]
+struct MeasurementRow {
+    metric: String,
+    value: f64,
+    measured_at: String,
+}
+
 #[derive(sqlx::FromRow)]
 struct WateringDateRow {
     occurred_at: String,
@@ -98,15 +118,12 @@ pub async fn build_plant_context(
          p.difficulty, p.pet_safety, p.growth_speed, p.soil_type, p.soil_moisture, p.notes \
          FROM plants p LEFT JOIN locations l ON p.location_id = l.id \
          LEFT JOIN plant_last_watered lw ON lw.plant_id = p.id \
-         WHERE p.id = ?",
+         WHERE p.id = ? AND p.deleted_at IS NULL",
     )
     .bind(plant_id)
     .fetch_optional(pool)
     .await
-    .map_err(|e| {
-        tracing::error!("Database error: {e}");
-        ApiError::InternalError("INTERNAL_ERROR")
-    })?
+    .map_err(db_error)?
     .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
 
     let (watering_status, _) = crate::api::plants::compute_watering_status(
@@ -114,19 +131,25 @@ pub async fn build_plant_context(
         row.watering_interval_days,
     );
 
+    let tags = sqlx::query_scalar::<_, String>(
+        "SELECT t.name FROM plant_tags pt JOIN tags t ON t.id = pt.tag_id \
+         WHERE pt.plant_id = ? ORDER BY t.name",
+    )
+    .bind(plant_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error)?;
+
     let watering_rows = sqlx::query_as::<_, WateringDateRow>(
         "SELECT occurred_at FROM care_events \
-         WHERE plant_id = ? AND event_type = 'watered' \
+         WHERE plant_id = ? AND event_type = 'watered' AND deleted_at IS NULL \
          AND occurred_at >= datetime('now', '-1 year') \
          ORDER BY occurred_at DESC",
     )
     .bind(plant_id)
     .fetch_all(pool)
     .await
-    .map_err(|e| {
-        tracing::error!("Database error: {e}");
-        ApiError::InternalError("INTERNAL_ERROR")
-    })?;
+    .map_err(db_error)?;
 
     let watering_dates: Vec<String> = watering_rows
         .into_iter()
@@ -140,7 +163,7 @@ pub async fn build_plant_context(
 
     let event_rows = sqlx::query_as::<_, CareEventRow>(
         "SELECT event_type, occurred_at, notes FROM care_events \
-         WHERE plant_id = ? \
+         WHERE plant_id = ? AND deleted_at IS NULL \
          AND (event_type != 'watered' OR notes IS NOT NULL) \
          AND occurred_at >= datetime('now', '-5 years') \
          ORDER BY occurred_at DESC",
@@ -148,10 +171,7 @@ pub async fn build_plant_context(
     .bind(plant_id)
     .fetch_all(pool)
     .await
-    .map_err(|e| {
-        tracing::error!("Database error: {e}");
-        ApiError::InternalError("INTERNAL_ERROR")
-    })?;
+    .map_err(db_error)?;
 
     let care_events: Vec<CareEventContext> = event_rows
         .into_iter()
@@ -166,10 +186,13 @@ pub async fn build_plant_context(
         })
         .collect();
 
+    let measurements = fetch_measurements(pool, plant_id).await?;
+
     Ok(PlantContext {
         name: row.name,
         species: row.species,
         location_name: row.location_name,
+        tags,
         notes: row.notes,
         current_state: CurrentState {
             watering_status,
@@ -189,9 +212,40 @@ pub async fn build_plant_context(
         },
         watering_dates,
         care_events,
+        measurements,
     })
 }
 
+/// Measurements of the last five years, newest first.
+async fn fetch_measurements(
+    pool: &SqlitePool,
+    plant_id: i64,
+) -> Result<Vec<MeasurementContext>, ApiError> {
+    let rows = sqlx::query_as::<_, MeasurementRow>(
+        "SELECT metric, value, measured_at FROM measurements \
+         WHERE plant_id = ? AND measured_at >= datetime('now', '-5 years') \
+         ORDER BY measured_at DESC, id DESC",
+    )
+    .bind(plant_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|m| MeasurementContext {
+            unit: metric(&m.metric).map_or("", |metric| metric.unit),
+            metric: m.metric,
+            value: m.value,
+            date: m
+                .measured_at
+                .get(..10)
+                .unwrap_or(&m.measured_at)
+                .to_string(),
+        })
+        .collect())
+}
+
 pub fn build_chat_system_prompt(context: &PlantContext, locale: &str) -> String {
     let context_json = serde_json::to_string_pretty(context).unwrap_or_else(|_| "{}".to_string());
 
@@ -271,6 +325,7 @@ mod tests {
             name: "Monstera".to_string(),
             species: Some("Monstera deliciosa".to_string()),
             location_name: Some("Living Room".to_string()),
+            tags: vec!["aroids".to_string()],
             notes: None,
             current_state: CurrentState {
                 watering_status: "ok".to_string(),
@@ -291,6 +346,12 @@ mod tests {
                 date: "2026-02-15".to_string(),
                 notes: Some("Liquid fertilizer".to_string()),
             }],
+            measurements: vec![MeasurementContext {
+                metric: "height".to_string(),
+                value: 85.0,
+                unit: "cm",
+                date: "2026-02-01".to_string(),
+            }],
         };
 
         let json = serde_json::to_string_pretty(&context).unwrap();
@@ -310,6 +371,8 @@ mod tests {
         assert_eq!(prefs["watering_interval_days"], 10);
         assert_eq!(prefs["difficulty"], "easy");
 
+        assert_eq!(value["tags"][0], "aroids");
+
         // Verify care preference fields are NOT at top level
         assert!(value.get("light_needs").is_none());
         assert!(value.get("watering_interval_days").is_none());
@@ -326,6 +389,10 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert_eq!(events[0]["event_type"], "fertilized");
         assert_eq!(events[0]["notes"], "Liquid fertilizer");
+
+        let measurements = value["measurements"].as_array().unwrap();
+        assert_eq!(measurements[0]["metric"], "height");
+        assert_eq!(measurements[0]["unit"], "cm");
     }
 
     #[test]
@@ -334,6 +401,7 @@ mod tests {
             name: "Fern".to_string(),
             species: None,
             location_name: None,
+            tags: vec![],
             notes: None,
             current_state: CurrentState {
                 watering_status: "due".to_string(),
@@ -350,6 +418,7 @@ mod tests {
             },
             watering_dates: vec![],
             care_events: vec![],
+            measurements: vec![],
         };
         let prompt = build_chat_system_prompt(&context, "en");
         assert!(prompt.contains(
@@ -363,6 +432,7 @@ mod tests {
             name: "Monstera".to_string(),
             species: Some("Monstera deliciosa".to_string()),
             location_name: Some("Living Room".to_string()),
+            tags: vec![],
             notes: Some("Bought from nursery".to_string()),
             current_state: CurrentState {
                 watering_status: "ok".to_string(),
@@ -383,6 +453,7 @@ mod tests {
                 date: "2026-02-15".to_string(),
                 notes: Some("Liquid feed".to_string()),
             }],
+            measurements: vec![],
         };
         let prompt = build_chat_system_prompt(&context, "en");
         assert!(prompt.contains("flowl"));
@@ -401,6 +472,7 @@ mod tests {
             name: "Fern".to_string(),
             species: None,
             location_name: None,
+            tags: vec![],
             notes: None,
             current_state: CurrentState {
                 watering_status: "due".to_string(),
@@ -417,6 +489,7 @@ mod tests {
             },
             watering_dates: vec![],
             care_events: vec![],
+            measurements: vec![],
         };
         let prompt = build_chat_system_prompt(&context, "de");
         assert!(prompt.contains("Respond in German"));
@@ -428,6 +501,7 @@ mod tests {
             name: "Unknown".to_string(),
             species: None,
             location_name: None,
+            tags: vec![],
             notes: None,
             current_state: CurrentState {
                 watering_status: "ok".to_string(),
@@ -444,12 +518,14 @@ mod tests {
             },
             watering_dates: vec![],
             care_events: vec![],
+            measurements: vec![],
         };
         let prompt = build_chat_system_prompt(&context, "en");
         assert!(prompt.contains("Unknown"));
         // Empty collections should be omitted from JSON
         assert!(!prompt.contains("watering_dates"));
         assert!(!prompt.contains("care_events"));
+        assert!(!prompt.contains("measurements"));
         // Optional None fields should be omitted from JSON
         assert!(!prompt.contains("\"species\""));
         assert!(!prompt.contains("\"location_name\""));
@@ -462,6 +538,7 @@ mod tests {
             name: "Ficus".to_string(),
             species: None,
             location_name: None,
+            tags: vec![],
             notes: None,
             current_state: CurrentState {
                 watering_status: "ok".to_string(),
@@ -486,6 +563,7 @@ mod tests {
                 date: "2026-03-15".to_string(),
                 notes: Some("Leaves were drooping".to_string()),
             }],
+            measurements: vec![],
         };
 
         let json = serde_json::to_string_pretty(&context).unwrap();
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::time::Instant;
+
 use axum::Json;
 use axum::extract::{Multipart, State};
 use axum::response::sse::{Event, Sse};
@@ -11,6 +14,7 @@ use tracing::{debug, warn};
 use super::error::{ApiError, default_message};
 use crate::ai::prompts;
 use crate::ai::types::ChatMessage;
+use crate::metrics::Metrics;
 use crate::state::AppState;
 
 fn check_ai_rate_limit(state: &AppState) -> Result<(), ApiError> {
@@ -22,6 +26,27 @@ fn check_ai_rate_limit(state: &AppState) -> Result<(), ApiError> {
     Ok(())
 }
 
+/// Records a chat call once its response stream is dropped, so the latency covers the whole
+/// answer and a stream that fails part way counts as a failure.
+struct ChatOutcome {
+    metrics: Arc<Metrics>,
+    started: Instant,
+    success: bool,
+}
+
+impl ChatOutcome {
+    fn fail(&mut self) {
+        self.success = false;
+    }
+}
+
+impl Drop for ChatOutcome {
+    fn drop(&mut self) {
+        self.metrics
+            .record_ai("chat", self.started.elapsed(), self.success);
+    }
+}
+
 #[derive(Serialize)]
 pub struct AiStatus {
     pub enabled: bool,
@@ -102,7 +127,12 @@ pub async fn identify_plant(
     debug!(photo_count = photos.len(), locale = %locale, "sending photos to AI provider");
     let image_refs: Vec<&[u8]> = photos.iter().map(Vec::as_slice).collect();
 
-    let result = provider.identify(&image_refs, &locale).await.map_err(|e| {
+    let started = Instant::now();
+    let result = provider.identify(&image_refs, &locale).await;
+    state
+        .metrics
+        .record_ai("identify", started.elapsed(), result.is_ok());
+    let result = result.map_err(|e| {
         warn!(error = %e, "AI identify failed");
         ApiError::InternalError("AI_PROVIDER_FAILED")
     })?;
@@ -204,18 +234,26 @@ pub async fn chat(
         "AI chat request"
     );
 
+    let started = Instant::now();
     let stream = provider
         .chat(&system_prompt, &messages, image_ref, &locale)
         .await
         .map_err(|e| {
+            state.metrics.record_ai("chat", started.elapsed(), false);
             warn!(error = %e, "AI chat failed");
             ApiError::InternalError("AI_PROVIDER_FAILED")
         })?;
 
-    let sse_stream = stream.map(|result| {
+    let mut outcome = ChatOutcome {
+        metrics: state.metrics.clone(),
+        started,
+        success: true,
+    };
+    let sse_stream = stream.map(move |result| {
         let event = match result {
             Ok(delta) => Event::default().data(serde_json::json!({"delta": delta}).to_string()),
             Err(err) => {
+                outcome.fail();
                 warn!("AI stream error: {err}");
                 let code = "AI_STREAM_ERROR";
                 Event::default().data(
@@ -268,13 +306,17 @@ pub async fn summarize(
         "AI summarize request"
     );
 
+    let started = Instant::now();
     let summary = provider
         .summarize(&system_prompt, &body.history, &locale)
-        .await
-        .map_err(|e| {
-            warn!(error = %e, "AI summarize failed");
-            ApiError::InternalError("AI_PROVIDER_FAILED")
-        })?;
+        .await;
+    state
+        .metrics
+        .record_ai("summarize", started.elapsed(), summary.is_ok());
+    let summary = summary.map_err(|e| {
+        warn!(error = %e, "AI summarize failed");
+        ApiError::InternalError("AI_PROVIDER_FAILED")
+    })?;
 
     Ok(Json(SummarizeResponse { summary }))
 }
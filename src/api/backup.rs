@@ -1,25 +1,46 @@
-use std::io::Write;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
 
+use axum::body::{Body, Bytes};
 use axum::extract::State;
 use axum::http::header;
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
+use sqlx::SqliteConnection;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use zip::CompressionMethod;
+use zip::result::ZipResult;
 use zip::write::SimpleFileOptions;
 
 use tracing::info;
 
+use super::backup_format::FORMAT_VERSION;
 use super::error::{ApiError, db_error};
 use crate::images::is_thumbnail_filename;
 use crate::state::AppState;
 
+/// Size of the chunks the archive is streamed in.
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Chunks buffered before writing the archive waits for the client.
+const CHANNEL_CHUNKS: usize = 4;
+
 #[derive(Serialize)]
 pub struct ExportData {
     pub version: String,
+    pub format_version: usize,
     pub exported_at: String,
     pub locations: Vec<ExportLocation>,
     pub plants: Vec<ExportPlant>,
     pub care_events: Vec<ExportCareEvent>,
+    pub history: Vec<ExportHistoryEntry>,
+    pub tags: Vec<ExportTag>,
+    pub plant_tags: Vec<ExportPlantTag>,
+    pub plant_photos: Vec<ExportPlantPhoto>,
+    pub care_event_photos: Vec<ExportCareEventPhoto>,
+    pub measurements: Vec<ExportMeasurement>,
 }
 
 #[derive(Serialize, sqlx::FromRow)]
@@ -28,6 +49,50 @@ pub struct ExportLocation {
     pub name: String,
 }
 
+#[derive(Serialize, sqlx::FromRow)]
+pub struct ExportTag {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct ExportPlantTag {
+    pub plant_id: i64,
+    pub tag_id: i64,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct ExportPlantPhoto {
+    pub id: i64,
+    pub plant_id: i64,
+    pub filename: String,
+    pub caption: Option<String>,
+    pub taken_at: Option<String>,
+    pub position: i64,
+    pub created_at: String,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct ExportCareEventPhoto {
+    pub id: i64,
+    pub care_event_id: i64,
+    pub filename: String,
+    pub position: i64,
+    pub created_at: String,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct ExportMeasurement {
+    pub id: i64,
+    pub plant_id: i64,
+    pub care_event_id: Option<i64>,
+    pub metric: String,
+    pub value: f64,
+    pub notes: Option<String>,
+    pub measured_at: String,
+    pub created_at: String,
+}
+
 #[derive(Serialize, sqlx::FromRow)]
 pub struct ExportPlant {
     pub id: i64,
@@ -45,8 +110,13 @@ pub struct ExportPlant {
     pub soil_type: Option<String>,
     pub soil_moisture: Option<String>,
     pub notes: Option<String>,
+    pub lifecycle_status: String,
+    pub lifecycle_date: Option<String>,
+    pub lifecycle_reason: Option<String>,
+    pub parent_id: Option<i64>,
     pub created_at: String,
     pub updated_at: String,
+    pub deleted_at: Option<String>,
 }
 
 #[derive(Serialize, sqlx::FromRow)]
@@ -56,103 +126,243 @@ pub struct ExportCareEvent {
     pub event_type: String,
     pub notes: Option<String>,
     pub photo_path: Option<String>,
+    pub child_plant_id: Option<i64>,
     pub occurred_at: String,
     pub created_at: String,
+    pub deleted_at: Option<String>,
 }
 
-/// # Errors
-/// Returns `ApiError::InternalError` on database failures or if the ZIP archive cannot be created.
-pub async fn export_data(State(state): State<AppState>) -> Result<Response, ApiError> {
+#[derive(Serialize)]
+pub struct ExportHistoryEntry {
+    pub id: i64,
+    pub entity_type: String,
+    pub entity_id: i64,
+    pub plant_id: Option<i64>,
+    pub action: String,
+    pub changes: serde_json::Value,
+    pub changed_at: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct HistoryRow {
+    id: i64,
+    entity_type: String,
+    entity_id: i64,
+    plant_id: Option<i64>,
+    action: String,
+    changes: String,
+    changed_at: String,
+}
+
+async fn fetch_history(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<ExportHistoryEntry>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, HistoryRow>(
+        "SELECT id, entity_type, entity_id, plant_id, action, changes, changed_at \
+         FROM change_history ORDER BY id",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ExportHistoryEntry {
+            id: row.id,
+            entity_type: row.entity_type,
+            entity_id: row.entity_id,
+            plant_id: row.plant_id,
+            action: row.action,
+            changes: serde_json::from_str(&row.changes).unwrap_or_default(),
+            changed_at: row.changed_at,
+        })
+        .collect())
+}
+
+async fn fetch_tags(
+    conn: &mut SqliteConnection,
+) -> Result<(Vec<ExportTag>, Vec<ExportPlantTag>), sqlx::Error> {
+    let tags = sqlx::query_as::<_, ExportTag>("SELECT id, name FROM tags ORDER BY id")
+        .fetch_all(&mut *conn)
+        .await?;
+    let plant_tags = sqlx::query_as::<_, ExportPlantTag>(
+        "SELECT plant_id, tag_id FROM plant_tags ORDER BY plant_id, tag_id",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok((tags, plant_tags))
+}
+
+/// Read everything the export contains. Run it inside a transaction so that the archive is
+/// a consistent snapshot even while the collection is being edited.
+pub(crate) async fn collect_export_data(
+    conn: &mut SqliteConnection,
+) -> Result<ExportData, sqlx::Error> {
     let locations = sqlx::query_as::<_, ExportLocation>("SELECT id, name FROM locations")
-        .fetch_all(&state.pool)
-        .await
-        .map_err(db_error)?;
+        .fetch_all(&mut *conn)
+        .await?;
 
     let plants = sqlx::query_as::<_, ExportPlant>(
         "SELECT p.id, p.name, p.species, p.icon, p.photo_path, p.location_id, p.watering_interval_days, \
          lw.last_watered, \
          p.light_needs, p.difficulty, p.pet_safety, p.growth_speed, p.soil_type, \
-         p.soil_moisture, p.notes, p.created_at, p.updated_at \
+         p.soil_moisture, p.notes, p.lifecycle_status, p.lifecycle_date, p.lifecycle_reason, \
+         p.parent_id, p.created_at, p.updated_at, p.deleted_at \
          FROM plants p LEFT JOIN plant_last_watered lw ON lw.plant_id = p.id",
     )
-    .fetch_all(&state.pool)
-    .await
-    .map_err(db_error)?;
+    .fetch_all(&mut *conn)
+    .await?;
 
     let care_events = sqlx::query_as::<_, ExportCareEvent>(
-        "SELECT id, plant_id, event_type, notes, photo_path, occurred_at, created_at FROM care_events",
+        "SELECT id, plant_id, event_type, notes, photo_path, child_plant_id, occurred_at, \
+         created_at, deleted_at FROM care_events",
     )
-    .fetch_all(&state.pool)
-    .await
-    .map_err(db_error)?;
+    .fetch_all(&mut *conn)
+    .await?;
 
-    let data = ExportData {
+    let history = fetch_history(conn).await?;
+    let (tags, plant_tags) = fetch_tags(conn).await?;
+    let plant_photos = sqlx::query_as::<_, ExportPlantPhoto>(
+        "SELECT id, plant_id, filename, caption, taken_at, position, created_at \
+         FROM plant_photos ORDER BY id",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let care_event_photos = sqlx::query_as::<_, ExportCareEventPhoto>(
+        "SELECT id, care_event_id, filename, position, created_at \
+         FROM care_event_photos ORDER BY id",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let measurements = sqlx::query_as::<_, ExportMeasurement>(
+        "SELECT id, plant_id, care_event_id, metric, value, notes, measured_at, created_at \
+         FROM measurements ORDER BY id",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(ExportData {
         version: env!("CARGO_PKG_VERSION").to_string(),
+        format_version: FORMAT_VERSION,
         exported_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
         locations,
         plants,
         care_events,
-    };
+        history,
+        tags,
+        plant_tags,
+        plant_photos,
+        care_event_photos,
+        measurements,
+    })
+}
+
+/// Original photo files of the export (plant galleries + care events), each once and
+/// without thumbnails. Covers and first event photos are listed twice, but older rows may
+/// only be in `photo_path`.
+fn photo_paths(data: &ExportData) -> BTreeSet<&str> {
+    data.plants
+        .iter()
+        .filter_map(|p| p.photo_path.as_deref())
+        .chain(data.plant_photos.iter().map(|p| p.filename.as_str()))
+        .chain(
+            data.care_events
+                .iter()
+                .filter_map(|e| e.photo_path.as_deref()),
+        )
+        .chain(data.care_event_photos.iter().map(|p| p.filename.as_str()))
+        .filter(|p| !is_thumbnail_filename(p))
+        .collect()
+}
+
+/// Write the export archive to `out` as it is produced: `data.json`, then the original
+/// photos under `photos/`, read from `upload_dir` one at a time. Photos missing on disk
+/// are left out.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub(crate) fn write_archive<W: Write>(
+    out: W,
+    data: &ExportData,
+    upload_dir: &Path,
+) -> ZipResult<W> {
+    let mut zip = zip::ZipWriter::new_stream(out);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("data.json", options)?;
+    serde_json::to_writer_pretty(&mut zip, data).map_err(std::io::Error::from)?;
+
+    for photo_path in photo_paths(data) {
+        if let Ok(mut photo) = File::open(upload_dir.join(photo_path)) {
+            zip.start_file(format!("photos/{photo_path}"), options)?;
+            std::io::copy(&mut photo, &mut zip)?;
+        }
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Forwards the bytes of the archive to the response body in chunks.
+struct ChannelWriter {
+    tx: mpsc::Sender<Result<Bytes, std::io::Error>>,
+    buf: Vec<u8>,
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        if self.buf.len() >= CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(CHUNK_SIZE));
+        self.tx
+            .blocking_send(Ok(Bytes::from(chunk)))
+            .map_err(|_| std::io::Error::new(ErrorKind::BrokenPipe, "export download cancelled"))
+    }
+}
+
+/// # Errors
+/// Returns `ApiError::InternalError` on database failures. Failures while the archive is
+/// streamed abort the download.
+pub async fn export_data(State(state): State<AppState>) -> Result<Response, ApiError> {
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let data = collect_export_data(&mut tx).await.map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
     info!(
         locations = data.locations.len(),
         plants = data.plants.len(),
         care_events = data.care_events.len(),
+        history = data.history.len(),
         "Data export started"
     );
 
-    let json = serde_json::to_string_pretty(&data).map_err(|e| {
-        tracing::error!("JSON serialization failed: {e}");
-        ApiError::InternalError("INTERNAL_ERROR")
-    })?;
-
-    let mut buf = Vec::new();
-    {
-        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut buf));
-        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
-
-        zip.start_file("data.json", options).map_err(|e| {
-            tracing::error!("ZIP write failed: {e}");
-            ApiError::InternalError("INTERNAL_ERROR")
-        })?;
-        zip.write_all(json.as_bytes()).map_err(|e| {
-            tracing::error!("ZIP write failed: {e}");
-            ApiError::InternalError("INTERNAL_ERROR")
-        })?;
-
-        // Add original photo files (plants + care events), excluding thumbnails
-        let photo_paths: Vec<&str> = data
-            .plants
-            .iter()
-            .filter_map(|p| p.photo_path.as_deref())
-            .chain(
-                data.care_events
-                    .iter()
-                    .filter_map(|e| e.photo_path.as_deref()),
-            )
-            .filter(|p| !is_thumbnail_filename(p))
-            .collect();
-
-        for photo_path in photo_paths {
-            let file_path = state.image_store.upload_dir().join(photo_path);
-            if let Ok(photo_data) = tokio::fs::read(&file_path).await {
-                let archive_path = format!("photos/{photo_path}");
-                zip.start_file(&archive_path, options).map_err(|e| {
-                    tracing::error!("ZIP write failed: {e}");
-                    ApiError::InternalError("INTERNAL_ERROR")
-                })?;
-                zip.write_all(&photo_data).map_err(|e| {
-                    tracing::error!("ZIP write failed: {e}");
-                    ApiError::InternalError("INTERNAL_ERROR")
-                })?;
+    // The archive is written on a blocking thread and sent while it is produced, so
+    // memory use does not grow with the size of the photo collection.
+    let (tx, rx) = mpsc::channel(CHANNEL_CHUNKS);
+    let upload_dir = state.image_store.upload_dir().to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let writer = ChannelWriter {
+            tx: tx.clone(),
+            buf: Vec::with_capacity(CHUNK_SIZE),
+        };
+        match write_archive(writer, &data, &upload_dir).and_then(|mut writer| {
+            writer.flush()?;
+            Ok(())
+        }) {
+            Ok(()) => info!("Data export complete"),
+            Err(e) => {
+                tracing::error!("ZIP export failed: {e}");
+                let _ = tx.blocking_send(Err(std::io::Error::other("export failed")));
             }
         }
-
-        zip.finish().map_err(|e| {
-            tracing::error!("ZIP finalize failed: {e}");
-            ApiError::InternalError("INTERNAL_ERROR")
-        })?;
-    }
+    });
 
     let disposition = format!(
         "attachment; filename=\"flowl-export-v{}.zip\"",
@@ -164,7 +374,7 @@ pub async fn export_data(State(state): State<AppState>) -> Result<Response, ApiE
             (header::CONTENT_TYPE, "application/zip".to_string()),
             (header::CONTENT_DISPOSITION, disposition),
         ],
-        buf,
+        Body::from_stream(ReceiverStream::new(rx)),
     )
         .into_response())
 }
@@ -0,0 +1,115 @@
+use serde_json::{Map, Value};
+
+use super::error::ApiError;
+use super::plants::LIFECYCLE_ACTIVE;
+
+/// Forward migrations of `data.json`; the one at index `i` upgrades format `i + 1` to `i + 2`.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[add_missing_collections];
+
+/// Version of the `data.json` layout, independent of the application version. Bump it by
+/// adding a migration whenever the layout changes.
+pub const FORMAT_VERSION: usize = MIGRATIONS.len() + 1;
+
+/// Format 1 archives (written before the format version existed) may lack the collections
+/// and plant lifecycle fields added over time.
+fn add_missing_collections(document: &mut Map<String, Value>) {
+    for key in [
+        "history",
+        "tags",
+        "plant_tags",
+        "plant_photos",
+        "care_event_photos",
+        "measurements",
+    ] {
+        document
+            .entry(key)
+            .or_insert_with(|| Value::Array(Vec::new()));
+    }
+    if let Some(Value::Array(plants)) = document.get_mut("plants") {
+        for plant in plants.iter_mut().filter_map(Value::as_object_mut) {
+            plant
+                .entry("lifecycle_status")
+                .or_insert_with(|| LIFECYCLE_ACTIVE.into());
+        }
+    }
+}
+
+/// Upgrade a parsed `data.json` to the current format, returning its original format.
+///
+/// # Errors
+/// Returns `ApiError::BadRequest` if the document is not an object or has an invalid
+/// format version, or if it was written in a newer format than this server supports.
+pub(crate) fn upgrade(document: &mut Value) -> Result<usize, ApiError> {
+    let Value::Object(document) = document else {
+        return Err(ApiError::BadRequest("IMPORT_INVALID_DATA"));
+    };
+    let format = match document.get("format_version") {
+        None => 1,
+        Some(value) => value
+            .as_u64()
+            .and_then(|format| usize::try_from(format).ok())
+            .filter(|&format| format >= 1)
+            .ok_or(ApiError::BadRequest("IMPORT_INVALID_DATA"))?,
+    };
+    if format > FORMAT_VERSION {
+        return Err(ApiError::BadRequest("IMPORT_FORMAT_TOO_NEW"));
+    }
+
+    for migration in &MIGRATIONS[format - 1..] {
+        migration(document);
+    }
+    document.insert("format_version".to_string(), FORMAT_VERSION.into());
+    Ok(format)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn documents_without_format_version_are_upgraded() {
+        let mut document = json!({
+            "version": "1.4.2",
+            "locations": [],
+            "plants": [{"id": 1, "name": "Fern"}],
+            "care_events": [],
+            "tags": [{"id": 1, "name": "Tropical"}]
+        });
+        assert_eq!(upgrade(&mut document).ok(), Some(1));
+        assert_eq!(document["format_version"], FORMAT_VERSION);
+        assert_eq!(document["plants"][0]["lifecycle_status"], LIFECYCLE_ACTIVE);
+        assert_eq!(document["measurements"], json!([]));
+        assert_eq!(document["tags"][0]["name"], "Tropical");
+    }
+
+    #[test]
+    fn current_documents_are_unchanged() {
+        let mut document = json!({
+            "format_version": FORMAT_VERSION,
+            "plants": [{"id": 1, "lifecycle_status": "dead"}],
+        });
+        let original = document.clone();
+        assert_eq!(upgrade(&mut document).ok(), Some(FORMAT_VERSION));
+        assert_eq!(document, original);
+    }
+
+    #[test]
+    fn newer_and_invalid_formats_are_rejected() {
+        let code = |mut document: Value| upgrade(&mut document).err().map(|e| e.code());
+        assert_eq!(
+            code(json!({"format_version": FORMAT_VERSION + 1})),
+            Some("IMPORT_FORMAT_TOO_NEW")
+        );
+        assert_eq!(
+            code(json!({"format_version": 0})),
+            Some("IMPORT_INVALID_DATA")
+        );
+        assert_eq!(
+            code(json!({"format_version": "2"})),
+            Some("IMPORT_INVALID_DATA")
+        );
+        assert_eq!(code(json!([])), Some("IMPORT_INVALID_DATA"));
+    }
+}
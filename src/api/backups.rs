@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::Response;
+use serde::Serialize;
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
+use tracing::info;
+
+use super::error::ApiError;
+use super::restore::{ImportQuery, import_archive, import_mode};
+use crate::backups::{self, BackupInfo, Retention};
+use crate::state::AppState;
+
+#[derive(Serialize)]
+pub struct BackupList {
+    /// Configured schedule, or `None` when backups are only taken manually.
+    pub schedule: Option<String>,
+    /// Next scheduled backup (RFC 3339, UTC).
+    pub next_run: Option<String>,
+    pub retention: Retention,
+    /// Stored backups, newest first.
+    pub backups: Vec<BackupInfo>,
+}
+
+fn backup_error(e: impl std::fmt::Display) -> ApiError {
+    tracing::error!("Backup failed: {e}");
+    ApiError::InternalError("BACKUP_FAILED")
+}
+
+/// Path of an existing stored backup.
+async fn existing_backup(state: &AppState, name: &str) -> Result<PathBuf, ApiError> {
+    let path = state
+        .backups
+        .path(name)
+        .ok_or(ApiError::NotFound("BACKUP_NOT_FOUND"))?;
+    if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        Ok(path)
+    } else {
+        Err(ApiError::NotFound("BACKUP_NOT_FOUND"))
+    }
+}
+
+/// # Errors
+/// Returns `ApiError::InternalError` if the backup directory cannot be read.
+pub async fn list_backups(State(state): State<AppState>) -> Result<Json<BackupList>, ApiError> {
+    let backups = state.backups.list().await.map_err(backup_error)?;
+    let schedule = state.backups.schedule();
+    let next_run = match schedule {
+        Some(schedule) => backups::next_run(&state.pool, schedule)
+            .await
+            .map(|next| next.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+        None => None,
+    };
+    Ok(Json(BackupList {
+        schedule: schedule.map(|schedule| schedule.expression().to_string()),
+        next_run,
+        retention: state.backups.retention(),
+        backups,
+    }))
+}
+
+/// Take a backup now, applying the retention policy afterwards like a scheduled one.
+///
+/// # Errors
+/// Returns `ApiError::InternalError` if the data cannot be read or the backup written.
+pub async fn create_backup(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<BackupInfo>), ApiError> {
+    let backup = state
+        .backups
+        .create(&state.pool, &state.image_store)
+        .await
+        .map_err(backup_error)?;
+    info!(name = %backup.name, size_bytes = backup.size_bytes, "Manual backup written");
+    Ok((StatusCode::CREATED, Json(backup)))
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if no stored backup has this name.
+pub async fn download_backup(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    let path = existing_backup(&state, &name).await?;
+    let Ok(response) = ServeFile::new(path).oneshot(request).await;
+    let mut response = response.map(Body::new);
+    // Backup names only contain ASCII letters, digits and punctuation
+    if let Ok(disposition) = HeaderValue::from_str(&format!("attachment; filename=\"{name}\"")) {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_DISPOSITION, disposition);
+    }
+    Ok(response)
+}
+
+/// Restore a stored backup, replacing all data or merging it in like an uploaded archive.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if no stored backup has this name, `ApiError::BadRequest`
+/// for an unknown mode or an invalid archive, or `ApiError::InternalError` on database
+/// failures.
+pub async fn restore_backup(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<ImportQuery>,
+) -> Result<Response, ApiError> {
+    let merge = import_mode(&params)?;
+    let path = existing_backup(&state, &name).await?;
+
+    info!(name, merge, "Restoring stored backup");
+    import_archive(&state, path, merge).await
+}
@@ -1,16 +1,24 @@
 use axum::Json;
-use axum::extract::{Multipart, Path, State};
+use axum::extract::{Multipart, Path, Query, State};
 use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum_extra::extract::Query as ExtraQuery;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
 use tracing::{debug, info};
 
 use super::error::{ApiError, JsonBody, db_error};
-use super::plants::{PLANT_SELECT, Plant, PlantRow};
-use crate::images::ImageError;
-use crate::mqtt;
+use super::history::{self, ENTITY_CARE_EVENT};
+use super::photos::image_error;
+use super::plants::{
+    self, Plant, publish_plant_mqtt, publish_watering_mqtt, validate_required_name,
+};
+use super::search;
+use crate::events::ChangeEvent;
 use crate::state::AppState;
 
 const VALID_EVENT_TYPES: &[&str] = &[
@@ -18,6 +26,7 @@ const VALID_EVENT_TYPES: &[&str] = &[
     "fertilized",
     "repotted",
     "pruned",
+    "propagated",
     "custom",
     "ai-consultation",
 ];
@@ -29,37 +38,140 @@ pub struct CareEvent {
     pub plant_name: String,
     pub event_type: String,
     pub notes: Option<String>,
+    /// The first photo, kept for clients that show a single image.
     pub photo_url: Option<String>,
+    #[sqlx(try_from = "String")]
+    pub photos: CareEventPhotos,
+    pub child_plant_id: Option<i64>,
     pub occurred_at: String,
     pub created_at: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct CareEventPhoto {
+    pub id: i64,
+    pub url: String,
+    pub position: i64,
+}
+
+/// A care event's photos in order, decoded from the JSON array built by `CARE_EVENT_SELECT`.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct CareEventPhotos(pub Vec<CareEventPhoto>);
+
+impl TryFrom<String> for CareEventPhotos {
+    type Error = serde_json::Error;
+
+    fn try_from(json: String) -> Result<Self, Self::Error> {
+        serde_json::from_str(&json)
+    }
+}
+
+/// Most photos attached to one care event.
+const MAX_EVENT_PHOTOS: i64 = 10;
+
+#[derive(Deserialize)]
+pub struct ReorderCareEventPhotos {
+    pub ids: Vec<i64>,
+}
+
 #[derive(Deserialize)]
 pub struct CreateCareEvent {
     pub event_type: Option<String>,
     pub notes: Option<String>,
     pub occurred_at: Option<String>,
+    /// Name of the child plant created by a `propagated` event, defaults to the parent's name.
+    pub child_name: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct GlobalCareQuery {
     pub limit: Option<i64>,
-    pub before: Option<i64>,
+    /// Opaque `next_cursor` of the previous page.
+    pub cursor: Option<String>,
     #[serde(default, rename = "type")]
     pub event_types: Vec<String>,
+    /// Only list events of plants carrying the tag with this name.
+    pub tag: Option<String>,
+    #[serde(default, rename = "plant_id")]
+    pub plant_ids: Vec<i64>,
+    pub location_id: Option<i64>,
+    /// First day (`YYYY-MM-DD`) of `occurred_at`, inclusive.
+    pub from: Option<String>,
+    /// Last day (`YYYY-MM-DD`) of `occurred_at`, inclusive.
+    pub to: Option<String>,
+    pub has_photo: Option<bool>,
+    /// Words that must all occur in the notes, matched as prefixes.
+    pub q: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PlantCareQuery {
+    /// Page size; all events are listed when omitted.
+    pub limit: Option<i64>,
+    /// Opaque `X-Next-Cursor` of the previous page.
+    pub cursor: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct CareEventsPage {
     pub events: Vec<CareEvent>,
     pub has_more: bool,
+    /// Cursor for the next page, set when `has_more` is true.
+    pub next_cursor: Option<String>,
+}
+
+const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
+/// Position of an event in journal order (`occurred_at DESC, id DESC`). Encoded as an
+/// opaque token so clients do not depend on its contents.
+struct Cursor {
+    occurred_at: String,
+    id: i64,
 }
 
-const CARE_EVENT_SELECT: &str = "SELECT ce.id, ce.plant_id, p.name AS plant_name, \
+impl Cursor {
+    fn encode(event: &CareEvent) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}|{}", event.occurred_at, event.id))
+    }
+
+    fn decode(token: &str) -> Result<Self, ApiError> {
+        URL_SAFE_NO_PAD
+            .decode(token)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .and_then(|text| {
+                let (occurred_at, id) = text.rsplit_once('|')?;
+                Some(Self {
+                    occurred_at: occurred_at.to_string(),
+                    id: id.parse().ok()?,
+                })
+            })
+            .ok_or(ApiError::Validation("CARE_EVENT_INVALID_CURSOR"))
+    }
+}
+
+/// Condition selecting events after a cursor in journal order; binds `occurred_at`, `id`.
+const AFTER_CURSOR_SQL: &str = "(ce.occurred_at, ce.id) < (?, ?)";
+
+/// Drop the look-ahead row fetched beyond `limit` and return the cursor to continue from.
+fn next_page_cursor(events: &mut Vec<CareEvent>, limit: i64) -> Option<String> {
+    if events.len() <= usize::try_from(limit).unwrap_or(usize::MAX) {
+        return None;
+    }
+    events.pop();
+    events.last().map(Cursor::encode)
+}
+
+pub(crate) const CARE_EVENT_SELECT: &str = "SELECT ce.id, ce.plant_id, p.name AS plant_name, \
     ce.event_type, ce.notes, \
     CASE WHEN ce.photo_path IS NOT NULL THEN '/uploads/' || ce.photo_path END AS photo_url, \
-    ce.occurred_at, ce.created_at \
-    FROM care_events ce JOIN plants p ON ce.plant_id = p.id";
+    (SELECT json_group_array(json_object('id', id, 'url', '/uploads/' || filename, \
+        'position', position)) FROM (SELECT id, filename, position FROM care_event_photos \
+        WHERE care_event_id = ce.id ORDER BY position, id)) AS photos, \
+    ce.child_plant_id, ce.occurred_at, ce.created_at \
+    FROM care_events ce JOIN plants p ON ce.plant_id = p.id \
+    WHERE ce.deleted_at IS NULL AND p.deleted_at IS NULL";
 
 /// # Errors
 /// Returns `ApiError::Validation` if `event_type` is not in `VALID_EVENT_TYPES`.
@@ -71,12 +183,13 @@ pub fn validate_event_type(event_type: &str) -> Result<(), ApiError> {
     }
 }
 
-async fn plant_exists(pool: &SqlitePool, id: i64) -> Result<(), ApiError> {
-    let exists = sqlx::query_scalar::<_, i64>("SELECT id FROM plants WHERE id = ?")
-        .bind(id)
-        .fetch_optional(pool)
-        .await
-        .map_err(db_error)?;
+pub(crate) async fn plant_exists(pool: &SqlitePool, id: i64) -> Result<(), ApiError> {
+    let exists =
+        sqlx::query_scalar::<_, i64>("SELECT id FROM plants WHERE id = ? AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(db_error)?;
 
     if exists.is_none() {
         return Err(ApiError::NotFound("PLANT_NOT_FOUND"));
@@ -84,52 +197,62 @@ async fn plant_exists(pool: &SqlitePool, id: i64) -> Result<(), ApiError> {
     Ok(())
 }
 
-async fn publish_plant_watering_mqtt(state: &AppState, plant_id: i64) {
-    let Ok(row) = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!(
-        "{PLANT_SELECT} WHERE p.id = ?"
-    )))
-    .bind(plant_id)
-    .fetch_one(&state.pool)
-    .await
-    else {
+async fn fetch_plant(pool: &SqlitePool, plant_id: i64) -> Option<Plant> {
+    plants::fetch_plant(pool, plant_id).await.ok().flatten()
+}
+
+pub(crate) async fn publish_plant_watering_mqtt(
+    state: &AppState,
+    plant_id: i64,
+    previous_status: Option<&str>,
+) {
+    let Some(plant) = fetch_plant(&state.pool, plant_id).await else {
         return;
     };
-    let plant = Plant::from(row);
-    mqtt::publish_state(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        &plant.watering_status,
-    )
-    .await;
-    mqtt::publish_attributes(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        plant.last_watered.as_deref(),
-        plant.next_due.as_deref(),
-        plant.watering_interval_days,
-    )
-    .await;
+    state
+        .events
+        .publish_status_change(plant.id, previous_status, &plant.watering_status);
+    publish_watering_mqtt(state, &plant).await;
 }
 
+/// List a plant's care events, newest first. With `limit`, the `X-Next-Cursor` header
+/// carries the cursor for the next page while more events remain.
+///
 /// # Errors
-/// Returns `ApiError::NotFound` if the plant does not exist, or
+/// Returns `ApiError::NotFound` if the plant does not exist,
+/// `ApiError::Validation` if the cursor is invalid, or
 /// `ApiError::InternalError` on database failures.
 pub async fn list_care_events(
     State(pool): State<SqlitePool>,
     Path(plant_id): Path<i64>,
-) -> Result<Json<Vec<CareEvent>>, ApiError> {
+    Query(params): Query<PlantCareQuery>,
+) -> Result<Response, ApiError> {
     plant_exists(&pool, plant_id).await?;
+    let cursor = params.cursor.as_deref().map(Cursor::decode).transpose()?;
+    let limit = params.limit.map(|l| l.clamp(1, 100));
 
-    let query = format!("{CARE_EVENT_SELECT} WHERE ce.plant_id = ? ORDER BY ce.occurred_at DESC");
-    let events = sqlx::query_as::<_, CareEvent>(sqlx::AssertSqlSafe(query.as_str()))
-        .bind(plant_id)
+    let mut query = format!("{CARE_EVENT_SELECT} AND ce.plant_id = ?");
+    if cursor.is_some() {
+        query.push_str(" AND ");
+        query.push_str(AFTER_CURSOR_SQL);
+    }
+    // A negative LIMIT means no limit in SQLite
+    query.push_str(" ORDER BY ce.occurred_at DESC, ce.id DESC LIMIT ?");
+
+    let mut q = sqlx::query_as::<_, CareEvent>(sqlx::AssertSqlSafe(query)).bind(plant_id);
+    if let Some(cursor) = &cursor {
+        q = q.bind(&cursor.occurred_at).bind(cursor.id);
+    }
+    let mut events = q
+        .bind(limit.map_or(-1, |l| l + 1))
         .fetch_all(&pool)
         .await
         .map_err(db_error)?;
 
-    Ok(Json(events))
+    match limit.and_then(|l| next_page_cursor(&mut events, l)) {
+        Some(next) => Ok(([(NEXT_CURSOR_HEADER, next)], Json(events)).into_response()),
+        None => Ok(Json(events).into_response()),
+    }
 }
 
 /// # Errors
@@ -149,32 +272,87 @@ pub async fn create_care_event(
         .ok_or(ApiError::Validation("CARE_EVENT_TYPE_REQUIRED"))?;
 
     validate_event_type(&event_type)?;
+    if let Some(child_name) = &body.child_name {
+        validate_required_name(child_name, "PLANT_NAME_REQUIRED")?;
+    }
+
+    let previous_status = if event_type == "watered" {
+        fetch_plant(&state.pool, plant_id)
+            .await
+            .map(|p| p.watering_status)
+    } else {
+        None
+    };
 
     let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
     let occurred_at = body.occurred_at.unwrap_or_else(|| now.clone());
 
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let child_plant_id = if event_type == "propagated" {
+        let child_name = body.child_name.as_deref().map(str::trim);
+        let child_id = plants::insert_child_plant(&mut tx, plant_id, child_name, &now)
+            .await
+            .map_err(db_error)?;
+        Some(child_id)
+    } else {
+        None
+    };
     let id = sqlx::query_scalar::<_, i64>(
-        "INSERT INTO care_events (plant_id, event_type, notes, occurred_at, created_at) \
-         VALUES (?, ?, ?, ?, ?) RETURNING id",
+        "INSERT INTO care_events (plant_id, event_type, notes, child_plant_id, occurred_at, \
+         created_at) VALUES (?, ?, ?, ?, ?, ?) RETURNING id",
     )
     .bind(plant_id)
     .bind(&event_type)
     .bind(&body.notes)
+    .bind(child_plant_id)
     .bind(&occurred_at)
     .bind(&now)
-    .fetch_one(&state.pool)
+    .fetch_one(&mut *tx)
     .await
     .map_err(db_error)?;
 
-    let query = format!("{CARE_EVENT_SELECT} WHERE ce.id = ?");
+    let snapshot = history::care_event_snapshot(&mut *tx, id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::InternalError("INTERNAL_ERROR"))?;
+    history::record(
+        &mut *tx,
+        ENTITY_CARE_EVENT,
+        id,
+        Some(plant_id),
+        "created",
+        history::created(&snapshot),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    let query = format!("{CARE_EVENT_SELECT} AND ce.id = ?");
     let event = sqlx::query_as::<_, CareEvent>(sqlx::AssertSqlSafe(query.as_str()))
         .bind(id)
         .fetch_one(&state.pool)
         .await
         .map_err(db_error)?;
 
+    state.events.publish(ChangeEvent::CareEventCreated {
+        plant_id,
+        event_id: id,
+    });
     if event_type == "watered" {
-        publish_plant_watering_mqtt(&state, plant_id).await;
+        publish_plant_watering_mqtt(&state, plant_id, previous_status.as_deref()).await;
+    }
+    if let Some(child_id) = child_plant_id
+        && let Some(child) = fetch_plant(&state.pool, child_id).await
+    {
+        info!(
+            plant_id = child_id,
+            parent_id = plant_id,
+            "Plant propagated"
+        );
+        state
+            .events
+            .publish(ChangeEvent::PlantCreated { plant_id: child_id });
+        publish_plant_mqtt(&state, &child).await;
     }
 
     debug!(plant_id, event_type = %event_type, "Care event created");
@@ -188,9 +366,10 @@ pub async fn delete_care_event(
     State(state): State<AppState>,
     Path((plant_id, event_id)): Path<(i64, i64)>,
 ) -> Result<StatusCode, ApiError> {
-    // Read event type + photo before deleting
-    let row = sqlx::query_as::<_, (String, Option<String>)>(
-        "SELECT event_type, photo_path FROM care_events WHERE id = ? AND plant_id = ?",
+    plant_exists(&state.pool, plant_id).await?;
+
+    let event_type = sqlx::query_scalar::<_, String>(
+        "SELECT event_type FROM care_events WHERE id = ? AND plant_id = ? AND deleted_at IS NULL",
     )
     .bind(event_id)
     .bind(plant_id)
@@ -199,30 +378,123 @@ pub async fn delete_care_event(
     .map_err(db_error)?
     .ok_or(ApiError::NotFound("CARE_EVENT_NOT_FOUND"))?;
 
-    let (event_type, photo_path) = row;
+    let previous_status = if event_type == "watered" {
+        fetch_plant(&state.pool, plant_id)
+            .await
+            .map(|p| p.watering_status)
+    } else {
+        None
+    };
 
-    sqlx::query("DELETE FROM care_events WHERE id = ? AND plant_id = ?")
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let snapshot = history::care_event_snapshot(&mut *tx, event_id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("CARE_EVENT_NOT_FOUND"))?;
+    // Move to the trash -- the photo is kept until the event is purged
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    sqlx::query("UPDATE care_events SET deleted_at = ? WHERE id = ? AND plant_id = ?")
+        .bind(&now)
         .bind(event_id)
         .bind(plant_id)
-        .execute(&state.pool)
+        .execute(&mut *tx)
         .await
         .map_err(db_error)?;
+    history::record(
+        &mut *tx,
+        ENTITY_CARE_EVENT,
+        event_id,
+        Some(plant_id),
+        "deleted",
+        history::deleted(&snapshot),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
-    if let Some(filename) = photo_path {
-        state.image_store.delete(&filename).await;
-    }
-
+    state
+        .events
+        .publish(ChangeEvent::CareEventDeleted { plant_id, event_id });
     if event_type == "watered" {
-        publish_plant_watering_mqtt(&state, plant_id).await;
+        publish_plant_watering_mqtt(&state, plant_id, previous_status.as_deref()).await;
     }
 
-    debug!(plant_id, event_id, event_type = %event_type, "Care event deleted");
+    debug!(plant_id, event_id, event_type = %event_type, "Care event moved to trash");
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Check optional `YYYY-MM-DD` bounds, failing with `code` if either is not a date or
+/// `from` is after `to`.
+pub(crate) fn validate_date_range(
+    from: Option<&str>,
+    to: Option<&str>,
+    code: &'static str,
+) -> Result<(), ApiError> {
+    let parse = |date: Option<&str>| {
+        date.map(str::parse::<NaiveDate>)
+            .transpose()
+            .map_err(|_| ApiError::Validation(code))
+    };
+    if let (Some(from), Some(to)) = (parse(from)?, parse(to)?)
+        && from > to
+    {
+        return Err(ApiError::Validation(code));
+    }
+    Ok(())
+}
+
+/// SQL conditions for the global journal filters, in the order their values are bound.
+fn global_care_conditions(
+    params: &GlobalCareQuery,
+    cursor: Option<&Cursor>,
+    fts: Option<&str>,
+) -> Vec<String> {
+    let mut conditions: Vec<String> = Vec::new();
+
+    if cursor.is_some() {
+        conditions.push(AFTER_CURSOR_SQL.to_string());
+    }
+    if !params.event_types.is_empty() {
+        let placeholders: Vec<&str> = params.event_types.iter().map(|_| "?").collect();
+        conditions.push(format!("ce.event_type IN ({})", placeholders.join(", ")));
+    }
+    if params.tag.is_some() {
+        conditions.push(
+            "EXISTS (SELECT 1 FROM plant_tags pt JOIN tags t ON t.id = pt.tag_id \
+             WHERE pt.plant_id = ce.plant_id AND t.name = ?)"
+                .to_string(),
+        );
+    }
+    if !params.plant_ids.is_empty() {
+        let placeholders: Vec<&str> = params.plant_ids.iter().map(|_| "?").collect();
+        conditions.push(format!("ce.plant_id IN ({})", placeholders.join(", ")));
+    }
+    if params.location_id.is_some() {
+        conditions.push("p.location_id = ?".to_string());
+    }
+    if params.from.is_some() {
+        conditions.push("ce.occurred_at >= ?".to_string());
+    }
+    if params.to.is_some() {
+        conditions.push("ce.occurred_at < date(?, '+1 day')".to_string());
+    }
+    match params.has_photo {
+        Some(true) => conditions.push("ce.photo_path IS NOT NULL".to_string()),
+        Some(false) => conditions.push("ce.photo_path IS NULL".to_string()),
+        None => {}
+    }
+    if fts.is_some() {
+        conditions.push(
+            "ce.id IN (SELECT rowid FROM care_events_fts WHERE care_events_fts MATCH ?)"
+                .to_string(),
+        );
+    }
+    conditions
+}
+
 /// # Errors
-/// Returns `ApiError::Validation` if any event type filter is invalid, or
-/// `ApiError::InternalError` on database failures.
+/// Returns `ApiError::Validation` if any event type filter, the date range or the cursor
+/// is invalid, or `ApiError::InternalError` on database failures.
 pub async fn list_all_care_events(
     State(pool): State<SqlitePool>,
     ExtraQuery(params): ExtraQuery<GlobalCareQuery>,
@@ -233,64 +505,135 @@ pub async fn list_all_care_events(
     for event_type in &params.event_types {
         validate_event_type(event_type)?;
     }
+    validate_date_range(
+        params.from.as_deref(),
+        params.to.as_deref(),
+        "CARE_EVENT_INVALID_DATE_RANGE",
+    )?;
+    let cursor = params.cursor.as_deref().map(Cursor::decode).transpose()?;
+    let fts = params.q.as_deref().and_then(search::fts_query);
 
     let mut query = String::from(CARE_EVENT_SELECT);
-    let mut conditions: Vec<String> = Vec::new();
-
-    if params.before.is_some() {
-        conditions.push("ce.id < ?".to_string());
-    }
-    if !params.event_types.is_empty() {
-        let placeholders: Vec<&str> = params.event_types.iter().map(|_| "?").collect();
-        conditions.push(format!("ce.event_type IN ({})", placeholders.join(", ")));
-    }
-
+    let conditions = global_care_conditions(&params, cursor.as_ref(), fts.as_deref());
     if !conditions.is_empty() {
-        query.push_str(" WHERE ");
+        query.push_str(" AND ");
         query.push_str(&conditions.join(" AND "));
     }
 
     query.push_str(" ORDER BY ce.occurred_at DESC, ce.id DESC LIMIT ?");
 
     let mut q = sqlx::query_as::<_, CareEvent>(sqlx::AssertSqlSafe(query.as_str()));
-    if let Some(before) = params.before {
-        q = q.bind(before);
+    if let Some(cursor) = &cursor {
+        q = q.bind(&cursor.occurred_at).bind(cursor.id);
     }
     for event_type in &params.event_types {
         q = q.bind(event_type);
     }
+    if let Some(tag) = &params.tag {
+        q = q.bind(tag);
+    }
+    for plant_id in &params.plant_ids {
+        q = q.bind(plant_id);
+    }
+    if let Some(location_id) = params.location_id {
+        q = q.bind(location_id);
+    }
+    if let Some(from) = &params.from {
+        q = q.bind(from);
+    }
+    if let Some(to) = &params.to {
+        q = q.bind(to);
+    }
+    if let Some(fts) = &fts {
+        q = q.bind(fts);
+    }
     q = q.bind(fetch_count);
 
     let mut events = q.fetch_all(&pool).await.map_err(db_error)?;
 
-    let has_more = events.len() > usize::try_from(limit).unwrap_or(0);
-    if has_more {
-        events.pop();
-    }
+    let next_cursor = next_page_cursor(&mut events, limit);
 
-    Ok(Json(CareEventsPage { events, has_more }))
+    Ok(Json(CareEventsPage {
+        events,
+        has_more: next_cursor.is_some(),
+        next_cursor,
+    }))
 }
 
 // --- Care event photo handlers ---
 
+/// Check that a care event belongs to a plant and neither is in the trash.
+async fn event_exists(pool: &SqlitePool, plant_id: i64, event_id: i64) -> Result<(), ApiError> {
+    plant_exists(pool, plant_id).await?;
+    sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM care_events WHERE id = ? AND plant_id = ? AND deleted_at IS NULL",
+    )
+    .bind(event_id)
+    .bind(plant_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::NotFound("CARE_EVENT_NOT_FOUND"))?;
+    Ok(())
+}
+
+async fn fetch_care_event(pool: &SqlitePool, event_id: i64) -> Result<CareEvent, ApiError> {
+    let query = format!("{CARE_EVENT_SELECT} AND ce.id = ?");
+    sqlx::query_as::<_, CareEvent>(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(event_id)
+        .fetch_one(pool)
+        .await
+        .map_err(db_error)
+}
+
+async fn event_photo_filenames(pool: &SqlitePool, event_id: i64) -> Result<Vec<String>, ApiError> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT filename FROM care_event_photos WHERE care_event_id = ? ORDER BY position, id",
+    )
+    .bind(event_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error)
+}
+
+/// Point `care_events.photo_path` at the event's first photo, or NULL if it has none.
+async fn sync_first_photo(
+    conn: &mut sqlx::SqliteConnection,
+    event_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE care_events SET photo_path = (SELECT filename FROM care_event_photos \
+         WHERE care_event_id = ?1 ORDER BY position, id LIMIT 1) WHERE id = ?1",
+    )
+    .bind(event_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+fn publish_photo_changed(state: &AppState, plant_id: i64, event_id: i64) {
+    state.events.publish(ChangeEvent::PhotoChanged {
+        plant_id,
+        event_id: Some(event_id),
+    });
+}
+
+/// Add a photo to the end of a care event's photos.
+///
 /// # Errors
 /// Returns `ApiError::NotFound` if the care event does not exist,
-/// `ApiError::Validation` for invalid file types or oversized files, or
+/// `ApiError::Validation` for invalid file types, oversized files or too many photos, or
 /// `ApiError::InternalError` on multipart parsing or database failures.
 pub async fn upload_care_event_photo(
     State(state): State<AppState>,
     Path((plant_id, event_id)): Path<(i64, i64)>,
     mut multipart: Multipart,
 ) -> Result<Json<CareEvent>, ApiError> {
-    let current_photo = sqlx::query_scalar::<_, Option<String>>(
-        "SELECT photo_path FROM care_events WHERE id = ? AND plant_id = ?",
-    )
-    .bind(event_id)
-    .bind(plant_id)
-    .fetch_optional(&state.pool)
-    .await
-    .map_err(db_error)?
-    .ok_or(ApiError::NotFound("CARE_EVENT_NOT_FOUND"))?;
+    event_exists(&state.pool, plant_id, event_id).await?;
+    let count = event_photo_filenames(&state.pool, event_id).await?.len();
+    if i64::try_from(count).unwrap_or(i64::MAX) >= MAX_EVENT_PHOTOS {
+        return Err(ApiError::Validation("CARE_EVENT_TOO_MANY_PHOTOS"));
+    }
 
     let field = multipart
         .next_field()
@@ -308,67 +651,145 @@ pub async fn upload_care_event_photo(
         .image_store
         .save(&data, &content_type)
         .await
-        .map_err(|e| match e {
-            ImageError::InvalidContentType => ApiError::Validation("PHOTO_INVALID_TYPE"),
-            ImageError::TooLarge => ApiError::Validation("PHOTO_TOO_LARGE"),
-            ImageError::Io(ref io_err) => {
-                tracing::error!("Photo save failed: {io_err}");
-                ApiError::InternalError("PHOTO_SAVE_FAILED")
-            }
-        })?;
-
-    sqlx::query("UPDATE care_events SET photo_path = ? WHERE id = ? AND plant_id = ?")
-        .bind(&filename)
-        .bind(event_id)
-        .bind(plant_id)
-        .execute(&state.pool)
-        .await
-        .map_err(db_error)?;
+        .map_err(|e| image_error(&e))?;
 
-    if let Some(ref old_filename) = current_photo {
-        state.image_store.delete(old_filename).await;
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let inserted = async {
+        let mut tx = state.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO care_event_photos (care_event_id, filename, position, created_at) \
+             SELECT ?1, ?2, COALESCE(MAX(position) + 1, 0), ?3 \
+             FROM care_event_photos WHERE care_event_id = ?1",
+        )
+        .bind(event_id)
+        .bind(&filename)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+        sync_first_photo(&mut tx, event_id).await?;
+        tx.commit().await
+    }
+    .await;
+    if let Err(e) = inserted {
+        state.image_store.delete(&filename).await;
+        return Err(db_error(e));
     }
 
     info!(plant_id, event_id, filename = %filename, "Care event photo uploaded");
+    publish_photo_changed(&state, plant_id, event_id);
 
-    let query = format!("{CARE_EVENT_SELECT} WHERE ce.id = ?");
-    let event = sqlx::query_as::<_, CareEvent>(sqlx::AssertSqlSafe(query.as_str()))
+    Ok(Json(fetch_care_event(&state.pool, event_id).await?))
+}
+
+/// Delete all photos of a care event.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the care event does not exist or has no photo, or
+/// `ApiError::InternalError` on database failures.
+pub async fn delete_care_event_photo(
+    State(state): State<AppState>,
+    Path((plant_id, event_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, ApiError> {
+    event_exists(&state.pool, plant_id, event_id).await?;
+
+    let filenames = event_photo_filenames(&state.pool, event_id).await?;
+    if filenames.is_empty() {
+        return Err(ApiError::NotFound("PHOTO_NOT_FOUND"));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    sqlx::query("DELETE FROM care_event_photos WHERE care_event_id = ?")
         .bind(event_id)
-        .fetch_one(&state.pool)
+        .execute(&mut *tx)
         .await
         .map_err(db_error)?;
+    sync_first_photo(&mut tx, event_id)
+        .await
+        .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    for filename in &filenames {
+        state.image_store.delete(filename).await;
+    }
 
-    Ok(Json(event))
+    info!(plant_id, event_id, "Care event photos deleted");
+    publish_photo_changed(&state, plant_id, event_id);
+    Ok(StatusCode::NO_CONTENT)
 }
 
+/// Delete one photo of a care event.
+///
 /// # Errors
-/// Returns `ApiError::NotFound` if the care event does not exist or has no photo, or
+/// Returns `ApiError::NotFound` if the care event or photo does not exist, or
 /// `ApiError::InternalError` on database failures.
-pub async fn delete_care_event_photo(
+pub async fn delete_care_event_photo_by_id(
     State(state): State<AppState>,
-    Path((plant_id, event_id)): Path<(i64, i64)>,
+    Path((plant_id, event_id, photo_id)): Path<(i64, i64, i64)>,
 ) -> Result<StatusCode, ApiError> {
-    let photo_path = sqlx::query_scalar::<_, Option<String>>(
-        "SELECT photo_path FROM care_events WHERE id = ? AND plant_id = ?",
+    event_exists(&state.pool, plant_id, event_id).await?;
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let filename = sqlx::query_scalar::<_, String>(
+        "DELETE FROM care_event_photos WHERE id = ? AND care_event_id = ? RETURNING filename",
     )
+    .bind(photo_id)
     .bind(event_id)
-    .bind(plant_id)
-    .fetch_optional(&state.pool)
+    .fetch_optional(&mut *tx)
     .await
     .map_err(db_error)?
-    .ok_or(ApiError::NotFound("CARE_EVENT_NOT_FOUND"))?;
-
-    let filename = photo_path.ok_or(ApiError::NotFound("PHOTO_NOT_FOUND"))?;
-
-    sqlx::query("UPDATE care_events SET photo_path = NULL WHERE id = ? AND plant_id = ?")
-        .bind(event_id)
-        .bind(plant_id)
-        .execute(&state.pool)
+    .ok_or(ApiError::NotFound("PHOTO_NOT_FOUND"))?;
+    sync_first_photo(&mut tx, event_id)
         .await
         .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
     state.image_store.delete(&filename).await;
 
-    info!(plant_id, event_id, "Care event photo deleted");
+    info!(plant_id, event_id, photo_id, "Care event photo deleted");
+    publish_photo_changed(&state, plant_id, event_id);
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// Reorder a care event's photos. `ids` must list every photo of the event exactly once.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the care event does not exist,
+/// `ApiError::Validation` if `ids` does not match the event's photos, or
+/// `ApiError::InternalError` on database failures.
+pub async fn reorder_care_event_photos(
+    State(state): State<AppState>,
+    Path((plant_id, event_id)): Path<(i64, i64)>,
+    JsonBody(body): JsonBody<ReorderCareEventPhotos>,
+) -> Result<Json<CareEvent>, ApiError> {
+    event_exists(&state.pool, plant_id, event_id).await?;
+
+    let mut current =
+        sqlx::query_scalar::<_, i64>("SELECT id FROM care_event_photos WHERE care_event_id = ?")
+            .bind(event_id)
+            .fetch_all(&state.pool)
+            .await
+            .map_err(db_error)?;
+    let mut requested = body.ids.clone();
+    current.sort_unstable();
+    requested.sort_unstable();
+    if current != requested {
+        return Err(ApiError::Validation("PHOTO_ORDER_MISMATCH"));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    for (position, photo_id) in (0_i64..).zip(&body.ids) {
+        sqlx::query("UPDATE care_event_photos SET position = ? WHERE id = ?")
+            .bind(position)
+            .bind(photo_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+    }
+    sync_first_photo(&mut tx, event_id)
+        .await
+        .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    publish_photo_changed(&state, plant_id, event_id);
+    Ok(Json(fetch_care_event(&state.pool, event_id).await?))
+}
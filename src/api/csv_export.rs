@@ -0,0 +1,442 @@
+use axum::body::{Body, Bytes};
+use axum::extract::State;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum_extra::extract::Query as ExtraQuery;
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{error, info};
+
+use super::care_events::{validate_date_range, validate_event_type};
+use super::error::{ApiError, db_error};
+use super::plants::{PLANT_SELECT, Plant, PlantRow};
+
+/// Bytes collected before a chunk is sent to the client.
+const CHUNK_SIZE: usize = 16 * 1024;
+/// Chunks buffered between the query and a slow client.
+const CHANNEL_CHUNKS: usize = 4;
+
+/// A column with its field name and header in English, German and Spanish.
+pub(crate) struct Column {
+    pub(crate) key: &'static str,
+    pub(crate) en: &'static str,
+    pub(crate) de: &'static str,
+    pub(crate) es: &'static str,
+}
+
+const fn column(key: &'static str, en: &'static str, de: &'static str, es: &'static str) -> Column {
+    Column { key, en, de, es }
+}
+
+pub(crate) const PLANT_COLUMNS: &[Column] = &[
+    column("id", "ID", "ID", "ID"),
+    column("name", "Name", "Name", "Nombre"),
+    column("species", "Species", "Art", "Especie"),
+    column("location", "Location", "Standort", "Ubicación"),
+    column(
+        "watering_status",
+        "Watering status",
+        "Gießstatus",
+        "Estado de riego",
+    ),
+    column(
+        "last_watered",
+        "Last watered",
+        "Zuletzt gegossen",
+        "Último riego",
+    ),
+    column(
+        "next_due",
+        "Next due",
+        "Nächstes Mal fällig",
+        "Próximo riego",
+    ),
+    column(
+        "watering_interval_days",
+        "Watering interval (days)",
+        "Gießintervall (Tage)",
+        "Intervalo de riego (días)",
+    ),
+    column(
+        "light_needs",
+        "Light needs",
+        "Lichtbedarf",
+        "Necesidades de luz",
+    ),
+    column("difficulty", "Difficulty", "Schwierigkeit", "Dificultad"),
+    column(
+        "pet_safety",
+        "Pet safety",
+        "Haustiersicherheit",
+        "Seguridad para mascotas",
+    ),
+    column(
+        "growth_speed",
+        "Growth speed",
+        "Wachstumsgeschwindigkeit",
+        "Velocidad de crecimiento",
+    ),
+    column("soil_type", "Soil type", "Bodenart", "Tipo de suelo"),
+    column(
+        "soil_moisture",
+        "Soil moisture",
+        "Bodenfeuchtigkeit",
+        "Humedad del suelo",
+    ),
+    column(
+        "lifecycle_status",
+        "Lifecycle status",
+        "Lebensphase",
+        "Ciclo de vida",
+    ),
+    column("tags", "Tags", "Tags", "Etiquetas"),
+    column("notes", "Notes", "Notizen", "Notas"),
+    column("created_at", "Created", "Erstellt", "Creado"),
+];
+
+const CARE_EVENT_COLUMNS: &[Column] = &[
+    column("id", "ID", "ID", "ID"),
+    column("occurred_at", "Date", "Datum", "Fecha"),
+    column("plant_id", "Plant ID", "Pflanzen-ID", "ID de planta"),
+    column("plant_name", "Plant", "Pflanze", "Planta"),
+    column("event_type", "Type", "Typ", "Tipo"),
+    column("notes", "Notes", "Notizen", "Notas"),
+];
+
+#[derive(Deserialize)]
+pub struct PlantCsvQuery {
+    #[serde(default, rename = "plant_id")]
+    pub plant_ids: Vec<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct CareCsvQuery {
+    /// First day (`YYYY-MM-DD`) of `occurred_at`, inclusive.
+    pub from: Option<String>,
+    /// Last day (`YYYY-MM-DD`) of `occurred_at`, inclusive.
+    pub to: Option<String>,
+    #[serde(default, rename = "plant_id")]
+    pub plant_ids: Vec<i64>,
+    #[serde(default, rename = "type")]
+    pub event_types: Vec<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct CareEventCsvRow {
+    id: i64,
+    occurred_at: String,
+    plant_id: i64,
+    plant_name: String,
+    event_type: String,
+    notes: Option<String>,
+}
+
+/// Quote a field if needed and defuse text a spreadsheet would run as a formula.
+fn push_field(line: &mut String, value: &str, text: bool) {
+    let formula = text && value.starts_with(['=', '+', '-', '@', '\t', '\r']);
+    if formula || value.contains([',', '"', '\n', '\r']) {
+        line.push('"');
+        if formula {
+            line.push('\'');
+        }
+        line.push_str(&value.replace('"', "\"\""));
+        line.push('"');
+    } else {
+        line.push_str(value);
+    }
+}
+
+/// Append a CSV record; `fields` are `(value, is_free_text)` pairs.
+fn push_record(buf: &mut String, fields: &[(&str, bool)]) {
+    for (i, (value, text)) in fields.iter().enumerate() {
+        if i > 0 {
+            buf.push(',');
+        }
+        push_field(buf, value, *text);
+    }
+    buf.push_str("\r\n");
+}
+
+fn header_record(columns: &[Column], locale: &str) -> String {
+    let mut buf = String::from('\u{feff}');
+    let names: Vec<(&str, bool)> = columns
+        .iter()
+        .map(|c| match locale {
+            "de" => (c.de, false),
+            "es" => (c.es, false),
+            _ => (c.en, false),
+        })
+        .collect();
+    push_record(&mut buf, &names);
+    buf
+}
+
+fn plant_record(buf: &mut String, plant: &Plant) {
+    let id = plant.id.to_string();
+    let interval = plant.watering_interval_days.to_string();
+    let tags = plant.tags.join(", ");
+    push_record(
+        buf,
+        &[
+            (&id, false),
+            (&plant.name, true),
+            (plant.species.as_deref().unwrap_or_default(), true),
+            (plant.location_name.as_deref().unwrap_or_default(), true),
+            (&plant.watering_status, false),
+            (plant.last_watered.as_deref().unwrap_or_default(), false),
+            (plant.next_due.as_deref().unwrap_or_default(), false),
+            (&interval, false),
+            (&plant.light_needs, false),
+            (plant.difficulty.as_deref().unwrap_or_default(), false),
+            (plant.pet_safety.as_deref().unwrap_or_default(), false),
+            (plant.growth_speed.as_deref().unwrap_or_default(), false),
+            (plant.soil_type.as_deref().unwrap_or_default(), false),
+            (plant.soil_moisture.as_deref().unwrap_or_default(), false),
+            (&plant.lifecycle_status, false),
+            (&tags, true),
+            (plant.notes.as_deref().unwrap_or_default(), true),
+            (&plant.created_at, false),
+        ],
+    );
+}
+
+fn care_event_record(buf: &mut String, event: &CareEventCsvRow) {
+    let id = event.id.to_string();
+    let plant_id = event.plant_id.to_string();
+    push_record(
+        buf,
+        &[
+            (&id, false),
+            (&event.occurred_at, false),
+            (&plant_id, false),
+            (&event.plant_name, true),
+            (&event.event_type, false),
+            (event.notes.as_deref().unwrap_or_default(), true),
+        ],
+    );
+}
+
+/// Sends CSV text to the response body in chunks.
+struct CsvSink {
+    tx: mpsc::Sender<Result<Bytes, std::io::Error>>,
+    buf: String,
+}
+
+impl CsvSink {
+    /// Send the buffer once it is full. Returns `false` once the client is gone.
+    async fn flush_full(&mut self) -> bool {
+        if self.buf.len() < CHUNK_SIZE {
+            return true;
+        }
+        self.flush().await
+    }
+
+    async fn flush(&mut self) -> bool {
+        let chunk = Bytes::from(std::mem::take(&mut self.buf));
+        self.tx.send(Ok(chunk)).await.is_ok()
+    }
+
+    /// Abort the response so the client sees a failed download, not a truncated file.
+    async fn fail(self, e: &sqlx::Error) {
+        error!("CSV export failed: {e}");
+        let _ = self
+            .tx
+            .send(Err(std::io::Error::other("CSV export failed")))
+            .await;
+    }
+}
+
+/// Start a streamed CSV response whose rows are written by `produce`.
+fn csv_response<F, Fut>(name: &str, header: String, produce: F) -> Response
+where
+    F: FnOnce(CsvSink) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(CHANNEL_CHUNKS);
+    tokio::spawn(produce(CsvSink { tx, buf: header }));
+
+    let today = chrono::Utc::now().date_naive();
+    let disposition = format!("attachment; filename=\"flowl-{name}-{today}.csv\"");
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response()
+}
+
+async fn locale(pool: &SqlitePool) -> Result<String, ApiError> {
+    sqlx::query_scalar::<_, String>("SELECT locale FROM user_settings WHERE id = 1")
+        .fetch_one(pool)
+        .await
+        .map_err(db_error)
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+/// Stream all plants not in the trash as CSV, with location names and watering status.
+///
+/// # Errors
+/// Returns `ApiError::InternalError` if the locale cannot be read; later database failures
+/// abort the download.
+pub async fn export_plants_csv(
+    State(pool): State<SqlitePool>,
+    ExtraQuery(params): ExtraQuery<PlantCsvQuery>,
+) -> Result<Response, ApiError> {
+    let header = header_record(PLANT_COLUMNS, &locale(&pool).await?);
+
+    Ok(csv_response("plants", header, |mut sink| async move {
+        let mut query = String::from(PLANT_SELECT);
+        if !params.plant_ids.is_empty() {
+            query.push_str(" AND p.id IN (");
+            query.push_str(&placeholders(params.plant_ids.len()));
+            query.push(')');
+        }
+        query.push_str(" ORDER BY p.name COLLATE NOCASE, p.id");
+
+        let mut q = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(query));
+        for plant_id in &params.plant_ids {
+            q = q.bind(plant_id);
+        }
+        let mut rows = q.fetch(&pool);
+        let mut count = 0;
+        while let Some(row) = rows.next().await {
+            match row {
+                Ok(row) => plant_record(&mut sink.buf, &Plant::from(row)),
+                Err(e) => return sink.fail(&e).await,
+            }
+            count += 1;
+            if !sink.flush_full().await {
+                return;
+            }
+        }
+        if sink.flush().await {
+            info!(plants = count, "Plant CSV export finished");
+        }
+    }))
+}
+
+/// Stream the care journal as CSV, oldest first, with plant names.
+///
+/// # Errors
+/// Returns `ApiError::Validation` if an event type filter or the date range is invalid,
+/// or `ApiError::InternalError` if the locale cannot be read; later database failures
+/// abort the download.
+pub async fn export_care_events_csv(
+    State(pool): State<SqlitePool>,
+    ExtraQuery(params): ExtraQuery<CareCsvQuery>,
+) -> Result<Response, ApiError> {
+    for event_type in &params.event_types {
+        validate_event_type(event_type)?;
+    }
+    validate_date_range(
+        params.from.as_deref(),
+        params.to.as_deref(),
+        "CARE_EVENT_INVALID_DATE_RANGE",
+    )?;
+    let header = header_record(CARE_EVENT_COLUMNS, &locale(&pool).await?);
+
+    Ok(csv_response(
+        "care-journal",
+        header,
+        |mut sink| async move {
+            let mut query = String::from(
+                "SELECT ce.id, ce.occurred_at, ce.plant_id, p.name AS plant_name, ce.event_type, \
+             ce.notes FROM care_events ce JOIN plants p ON ce.plant_id = p.id \
+             WHERE ce.deleted_at IS NULL AND p.deleted_at IS NULL",
+            );
+            if params.from.is_some() {
+                query.push_str(" AND ce.occurred_at >= ?");
+            }
+            if params.to.is_some() {
+                query.push_str(" AND ce.occurred_at < date(?, '+1 day')");
+            }
+            if !params.plant_ids.is_empty() {
+                query.push_str(" AND ce.plant_id IN (");
+                query.push_str(&placeholders(params.plant_ids.len()));
+                query.push(')');
+            }
+            if !params.event_types.is_empty() {
+                query.push_str(" AND ce.event_type IN (");
+                query.push_str(&placeholders(params.event_types.len()));
+                query.push(')');
+            }
+            query.push_str(" ORDER BY ce.occurred_at, ce.id");
+
+            let mut q = sqlx::query_as::<_, CareEventCsvRow>(sqlx::AssertSqlSafe(query));
+            for date in [&params.from, &params.to].into_iter().flatten() {
+                q = q.bind(date);
+            }
+            for plant_id in &params.plant_ids {
+                q = q.bind(plant_id);
+            }
+            for event_type in &params.event_types {
+                q = q.bind(event_type);
+            }
+            let mut rows = q.fetch(&pool);
+            let mut count = 0;
+            while let Some(row) = rows.next().await {
+                match row {
+                    Ok(event) => care_event_record(&mut sink.buf, &event),
+                    Err(e) => return sink.fail(&e).await,
+                }
+                count += 1;
+                if !sink.flush_full().await {
+                    return;
+                }
+            }
+            if sink.flush().await {
+                info!(care_events = count, "Care journal CSV export finished");
+            }
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[(&str, bool)]) -> String {
+        let mut buf = String::new();
+        push_record(&mut buf, fields);
+        buf
+    }
+
+    #[test]
+    fn plain_fields_are_written_as_is() {
+        assert_eq!(record(&[("1", false), ("Fern", true)]), "1,Fern\r\n");
+        assert_eq!(record(&[("", false), ("", true)]), ",\r\n");
+    }
+
+    #[test]
+    fn fields_with_separators_or_quotes_are_quoted() {
+        assert_eq!(
+            record(&[("a, b", true), ("say \"hi\"", true), ("two\nlines", true)]),
+            "\"a, b\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+    }
+
+    #[test]
+    fn formulas_in_free_text_are_defused() {
+        assert_eq!(record(&[("=SUM(A1)", true)]), "\"'=SUM(A1)\"\r\n");
+        assert_eq!(record(&[("-5", true)]), "\"'-5\"\r\n");
+        // Non-text values such as negative numbers stay untouched
+        assert_eq!(record(&[("-5", false)]), "-5\r\n");
+    }
+
+    #[test]
+    fn headers_follow_the_locale_with_a_bom() {
+        assert_eq!(
+            header_record(CARE_EVENT_COLUMNS, "de"),
+            "\u{feff}ID,Datum,Pflanzen-ID,Pflanze,Typ,Notizen\r\n"
+        );
+        assert!(header_record(CARE_EVENT_COLUMNS, "fr").starts_with("\u{feff}ID,Date,"));
+    }
+}
@@ -0,0 +1,670 @@
+use std::collections::{BTreeMap, HashMap};
+
+use axum::Json;
+use axum::extract::{Multipart, Query, State};
+use axum::http::StatusCode;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use tracing::info;
+
+use super::csv_export::PLANT_COLUMNS;
+use super::error::{ApiError, db_error, default_message};
+use super::history::{self, ENTITY_CARE_EVENT, ENTITY_LOCATION, ENTITY_PLANT};
+use super::plants::{
+    fetch_plant, publish_plant_mqtt, validate_all_care_info, validate_light_needs,
+    validate_required_name, validate_watering_interval,
+};
+use crate::events::ChangeEvent;
+use crate::state::AppState;
+
+/// Most data rows one import may contain.
+const MAX_ROWS: usize = 1000;
+
+/// Plant fields a CSV column can be mapped to.
+const FIELDS: &[&str] = &[
+    "name",
+    "species",
+    "location",
+    "watering_interval_days",
+    "light_needs",
+    "difficulty",
+    "pet_safety",
+    "growth_speed",
+    "soil_type",
+    "soil_moisture",
+    "notes",
+    "last_watered",
+];
+
+#[derive(Deserialize)]
+pub struct CsvImportQuery {
+    /// Validate and report without creating anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Import the valid rows even if others are invalid.
+    #[serde(default)]
+    pub skip_invalid: bool,
+}
+
+#[derive(Serialize)]
+pub struct RowError {
+    /// Spreadsheet row number; the header is row 1.
+    pub row: usize,
+    pub field: &'static str,
+    pub code: &'static str,
+    pub message: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct CsvImportReport {
+    pub dry_run: bool,
+    /// The column each field is read from.
+    pub columns: BTreeMap<&'static str, String>,
+    /// Data rows in the file, not counting blank lines.
+    pub rows: usize,
+    pub valid_rows: usize,
+    /// Locations that are (or, in a dry run, would be) created, in order of first use.
+    pub new_locations: Vec<String>,
+    /// Plants created by the import; empty for a dry run.
+    pub created_plant_ids: Vec<i64>,
+    pub errors: Vec<RowError>,
+}
+
+/// A validated row, ready to be inserted.
+struct PlantDraft {
+    name: String,
+    species: Option<String>,
+    location: Option<String>,
+    watering_interval_days: i64,
+    light_needs: String,
+    difficulty: Option<String>,
+    pet_safety: Option<String>,
+    growth_speed: Option<String>,
+    soil_type: Option<String>,
+    soil_moisture: Option<String>,
+    notes: Option<String>,
+    /// `occurred_at` of the initial watering.
+    last_watered: Option<String>,
+}
+
+/// Split CSV text into records. The delimiter -- comma, semicolon or tab -- is the one
+/// that occurs most often in the first line. Returns `None` for an unterminated quote.
+fn parse_csv(text: &str) -> Option<Vec<Vec<String>>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let first_line = text.lines().next().unwrap_or_default();
+    let delimiter = [',', ';', '\t']
+        .into_iter()
+        .rev()
+        .max_by_key(|d| first_line.matches(*d).count())
+        .unwrap_or(',');
+
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                chars.next();
+                field.push('"');
+            } else {
+                quoted = false;
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Some(records)
+}
+
+/// Whether a header names `field`, either by the field name itself or by the header
+/// the CSV export uses for it in any language.
+fn header_matches(header: &str, field: &str) -> bool {
+    let header = header.trim().to_lowercase();
+    header == field
+        || PLANT_COLUMNS.iter().any(|c| {
+            c.key == field
+                && [c.en, c.de, c.es]
+                    .iter()
+                    .any(|name| name.to_lowercase() == header)
+        })
+}
+
+/// Find the column of every field: explicitly mapped ones by their exact header, the
+/// others by [`header_matches`]. An empty mapping skips the field.
+fn resolve_columns(
+    header: &[String],
+    mapping: &BTreeMap<String, String>,
+) -> Result<BTreeMap<&'static str, usize>, ApiError> {
+    let invalid = || ApiError::Validation("IMPORT_CSV_INVALID_MAPPING");
+    if mapping
+        .keys()
+        .any(|field| !FIELDS.contains(&field.as_str()))
+    {
+        return Err(invalid());
+    }
+
+    let mut columns = BTreeMap::new();
+    for &field in FIELDS {
+        let index = match mapping.get(field).map(|name| name.trim()) {
+            Some("") => None,
+            Some(name) => Some(
+                header
+                    .iter()
+                    .position(|h| h.trim() == name)
+                    .ok_or_else(invalid)?,
+            ),
+            None => header.iter().position(|h| header_matches(h, field)),
+        };
+        if let Some(index) = index {
+            columns.insert(field, index);
+        }
+    }
+    if !columns.contains_key("name") {
+        return Err(ApiError::Validation("IMPORT_CSV_NAME_COLUMN_REQUIRED"));
+    }
+    Ok(columns)
+}
+
+/// Parse a last-watered date or timestamp into an `occurred_at` value. Dates are placed
+/// at noon UTC so they fall on the same calendar day in most zones.
+fn parse_last_watered(value: &str, today: NaiveDate) -> Option<String> {
+    let (date, occurred_at) = if let Ok(date) = value.parse::<NaiveDate>() {
+        (date, format!("{date}T12:00:00Z"))
+    } else {
+        let at = DateTime::parse_from_rfc3339(value)
+            .ok()?
+            .with_timezone(&Utc);
+        (
+            at.date_naive(),
+            at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        )
+    };
+    (date <= today).then_some(occurred_at)
+}
+
+/// Validate one record, collecting an error for every invalid field.
+fn validate_record(
+    record: &[String],
+    columns: &BTreeMap<&'static str, usize>,
+    row: usize,
+    today: NaiveDate,
+) -> Result<PlantDraft, Vec<RowError>> {
+    let value = |field: &str| {
+        columns
+            .get(field)
+            .and_then(|&i| record.get(i))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    };
+    let choice = |field: &str| value(field).map(str::to_lowercase);
+    let mut errors = Vec::new();
+    let mut check = |field: &'static str, result: Result<(), ApiError>| {
+        if let Err(e) = result {
+            errors.push(RowError {
+                row,
+                field,
+                code: e.code(),
+                message: default_message(e.code()),
+            });
+        }
+    };
+
+    let name = value("name").unwrap_or_default().to_string();
+    check("name", validate_required_name(&name, "PLANT_NAME_REQUIRED"));
+    let watering_interval_days = match value("watering_interval_days") {
+        None => 7,
+        Some(days) => days.parse().unwrap_or(0),
+    };
+    check(
+        "watering_interval_days",
+        validate_watering_interval(watering_interval_days),
+    );
+    let light_needs = choice("light_needs").unwrap_or_else(|| "indirect".to_string());
+    check("light_needs", validate_light_needs(&light_needs));
+    let difficulty = choice("difficulty");
+    let pet_safety = choice("pet_safety");
+    let growth_speed = choice("growth_speed");
+    let soil_type = choice("soil_type");
+    let soil_moisture = choice("soil_moisture");
+    // One field at a time, so that every invalid one is reported
+    let care = validate_all_care_info;
+    check(
+        "difficulty",
+        care(difficulty.as_deref(), None, None, None, None),
+    );
+    check(
+        "pet_safety",
+        care(None, pet_safety.as_deref(), None, None, None),
+    );
+    check(
+        "growth_speed",
+        care(None, None, growth_speed.as_deref(), None, None),
+    );
+    check(
+        "soil_type",
+        care(None, None, None, soil_type.as_deref(), None),
+    );
+    check(
+        "soil_moisture",
+        care(None, None, None, None, soil_moisture.as_deref()),
+    );
+    let last_watered = value("last_watered").map(|v| parse_last_watered(v, today));
+    if let Some(None) = last_watered {
+        check(
+            "last_watered",
+            Err(ApiError::Validation("IMPORT_CSV_INVALID_DATE")),
+        );
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(PlantDraft {
+        name,
+        species: value("species").map(String::from),
+        location: value("location").map(String::from),
+        watering_interval_days,
+        light_needs,
+        difficulty,
+        pet_safety,
+        growth_speed,
+        soil_type,
+        soil_moisture,
+        notes: value("notes").map(String::from),
+        last_watered: last_watered.flatten(),
+    })
+}
+
+/// Read the `file` and optional `mapping` (JSON object of field to column header) parts.
+async fn read_upload(
+    multipart: &mut Multipart,
+) -> Result<(String, BTreeMap<String, String>), ApiError> {
+    let bad_body = |_| ApiError::BadRequest("INVALID_REQUEST_BODY");
+    let mut file = None;
+    let mut mapping = BTreeMap::new();
+    while let Some(field) = multipart.next_field().await.map_err(bad_body)? {
+        match field.name() {
+            Some("file") => {
+                let bytes = field.bytes().await.map_err(bad_body)?;
+                let text = String::from_utf8(bytes.to_vec())
+                    .map_err(|_| ApiError::BadRequest("IMPORT_CSV_INVALID"))?;
+                file = Some(text);
+            }
+            Some("mapping") => {
+                let text = field.text().await.map_err(bad_body)?;
+                mapping = serde_json::from_str(&text)
+                    .map_err(|_| ApiError::Validation("IMPORT_CSV_INVALID_MAPPING"))?;
+            }
+            _ => {}
+        }
+    }
+    let file = file.ok_or(ApiError::BadRequest("IMPORT_NO_FILE"))?;
+    Ok((file, mapping))
+}
+
+async fn insert_location(conn: &mut SqliteConnection, name: &str) -> Result<i64, sqlx::Error> {
+    let id = sqlx::query_scalar::<_, i64>("INSERT INTO locations (name) VALUES (?) RETURNING id")
+        .bind(name)
+        .fetch_one(&mut *conn)
+        .await?;
+    history::record(
+        &mut *conn,
+        ENTITY_LOCATION,
+        id,
+        None,
+        "created",
+        history::created(&serde_json::json!({ "name": name })),
+    )
+    .await?;
+    Ok(id)
+}
+
+/// Insert a plant and its initial watering, recording both in the history.
+async fn insert_plant(
+    conn: &mut SqliteConnection,
+    draft: &PlantDraft,
+    location_id: Option<i64>,
+    now: &str,
+) -> Result<i64, sqlx::Error> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO plants (name, species, location_id, watering_interval_days, light_needs, \
+         difficulty, pet_safety, growth_speed, soil_type, soil_moisture, notes, \
+         created_at, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+    )
+    .bind(&draft.name)
+    .bind(&draft.species)
+    .bind(location_id)
+    .bind(draft.watering_interval_days)
+    .bind(&draft.light_needs)
+    .bind(&draft.difficulty)
+    .bind(&draft.pet_safety)
+    .bind(&draft.growth_speed)
+    .bind(&draft.soil_type)
+    .bind(&draft.soil_moisture)
+    .bind(&draft.notes)
+    .bind(now)
+    .bind(now)
+    .fetch_one(&mut *conn)
+    .await?;
+    if let Some(snapshot) = history::plant_snapshot(&mut *conn, id).await? {
+        history::record(
+            &mut *conn,
+            ENTITY_PLANT,
+            id,
+            Some(id),
+            "created",
+            history::created(&snapshot),
+        )
+        .await?;
+    }
+
+    if let Some(occurred_at) = &draft.last_watered {
+        let event_id = sqlx::query_scalar::<_, i64>(
+            "INSERT INTO care_events (plant_id, event_type, occurred_at, created_at) \
+             VALUES (?, 'watered', ?, ?) RETURNING id",
+        )
+        .bind(id)
+        .bind(occurred_at)
+        .bind(now)
+        .fetch_one(&mut *conn)
+        .await?;
+        if let Some(snapshot) = history::care_event_snapshot(&mut *conn, event_id).await? {
+            history::record(
+                &mut *conn,
+                ENTITY_CARE_EVENT,
+                event_id,
+                Some(id),
+                "created",
+                history::created(&snapshot),
+            )
+            .await?;
+        }
+    }
+    Ok(id)
+}
+
+/// Locations used by the drafts that do not exist yet, in order of first use.
+fn new_locations(drafts: &[PlantDraft], existing: &HashMap<String, i64>) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in drafts.iter().filter_map(|d| d.location.as_ref()) {
+        if !existing.contains_key(name) && !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    names
+}
+
+/// Create plants from a spreadsheet. Columns are matched to fields by the optional
+/// `mapping` or by their headers; every row is validated like a new plant. With
+/// `dry_run` nothing is written. Otherwise missing locations are created and the plants
+/// inserted in one transaction, each with a watering on its last-watered date.
+///
+/// # Errors
+/// Returns `ApiError::BadRequest` for a missing or unreadable file,
+/// `ApiError::Validation` for an invalid mapping, too many rows, or invalid rows when
+/// committing without `skip_invalid`, or `ApiError::InternalError` on database failures.
+pub async fn import_plants_csv(
+    State(state): State<AppState>,
+    Query(params): Query<CsvImportQuery>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<CsvImportReport>), ApiError> {
+    let (text, mapping) = read_upload(&mut multipart).await?;
+    let records = parse_csv(&text).ok_or(ApiError::BadRequest("IMPORT_CSV_INVALID"))?;
+    let (header, records) = records
+        .split_first()
+        .ok_or(ApiError::BadRequest("IMPORT_CSV_INVALID"))?;
+    let columns = resolve_columns(header, &mapping)?;
+
+    // Spreadsheet rows: the header is row 1 and blank lines keep their number
+    let rows: Vec<(usize, &Vec<String>)> = records
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| record.iter().any(|v| !v.trim().is_empty()))
+        .map(|(i, record)| (i + 2, record))
+        .collect();
+    if rows.len() > MAX_ROWS {
+        return Err(ApiError::Validation("IMPORT_CSV_TOO_MANY_ROWS"));
+    }
+
+    let today = Utc::now().date_naive();
+    let mut drafts = Vec::new();
+    let mut errors = Vec::new();
+    for (row, record) in &rows {
+        match validate_record(record, &columns, *row, today) {
+            Ok(draft) => drafts.push(draft),
+            Err(row_errors) => errors.extend(row_errors),
+        }
+    }
+
+    let mut report = CsvImportReport {
+        dry_run: params.dry_run,
+        columns: columns
+            .iter()
+            .map(|(&field, &i)| (field, header[i].trim().to_string()))
+            .collect(),
+        rows: rows.len(),
+        valid_rows: drafts.len(),
+        new_locations: Vec::new(),
+        created_plant_ids: Vec::new(),
+        errors,
+    };
+    if !params.dry_run && !params.skip_invalid && !report.errors.is_empty() {
+        return Err(ApiError::Validation("IMPORT_CSV_INVALID_ROWS"));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let mut location_ids: HashMap<String, i64> =
+        sqlx::query_as::<_, (String, i64)>("SELECT name, id FROM locations")
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .collect();
+    report.new_locations = new_locations(&drafts, &location_ids);
+    if params.dry_run {
+        return Ok((StatusCode::OK, Json(report)));
+    }
+
+    let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    for name in &report.new_locations {
+        let id = insert_location(&mut tx, name).await.map_err(db_error)?;
+        location_ids.insert(name.clone(), id);
+    }
+    for draft in &drafts {
+        let location_id = draft.location.as_ref().map(|name| location_ids[name]);
+        let id = insert_plant(&mut tx, draft, location_id, &now)
+            .await
+            .map_err(db_error)?;
+        report.created_plant_ids.push(id);
+    }
+    tx.commit().await.map_err(db_error)?;
+
+    info!(
+        plants = report.created_plant_ids.len(),
+        locations = report.new_locations.len(),
+        skipped_rows = report.rows - report.valid_rows,
+        "Plant CSV import finished"
+    );
+    for &id in &report.created_plant_ids {
+        state
+            .events
+            .publish(ChangeEvent::PlantCreated { plant_id: id });
+        if let Ok(Some(plant)) = fetch_plant(&state.pool, id).await {
+            publish_plant_mqtt(&state, &plant).await;
+        }
+    }
+
+    Ok((StatusCode::CREATED, Json(report)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    fn columns(header: &[&str]) -> BTreeMap<&'static str, usize> {
+        resolve_columns(&strings(header), &BTreeMap::new())
+            .ok()
+            .unwrap()
+    }
+
+    fn today() -> NaiveDate {
+        "2026-06-15".parse().unwrap()
+    }
+
+    #[test]
+    fn csv_quotes_line_breaks_and_bom_are_parsed() {
+        let records =
+            parse_csv("\u{feff}name,notes\r\nFern,\"Mist, \"\"daily\"\"\nor not\"\nIvy,\n")
+                .unwrap();
+        assert_eq!(
+            records,
+            vec![
+                strings(&["name", "notes"]),
+                strings(&["Fern", "Mist, \"daily\"\nor not"]),
+                strings(&["Ivy", ""]),
+            ]
+        );
+        assert!(parse_csv("name\n\"unterminated").is_none());
+    }
+
+    #[test]
+    fn csv_delimiter_is_detected_from_the_first_line() {
+        let records = parse_csv("Name;Standort\nFarn;Küche, Fenster").unwrap();
+        assert_eq!(records[1], strings(&["Farn", "Küche, Fenster"]));
+        let records = parse_csv("name\tspecies\nFern\tNephrolepis").unwrap();
+        assert_eq!(records[1], strings(&["Fern", "Nephrolepis"]));
+    }
+
+    #[test]
+    fn headers_match_field_names_and_export_headers() {
+        let columns = columns(&["Name", "Standort", "Gießintervall (Tage)", "notes", "Other"]);
+        assert_eq!(columns["name"], 0);
+        assert_eq!(columns["location"], 1);
+        assert_eq!(columns["watering_interval_days"], 2);
+        assert_eq!(columns["notes"], 3);
+        assert_eq!(columns.len(), 4);
+    }
+
+    #[test]
+    fn explicit_mapping_overrides_and_is_validated() {
+        let header = strings(&["Plant", "Name", "Room"]);
+        let mapping = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect()
+        };
+        let columns = resolve_columns(
+            &header,
+            &mapping(&[("name", "Plant"), ("location", "Room")]),
+        )
+        .ok()
+        .unwrap();
+        assert_eq!((columns["name"], columns["location"]), (0, 2));
+
+        let err = |m| resolve_columns(&header, &m).err().map(|e| e.code());
+        assert_eq!(
+            err(mapping(&[("height", "Plant")])),
+            Some("IMPORT_CSV_INVALID_MAPPING")
+        );
+        assert_eq!(
+            err(mapping(&[("name", "Title")])),
+            Some("IMPORT_CSV_INVALID_MAPPING")
+        );
+        assert_eq!(
+            err(mapping(&[("name", "")])),
+            Some("IMPORT_CSV_NAME_COLUMN_REQUIRED")
+        );
+    }
+
+    #[test]
+    fn valid_record_becomes_a_draft_with_defaults() {
+        let columns = columns(&["name", "light_needs", "difficulty", "last_watered"]);
+        let draft = validate_record(
+            &strings(&[" Fern ", "Low", "", "2026-06-01"]),
+            &columns,
+            2,
+            today(),
+        )
+        .ok()
+        .unwrap();
+        assert_eq!(draft.name, "Fern");
+        assert_eq!(draft.watering_interval_days, 7);
+        assert_eq!(draft.light_needs, "low");
+        assert_eq!(draft.difficulty, None);
+        assert_eq!(draft.last_watered.as_deref(), Some("2026-06-01T12:00:00Z"));
+    }
+
+    #[test]
+    fn every_invalid_field_is_reported() {
+        let columns = columns(&[
+            "name",
+            "watering_interval_days",
+            "pet_safety",
+            "soil_type",
+            "last_watered",
+        ]);
+        let errors = validate_record(
+            &strings(&["", "0", "maybe", "sand", "2026-07-01"]),
+            &columns,
+            5,
+            today(),
+        )
+        .err()
+        .unwrap();
+        let fields: Vec<(usize, &str, &str)> =
+            errors.iter().map(|e| (e.row, e.field, e.code)).collect();
+        assert_eq!(
+            fields,
+            [
+                (5, "name", "PLANT_NAME_REQUIRED"),
+                (
+                    5,
+                    "watering_interval_days",
+                    "PLANT_INVALID_WATERING_INTERVAL"
+                ),
+                (5, "pet_safety", "PLANT_INVALID_PET_SAFETY"),
+                (5, "soil_type", "PLANT_INVALID_SOIL_TYPE"),
+                (5, "last_watered", "IMPORT_CSV_INVALID_DATE"),
+            ]
+        );
+    }
+
+    #[test]
+    fn last_watered_accepts_dates_and_timestamps() {
+        assert_eq!(
+            parse_last_watered("2026-06-10T08:30:00+02:00", today()).as_deref(),
+            Some("2026-06-10T06:30:00Z")
+        );
+        assert_eq!(parse_last_watered("10.06.2026", today()), None);
+        assert_eq!(parse_last_watered("2026-06-16", today()), None);
+    }
+}
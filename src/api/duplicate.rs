@@ -0,0 +1,208 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+use sqlx::SqliteConnection;
+
+use tracing::info;
+
+use super::error::{ApiError, JsonBody, db_error};
+use super::history::{self, ENTITY_PLANT};
+use super::photos::{image_error, insert_photo};
+use super::plants::{Plant, fetch_plant, publish_plant_mqtt, validate_required_name};
+use crate::events::ChangeEvent;
+use crate::state::AppState;
+
+/// Most copies created by one request.
+const MAX_COPIES: i64 = 20;
+
+#[derive(Deserialize)]
+pub struct DuplicatePlant {
+    /// Number of copies, 1 by default.
+    pub count: Option<i64>,
+    /// Name of the copies, defaults to the source plant's name.
+    pub name: Option<String>,
+    /// Append " 1", " 2", ... to the names of the copies.
+    #[serde(default)]
+    pub numbered: bool,
+    /// Give every copy its own copy of the source plant's photo.
+    #[serde(default)]
+    pub copy_photo: bool,
+}
+
+fn copy_names(base: &str, count: i64, numbered: bool) -> Vec<String> {
+    (1..=count)
+        .map(|n| {
+            if numbered {
+                format!("{base} {n}")
+            } else {
+                base.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Insert a copy of the source plant's profile and tags, without journal or lineage.
+async fn insert_copy(
+    conn: &mut SqliteConnection,
+    source_id: i64,
+    name: &str,
+    photo_path: Option<&str>,
+    now: &str,
+) -> Result<i64, sqlx::Error> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO plants (name, species, icon, photo_path, location_id, watering_interval_days, \
+         light_needs, difficulty, pet_safety, growth_speed, soil_type, soil_moisture, notes, \
+         created_at, updated_at) \
+         SELECT ?1, species, icon, ?2, location_id, watering_interval_days, light_needs, \
+         difficulty, pet_safety, growth_speed, soil_type, soil_moisture, notes, ?3, ?3 \
+         FROM plants WHERE id = ?4 RETURNING id",
+    )
+    .bind(name)
+    .bind(photo_path)
+    .bind(now)
+    .bind(source_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query("INSERT INTO plant_tags (plant_id, tag_id) SELECT ?, tag_id FROM plant_tags WHERE plant_id = ?")
+        .bind(id)
+        .bind(source_id)
+        .execute(&mut *conn)
+        .await?;
+    if let Some(photo_path) = photo_path {
+        insert_photo(&mut *conn, id, photo_path, None, None, now).await?;
+    }
+
+    if let Some(snapshot) = history::plant_snapshot(&mut *conn, id).await? {
+        history::record(
+            &mut *conn,
+            ENTITY_PLANT,
+            id,
+            Some(id),
+            "created",
+            history::created(&snapshot),
+        )
+        .await?;
+    }
+    Ok(id)
+}
+
+/// Save one copy of the source cover photo per new plant through the image store.
+async fn copy_photos(
+    state: &AppState,
+    photo_path: &str,
+    count: usize,
+) -> Result<Vec<String>, ApiError> {
+    let data = tokio::fs::read(state.image_store.upload_dir().join(photo_path))
+        .await
+        .map_err(|e| {
+            tracing::error!("Photo read failed: {e}");
+            ApiError::InternalError("PHOTO_SAVE_FAILED")
+        })?;
+
+    let mut filenames = Vec::with_capacity(count);
+    for _ in 0..count {
+        match state.image_store.save(&data, "").await {
+            Ok(filename) => filenames.push(filename),
+            Err(e) => {
+                for filename in &filenames {
+                    state.image_store.delete(filename).await;
+                }
+                return Err(image_error(&e));
+            }
+        }
+    }
+    Ok(filenames)
+}
+
+/// Create copies of a plant's profile -- species, icon, location, care info, notes and
+/// tags -- without its care journal, history or lineage.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist,
+/// `ApiError::Validation` if the count or name is invalid, or
+/// `ApiError::InternalError` on database or photo failures.
+pub async fn duplicate_plant(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    JsonBody(body): JsonBody<DuplicatePlant>,
+) -> Result<(StatusCode, Json<Vec<Plant>>), ApiError> {
+    let source = fetch_plant(&state.pool, id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+
+    let count = body.count.unwrap_or(1);
+    if !(1..=MAX_COPIES).contains(&count) {
+        return Err(ApiError::Validation("PLANT_INVALID_COPY_COUNT"));
+    }
+    let base = match body.name {
+        Some(name) => {
+            validate_required_name(&name, "PLANT_NAME_REQUIRED")?;
+            name.trim().to_string()
+        }
+        None => source.name.clone(),
+    };
+    let names = copy_names(&base, count, body.numbered);
+
+    let source_photo =
+        sqlx::query_scalar::<_, Option<String>>("SELECT photo_path FROM plants WHERE id = ?")
+            .bind(id)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(db_error)?;
+    let photos = match source_photo.as_deref() {
+        Some(photo_path) if body.copy_photo => copy_photos(&state, photo_path, names.len()).await?,
+        _ => Vec::new(),
+    };
+
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let inserted = async {
+        let mut tx = state.pool.begin().await?;
+        let mut ids = Vec::with_capacity(names.len());
+        for (i, name) in names.iter().enumerate() {
+            let photo = photos.get(i).map(String::as_str);
+            ids.push(insert_copy(&mut tx, id, name, photo, &now).await?);
+        }
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(ids)
+    }
+    .await;
+    let ids = match inserted {
+        Ok(ids) => ids,
+        Err(e) => {
+            for filename in &photos {
+                state.image_store.delete(filename).await;
+            }
+            return Err(db_error(e));
+        }
+    };
+
+    let mut plants = Vec::with_capacity(ids.len());
+    for copy_id in ids {
+        let plant = fetch_plant(&state.pool, copy_id)
+            .await
+            .map_err(db_error)?
+            .ok_or(ApiError::InternalError("INTERNAL_ERROR"))?;
+        state
+            .events
+            .publish(ChangeEvent::PlantCreated { plant_id: copy_id });
+        publish_plant_mqtt(&state, &plant).await;
+        plants.push(plant);
+    }
+
+    info!(plant_id = id, copies = plants.len(), "Plant duplicated");
+    Ok((StatusCode::CREATED, Json(plants)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_names_are_numbered_on_request() {
+        assert_eq!(copy_names("Fern", 2, false), ["Fern", "Fern"]);
+        assert_eq!(copy_names("Fern", 3, true), ["Fern 1", "Fern 2", "Fern 3"]);
+    }
+}
@@ -30,6 +30,20 @@ pub enum ApiError {
     TooManyRequests(&'static str),
 }
 
+impl ApiError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(c)
+            | Self::Validation(c)
+            | Self::Conflict(c)
+            | Self::BadRequest(c)
+            | Self::ServiceUnavailable(c)
+            | Self::InternalError(c)
+            | Self::TooManyRequests(c) => c,
+        }
+    }
+}
+
 #[allow(clippy::needless_pass_by_value)]
 pub fn db_error(e: sqlx::Error) -> ApiError {
     tracing::error!("Database error: {e}");
@@ -52,35 +66,88 @@ pub fn default_message(code: &str) -> &'static str {
         "PLANT_INVALID_SOIL_TYPE" => "Invalid value for soil_type",
         "PLANT_INVALID_SOIL_MOISTURE" => "Invalid value for soil_moisture",
         "PLANT_INVALID_WATERING_INTERVAL" => "Watering interval must be between 1 and 365 days",
+        "PLANT_INVALID_LIFECYCLE_STATUS" => "Invalid value for lifecycle_status",
+        "PLANT_INVALID_LIFECYCLE_DATE" => "lifecycle_date must be a date (YYYY-MM-DD)",
+        "PLANT_INVALID_PARENT" => "Parent plant does not exist or descends from this plant",
+        "PLANT_INVALID_WATERING_STATUS" => "Invalid value for status",
+        "PLANT_INVALID_SORT" => "Invalid sort field or order",
+        "PLANT_INVALID_PAGINATION" => "limit must be between 1 and 500 and offset at least 0",
+        "PLANT_BULK_IDS_REQUIRED" => "At least one plant id is required",
+        "PLANT_INVALID_COPY_COUNT" => "Number of copies must be between 1 and 20",
 
         // Care events
         "CARE_EVENT_NOT_FOUND" => "Care event not found",
         "CARE_EVENT_TYPE_REQUIRED" => "Event type is required",
         "CARE_EVENT_INVALID_TYPE" => "Invalid event type",
+        "CARE_EVENT_INVALID_DATE_RANGE" | "MEASUREMENT_INVALID_DATE_RANGE" => {
+            "from/to must be YYYY-MM-DD dates, from not after to"
+        }
+        "CARE_EVENT_INVALID_CURSOR" => "Invalid cursor",
+        "CARE_EVENT_TOO_MANY_PHOTOS" => "A care event can have at most 10 photos",
+
+        // Measurements
+        "MEASUREMENT_NOT_FOUND" => "Measurement not found",
+        "MEASUREMENT_METRIC_REQUIRED" => "Metric is required",
+        "MEASUREMENT_VALUE_REQUIRED" => "Value is required",
+        "MEASUREMENT_INVALID_METRIC" => "Invalid measurement metric",
+        "MEASUREMENT_INVALID_VALUE" => "Value must be a non-negative number, whole for counts",
+        "MEASUREMENT_INVALID_DATE" => "measured_at must be an RFC 3339 timestamp",
+        "MEASUREMENT_INVALID_CARE_EVENT" => "Care event not found for this plant",
 
         // Locations
         "LOCATION_NOT_FOUND" => "Location not found",
         "LOCATION_NAME_REQUIRED" => "Location name is required",
         "LOCATION_ALREADY_EXISTS" => "A location with this name already exists",
 
+        // Tags
+        "TAG_NOT_FOUND" => "Tag not found",
+        "TAG_NAME_REQUIRED" => "Tag name is required",
+        "TAG_ALREADY_EXISTS" => "A tag with this name already exists",
+
+        // History
+        "HISTORY_ENTRY_NOT_FOUND" => "History entry not found",
+
+        // Stats
+        "STATS_INVALID_DATE_RANGE" | "HEATMAP_INVALID_DATE_RANGE" => {
+            "from/to must be YYYY-MM-DD dates at most 10 years apart"
+        }
+
+        // Search
+        "SEARCH_QUERY_REQUIRED" => "Search query is required",
+
         // Photos
         "PHOTO_NOT_FOUND" => "Photo not found",
         "PHOTO_NO_FILE" | "IMPORT_NO_FILE" => "No file provided",
         "PHOTO_INVALID_TYPE" => "Invalid image type",
         "PHOTO_TOO_LARGE" => "File is too large",
         "PHOTO_SAVE_FAILED" => "Failed to save photo",
+        "PHOTO_INVALID_TAKEN_AT" => "Taken-at date must be YYYY-MM-DD",
+        "PHOTO_ORDER_MISMATCH" => "Photo order must list every photo of the plant once",
+        "TIMELAPSE_NO_PHOTOS" => "The plant has no photos for a time-lapse",
 
         // Settings
         "SETTINGS_INVALID_THEME" => "Invalid theme value",
         "SETTINGS_INVALID_LOCALE" => "Invalid locale value",
+        "SETTINGS_INVALID_TIME_ZONE" => "Time zone must be an IANA name such as Europe/Berlin",
 
         // Import
         "IMPORT_INVALID_ARCHIVE" => "Invalid ZIP archive",
         "IMPORT_INVALID_DATA" => "Invalid import data",
-        "IMPORT_VERSION_MISMATCH" => "Incompatible export version",
+        "IMPORT_FORMAT_TOO_NEW" => "Backup is from a newer version; update to restore it",
         "IMPORT_INVALID_FILENAME" => "Invalid filename in archive",
         "IMPORT_FILE_TOO_LARGE" => "File in archive is too large",
         "IMPORT_VALIDATION_FAILED" => "Import data validation failed",
+        "IMPORT_INVALID_MODE" => "Import mode must be replace or merge",
+        "IMPORT_CSV_INVALID" => "Invalid CSV file",
+        "IMPORT_CSV_TOO_MANY_ROWS" => "A CSV import can have at most 1000 rows",
+        "IMPORT_CSV_INVALID_MAPPING" => "Column mapping names an unknown field or column",
+        "IMPORT_CSV_NAME_COLUMN_REQUIRED" => "No column is mapped to the plant name",
+        "IMPORT_CSV_INVALID_ROWS" => "Some rows are invalid; fix them or skip invalid rows",
+        "IMPORT_CSV_INVALID_DATE" => "Last watered must be a YYYY-MM-DD date, not in the future",
+
+        // Backups
+        "BACKUP_NOT_FOUND" => "Backup not found",
+        "BACKUP_FAILED" => "Backup could not be written",
 
         // AI
         "AI_NOT_CONFIGURED" => "AI provider is not configured",
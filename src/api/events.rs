@@ -0,0 +1,39 @@
+use std::convert::Infallible;
+
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::{Stream, StreamExt};
+use tracing::debug;
+
+use crate::events::ChangeEvent;
+use crate::state::AppState;
+
+/// Stream collection changes to the client as Server-Sent Events.
+///
+/// Each event uses the change name as the SSE event type and the JSON-encoded change as data.
+/// Clients that fall too far behind receive a `resync` event and should reload everything.
+pub async fn stream_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    debug!("Change feed client connected");
+
+    let stream = BroadcastStream::new(state.events.subscribe()).map(|result| {
+        let event = match result {
+            Ok(change) => Event::default()
+                .event(change.name())
+                .data(serde_json::to_string(&change).unwrap_or_default()),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                debug!(skipped, "Change feed client lagged, requesting resync");
+                let change = ChangeEvent::Resync;
+                Event::default()
+                    .event(change.name())
+                    .data(serde_json::to_string(&change).unwrap_or_default())
+            }
+        };
+        Ok(event)
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
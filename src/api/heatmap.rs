@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use axum::Json;
+use axum::extract::State;
+use axum_extra::extract::Query as ExtraQuery;
+use chrono::{Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use super::care_events::validate_event_type;
+use super::error::{ApiError, db_error};
+use super::time_zone::{configured_time_zone, local_time_sql, offset_spans};
+
+/// Longest range the heatmap covers.
+const MAX_RANGE_DAYS: i64 = 3660;
+
+#[derive(Deserialize)]
+pub struct HeatmapQuery {
+    /// First day (`YYYY-MM-DD`), inclusive. Defaults to 364 days before `to`.
+    pub from: Option<String>,
+    /// Last day (`YYYY-MM-DD`), inclusive. Defaults to today in the configured time zone.
+    pub to: Option<String>,
+    #[serde(default, rename = "type")]
+    pub event_types: Vec<String>,
+    #[serde(default, rename = "plant_id")]
+    pub plant_ids: Vec<i64>,
+}
+
+#[derive(Serialize)]
+pub struct HeatmapDay {
+    /// `YYYY-MM-DD` in the configured time zone.
+    pub date: String,
+    pub count: i64,
+    /// Events of the day per event type.
+    pub types: BTreeMap<String, i64>,
+}
+
+#[derive(Serialize)]
+pub struct CareHeatmap {
+    pub from: String,
+    pub to: String,
+    /// IANA time zone the days were bucketed in.
+    pub time_zone: String,
+    pub total: i64,
+    /// Events of the busiest day, for scaling the colours.
+    pub max: i64,
+    /// Days with at least one event, oldest first.
+    pub days: Vec<HeatmapDay>,
+}
+
+fn parse_range(
+    params: &HeatmapQuery,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), ApiError> {
+    let invalid = || ApiError::Validation("HEATMAP_INVALID_DATE_RANGE");
+    let parse = |date: Option<&str>| date.map(str::parse::<NaiveDate>).transpose();
+
+    let to = parse(params.to.as_deref())
+        .map_err(|_| invalid())?
+        .unwrap_or(today);
+    let from = parse(params.from.as_deref())
+        .map_err(|_| invalid())?
+        .unwrap_or_else(|| to - Days::new(364));
+    if from > to || (to - from).num_days() > MAX_RANGE_DAYS {
+        return Err(invalid());
+    }
+    Ok((from, to))
+}
+
+/// Group `(day, event_type, count)` rows, ordered by day, into heatmap days.
+fn group_days(rows: Vec<(String, String, i64)>) -> Vec<HeatmapDay> {
+    let mut days: Vec<HeatmapDay> = Vec::new();
+    for (date, event_type, count) in rows {
+        match days.last_mut() {
+            Some(day) if day.date == date => {
+                day.count += count;
+                day.types.insert(event_type, count);
+            }
+            _ => days.push(HeatmapDay {
+                date,
+                count,
+                types: BTreeMap::from([(event_type, count)]),
+            }),
+        }
+    }
+    days
+}
+
+/// # Errors
+/// Returns `ApiError::Validation` if an event type filter or the date range is invalid,
+/// or `ApiError::InternalError` on database failures.
+pub async fn get_care_heatmap(
+    State(pool): State<SqlitePool>,
+    ExtraQuery(params): ExtraQuery<HeatmapQuery>,
+) -> Result<Json<CareHeatmap>, ApiError> {
+    for event_type in &params.event_types {
+        validate_event_type(event_type)?;
+    }
+    let zone = configured_time_zone(&pool).await.map_err(db_error)?;
+    let today = Utc::now().with_timezone(&zone).date_naive();
+    let (from, to) = parse_range(&params, today)?;
+    let spans = offset_spans(zone, from, to);
+
+    // UTC offsets never exceed a day, so the UTC bounds one day either side of the range
+    // narrow the scan via the index before the exact bucketing in the zone.
+    let mut query = format!(
+        "SELECT date({}) AS day, ce.event_type, COUNT(*) \
+         FROM care_events ce JOIN plants p ON ce.plant_id = p.id \
+         WHERE ce.deleted_at IS NULL AND p.deleted_at IS NULL \
+         AND ce.occurred_at >= date(?, '-1 day') AND ce.occurred_at < date(?, '+2 days')",
+        local_time_sql("ce.occurred_at", "?")
+    );
+    if !params.event_types.is_empty() {
+        let placeholders: Vec<&str> = params.event_types.iter().map(|_| "?").collect();
+        query.push_str(" AND ce.event_type IN (");
+        query.push_str(&placeholders.join(", "));
+        query.push(')');
+    }
+    if !params.plant_ids.is_empty() {
+        let placeholders: Vec<&str> = params.plant_ids.iter().map(|_| "?").collect();
+        query.push_str(" AND ce.plant_id IN (");
+        query.push_str(&placeholders.join(", "));
+        query.push(')');
+    }
+    query.push_str(
+        " GROUP BY day, ce.event_type HAVING day BETWEEN ? AND ? ORDER BY day, ce.event_type",
+    );
+
+    let from = from.to_string();
+    let to = to.to_string();
+    let mut q = sqlx::query_as::<_, (String, String, i64)>(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(&spans)
+        .bind(&from)
+        .bind(&to);
+    for event_type in &params.event_types {
+        q = q.bind(event_type);
+    }
+    for plant_id in &params.plant_ids {
+        q = q.bind(plant_id);
+    }
+    let rows = q
+        .bind(&from)
+        .bind(&to)
+        .fetch_all(&pool)
+        .await
+        .map_err(db_error)?;
+
+    let days = group_days(rows);
+    Ok(Json(CareHeatmap {
+        from,
+        to,
+        time_zone: zone.name().to_string(),
+        total: days.iter().map(|day| day.count).sum(),
+        max: days.iter().map(|day| day.count).max().unwrap_or(0),
+        days,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(from: Option<&str>, to: Option<&str>) -> HeatmapQuery {
+        HeatmapQuery {
+            from: from.map(String::from),
+            to: to.map(String::from),
+            event_types: Vec::new(),
+            plant_ids: Vec::new(),
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn range_defaults_to_the_year_up_to_today() {
+        let today = date("2026-06-15");
+        let (from, to) = parse_range(&query(None, None), today).ok().unwrap();
+        assert_eq!((from, to), (date("2025-06-16"), today));
+        let (from, _) = parse_range(&query(None, Some("2026-01-01")), today)
+            .ok()
+            .unwrap();
+        assert_eq!(from, date("2025-01-02"));
+    }
+
+    #[test]
+    fn range_is_validated() {
+        let today = date("2026-06-15");
+        assert!(parse_range(&query(Some("2026-06-02"), Some("2026-06-01")), today).is_err());
+        assert!(parse_range(&query(Some("2026-13-01"), None), today).is_err());
+        assert!(parse_range(&query(Some("2010-01-01"), Some("2026-01-01")), today).is_err());
+        assert!(parse_range(&query(Some("2026-06-01"), Some("2026-06-01")), today).is_ok());
+    }
+
+    #[test]
+    fn rows_are_grouped_per_day() {
+        let row =
+            |day: &str, event_type: &str, count| (day.to_string(), event_type.to_string(), count);
+        let days = group_days(vec![
+            row("2026-06-01", "fertilizing", 1),
+            row("2026-06-01", "watering", 2),
+            row("2026-06-03", "watering", 1),
+        ]);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].count, 3);
+        assert_eq!(days[0].types.get("watering"), Some(&2));
+        assert_eq!(days[1].date, "2026-06-03");
+        assert_eq!(days[1].count, 1);
+    }
+}
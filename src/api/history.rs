@@ -0,0 +1,461 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use sqlx::{SqliteConnection, SqliteExecutor, SqlitePool};
+
+use tracing::info;
+
+use super::care_events::plant_exists;
+use super::error::{ApiError, db_error};
+use super::lineage;
+use super::plants::{PLANT_SELECT, Plant, PlantRow, publish_plant_mqtt};
+use crate::events::ChangeEvent;
+use crate::state::AppState;
+
+pub(crate) const ENTITY_PLANT: &str = "plant";
+pub(crate) const ENTITY_CARE_EVENT: &str = "care_event";
+pub(crate) const ENTITY_LOCATION: &str = "location";
+pub(crate) const ENTITY_TAG: &str = "tag";
+
+/// Field-level changes of one history entry: `{field: {"old": .., "new": ..}}`.
+pub(crate) type ChangeSet = Map<String, Value>;
+
+/// The user-editable plant columns tracked by the history and restored by revert.
+#[derive(Serialize, Deserialize, sqlx::FromRow)]
+pub(crate) struct PlantSnapshot {
+    pub(crate) name: String,
+    pub(crate) species: Option<String>,
+    pub(crate) icon: String,
+    pub(crate) location_id: Option<i64>,
+    pub(crate) watering_interval_days: i64,
+    pub(crate) light_needs: String,
+    pub(crate) difficulty: Option<String>,
+    pub(crate) pet_safety: Option<String>,
+    pub(crate) growth_speed: Option<String>,
+    pub(crate) soil_type: Option<String>,
+    pub(crate) soil_moisture: Option<String>,
+    pub(crate) notes: Option<String>,
+    pub(crate) lifecycle_status: String,
+    pub(crate) lifecycle_date: Option<String>,
+    pub(crate) lifecycle_reason: Option<String>,
+    pub(crate) parent_id: Option<i64>,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub(crate) struct CareEventSnapshot {
+    pub(crate) event_type: String,
+    pub(crate) notes: Option<String>,
+    pub(crate) occurred_at: String,
+}
+
+#[derive(Serialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub entity_type: String,
+    pub entity_id: i64,
+    pub action: String,
+    pub changes: Value,
+    pub changed_at: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct HistoryRow {
+    id: i64,
+    entity_type: String,
+    entity_id: i64,
+    action: String,
+    changes: String,
+    changed_at: String,
+}
+
+impl From<HistoryRow> for HistoryEntry {
+    fn from(row: HistoryRow) -> Self {
+        Self {
+            id: row.id,
+            entity_type: row.entity_type,
+            entity_id: row.entity_id,
+            action: row.action,
+            changes: serde_json::from_str(&row.changes).unwrap_or_else(|_| json!({})),
+            changed_at: row.changed_at,
+        }
+    }
+}
+
+fn to_object(value: &impl Serialize) -> ChangeSet {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Fields whose value differs between `old` and `new`.
+pub(crate) fn diff(old: &impl Serialize, new: &impl Serialize) -> ChangeSet {
+    let old = to_object(old);
+    let new = to_object(new);
+    new.into_iter()
+        .filter_map(|(field, new_value)| {
+            let old_value = old.get(&field).cloned().unwrap_or(Value::Null);
+            (old_value != new_value).then(|| (field, json!({"old": old_value, "new": new_value})))
+        })
+        .collect()
+}
+
+/// All non-null fields of a newly created entity.
+pub(crate) fn created(new: &impl Serialize) -> ChangeSet {
+    to_object(new)
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(field, value)| (field, json!({"old": null, "new": value})))
+        .collect()
+}
+
+/// All non-null fields of a deleted entity.
+pub(crate) fn deleted(old: &impl Serialize) -> ChangeSet {
+    to_object(old)
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(field, value)| (field, json!({"old": value, "new": null})))
+        .collect()
+}
+
+/// Append a history entry. Updates without any changed field are not recorded.
+pub(crate) async fn record(
+    executor: impl SqliteExecutor<'_>,
+    entity_type: &str,
+    entity_id: i64,
+    plant_id: Option<i64>,
+    action: &str,
+    changes: ChangeSet,
+) -> Result<(), sqlx::Error> {
+    if changes.is_empty() && action == "updated" {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    sqlx::query(
+        "INSERT INTO change_history (entity_type, entity_id, plant_id, action, changes, changed_at) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(entity_type)
+    .bind(entity_id)
+    .bind(plant_id)
+    .bind(action)
+    .bind(Value::Object(changes).to_string())
+    .bind(&now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Write the user-editable columns of a plant, leaving trashed plants untouched.
+pub(crate) async fn write_plant_snapshot(
+    conn: &mut SqliteConnection,
+    id: i64,
+    snapshot: &PlantSnapshot,
+    now: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE plants SET name = ?, species = ?, icon = ?, location_id = ?, \
+         watering_interval_days = ?, light_needs = ?, \
+         difficulty = ?, pet_safety = ?, growth_speed = ?, soil_type = ?, \
+         soil_moisture = ?, notes = ?, \
+         lifecycle_status = ?, lifecycle_date = ?, lifecycle_reason = ?, parent_id = ?, \
+         updated_at = ? WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(&snapshot.name)
+    .bind(&snapshot.species)
+    .bind(&snapshot.icon)
+    .bind(snapshot.location_id)
+    .bind(snapshot.watering_interval_days)
+    .bind(&snapshot.light_needs)
+    .bind(&snapshot.difficulty)
+    .bind(&snapshot.pet_safety)
+    .bind(&snapshot.growth_speed)
+    .bind(&snapshot.soil_type)
+    .bind(&snapshot.soil_moisture)
+    .bind(&snapshot.notes)
+    .bind(&snapshot.lifecycle_status)
+    .bind(&snapshot.lifecycle_date)
+    .bind(&snapshot.lifecycle_reason)
+    .bind(snapshot.parent_id)
+    .bind(now)
+    .bind(id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+pub(crate) async fn plant_snapshot(
+    executor: impl SqliteExecutor<'_>,
+    id: i64,
+) -> Result<Option<PlantSnapshot>, sqlx::Error> {
+    sqlx::query_as::<_, PlantSnapshot>(
+        "SELECT name, species, icon, location_id, watering_interval_days, light_needs, \
+         difficulty, pet_safety, growth_speed, soil_type, soil_moisture, notes, \
+         lifecycle_status, lifecycle_date, lifecycle_reason, parent_id \
+         FROM plants WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(executor)
+    .await
+}
+
+pub(crate) async fn care_event_snapshot(
+    executor: impl SqliteExecutor<'_>,
+    id: i64,
+) -> Result<Option<CareEventSnapshot>, sqlx::Error> {
+    sqlx::query_as::<_, CareEventSnapshot>(
+        "SELECT event_type, notes, occurred_at FROM care_events \
+         WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(executor)
+    .await
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist, or
+/// `ApiError::InternalError` on database failures.
+pub async fn list_plant_history(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<HistoryEntry>>, ApiError> {
+    plant_exists(&pool, id).await?;
+
+    let rows = sqlx::query_as::<_, HistoryRow>(
+        "SELECT id, entity_type, entity_id, action, changes, changed_at \
+         FROM change_history WHERE plant_id = ? ORDER BY id DESC",
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(Json(rows.into_iter().map(HistoryEntry::from).collect()))
+}
+
+/// Rebuild the plant as it was right after `entry_id` by undoing every later change,
+/// newest first, so each field ends up with the oldest later entry's `old` value.
+fn reverted_snapshot(current: &PlantSnapshot, later: &[HistoryRow]) -> Option<PlantSnapshot> {
+    let mut fields = to_object(current);
+    for row in later {
+        let Ok(Value::Object(changes)) = serde_json::from_str::<Value>(&row.changes) else {
+            continue;
+        };
+        for (field, change) in changes {
+            if fields.contains_key(&field)
+                && let Some(old) = change.get("old")
+            {
+                fields.insert(field, old.clone());
+            }
+        }
+    }
+    serde_json::from_value(Value::Object(fields)).ok()
+}
+
+/// Clear references of a reverted plant that can no longer be restored: the old location
+/// may have been deleted, and the old parent purged or turned into a descendant.
+async fn drop_stale_references(
+    conn: &mut SqliteConnection,
+    id: i64,
+    target: &mut PlantSnapshot,
+) -> Result<(), ApiError> {
+    if let Some(location_id) = target.location_id {
+        let exists = sqlx::query_scalar::<_, i64>("SELECT id FROM locations WHERE id = ?")
+            .bind(location_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(db_error)?;
+        if exists.is_none() {
+            target.location_id = None;
+        }
+    }
+    if let Some(parent_id) = target.parent_id {
+        match lineage::validate_parent(&mut *conn, Some(id), parent_id).await {
+            Err(ApiError::Validation(_)) => target.parent_id = None,
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant or history entry does not exist, or
+/// `ApiError::InternalError` on database failures.
+pub async fn revert_plant(
+    State(state): State<AppState>,
+    Path((id, entry_id)): Path<(i64, i64)>,
+) -> Result<Json<Plant>, ApiError> {
+    // Read the current state and later entries in the same transaction as the write, so
+    // a concurrent update cannot be overwritten unnoticed
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let current = plant_snapshot(&mut *tx, id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+
+    sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM change_history WHERE id = ? AND entity_type = ? AND entity_id = ?",
+    )
+    .bind(entry_id)
+    .bind(ENTITY_PLANT)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::NotFound("HISTORY_ENTRY_NOT_FOUND"))?;
+
+    let later = sqlx::query_as::<_, HistoryRow>(
+        "SELECT id, entity_type, entity_id, action, changes, changed_at \
+         FROM change_history WHERE entity_type = ? AND entity_id = ? AND id > ? ORDER BY id DESC",
+    )
+    .bind(ENTITY_PLANT)
+    .bind(id)
+    .bind(entry_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    let mut target = reverted_snapshot(&current, &later).ok_or_else(|| {
+        tracing::error!(
+            plant_id = id,
+            entry_id,
+            "History entry could not be applied"
+        );
+        ApiError::InternalError("INTERNAL_ERROR")
+    })?;
+
+    drop_stale_references(&mut tx, id, &mut target).await?;
+
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    write_plant_snapshot(&mut tx, id, &target, &now)
+        .await
+        .map_err(db_error)?;
+    record(
+        &mut *tx,
+        ENTITY_PLANT,
+        id,
+        Some(id),
+        "reverted",
+        diff(&current, &target),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    let row =
+        sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!("{PLANT_SELECT} AND p.id = ?")))
+            .bind(id)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(db_error)?;
+
+    let plant = Plant::from(row);
+    info!(plant_id = id, entry_id, "Plant reverted");
+    state
+        .events
+        .publish(ChangeEvent::PlantUpdated { plant_id: id });
+    publish_plant_mqtt(&state, &plant).await;
+
+    Ok(Json(plant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Sample {
+        name: String,
+        notes: Option<String>,
+    }
+
+    fn sample(name: &str, notes: Option<&str>) -> Sample {
+        Sample {
+            name: name.to_string(),
+            notes: notes.map(String::from),
+        }
+    }
+
+    #[test]
+    fn diff_contains_only_changed_fields() {
+        let changes = diff(&sample("Fern", None), &sample("Fern", Some("Sunny")));
+        assert_eq!(
+            Value::Object(changes),
+            json!({"notes": {"old": null, "new": "Sunny"}})
+        );
+    }
+
+    #[test]
+    fn diff_of_identical_values_is_empty() {
+        assert!(diff(&sample("Fern", None), &sample("Fern", None)).is_empty());
+    }
+
+    #[test]
+    fn created_and_deleted_skip_null_fields() {
+        assert_eq!(
+            Value::Object(created(&sample("Fern", None))),
+            json!({"name": {"old": null, "new": "Fern"}})
+        );
+        assert_eq!(
+            Value::Object(deleted(&sample("Fern", None))),
+            json!({"name": {"old": "Fern", "new": null}})
+        );
+    }
+
+    fn history_row(id: i64, changes: &Value) -> HistoryRow {
+        HistoryRow {
+            id,
+            entity_type: ENTITY_PLANT.to_string(),
+            entity_id: 1,
+            action: "updated".to_string(),
+            changes: changes.to_string(),
+            changed_at: String::new(),
+        }
+    }
+
+    fn snapshot(name: &str, interval: i64) -> PlantSnapshot {
+        PlantSnapshot {
+            name: name.to_string(),
+            species: None,
+            icon: "🪴".to_string(),
+            location_id: None,
+            watering_interval_days: interval,
+            light_needs: "indirect".to_string(),
+            difficulty: None,
+            pet_safety: None,
+            growth_speed: None,
+            soil_type: None,
+            soil_moisture: None,
+            notes: None,
+            lifecycle_status: "active".to_string(),
+            lifecycle_date: None,
+            lifecycle_reason: None,
+            parent_id: None,
+        }
+    }
+
+    #[test]
+    fn reverted_snapshot_undoes_later_changes() {
+        // Entry 2: name A -> B, entry 3: interval 7 -> 3, entry 4: name B -> C.
+        // Reverting to entry 2 undoes 3 and 4 (passed newest first).
+        let later = [
+            history_row(4, &json!({"name": {"old": "B", "new": "C"}})),
+            history_row(3, &json!({"watering_interval_days": {"old": 7, "new": 3}})),
+        ];
+        let target = reverted_snapshot(&snapshot("C", 3), &later).unwrap();
+        assert_eq!(target.name, "B");
+        assert_eq!(target.watering_interval_days, 7);
+    }
+
+    #[test]
+    fn reverted_snapshot_ignores_untracked_fields() {
+        let later = [history_row(
+            2,
+            &json!({"photo_path": {"old": "a.jpg", "new": null}}),
+        )];
+        let target = reverted_snapshot(&snapshot("Fern", 7), &later).unwrap();
+        assert_eq!(target.name, "Fern");
+    }
+}
@@ -0,0 +1,116 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use serde::Serialize;
+use sqlx::{SqliteExecutor, SqlitePool};
+
+use super::care_events::plant_exists;
+use super::error::{ApiError, db_error};
+
+/// Upper bound on lineage depth, guarding the recursive queries against cycles in imported data.
+const MAX_DEPTH: i64 = 100;
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct LineagePlant {
+    pub id: i64,
+    pub name: String,
+    pub species: Option<String>,
+    pub icon: String,
+    pub parent_id: Option<i64>,
+    pub lifecycle_status: String,
+    /// Generations away from the requested plant: 1 for parent and children.
+    pub depth: i64,
+}
+
+#[derive(Serialize)]
+pub struct Lineage {
+    pub ancestors: Vec<LineagePlant>,
+    pub descendants: Vec<LineagePlant>,
+}
+
+/// Check that `parent_id` names a live plant and that making it the parent of plant `id`
+/// (`None` for a new plant) would not create a cycle.
+///
+/// # Errors
+/// Returns `ApiError::Validation` with `PLANT_INVALID_PARENT` if the parent does not exist
+/// or descends from the plant, or `ApiError::InternalError` on database failures.
+pub(crate) async fn validate_parent(
+    executor: impl SqliteExecutor<'_>,
+    id: Option<i64>,
+    parent_id: i64,
+) -> Result<(), ApiError> {
+    let valid = sqlx::query_scalar::<_, bool>(
+        "WITH RECURSIVE descendants(id, depth) AS ( \
+             SELECT ?1, 0 \
+             UNION SELECT p.id, d.depth + 1 FROM plants p \
+             JOIN descendants d ON p.parent_id = d.id WHERE d.depth < ?3 \
+         ) \
+         SELECT EXISTS (SELECT 1 FROM plants WHERE id = ?2 AND deleted_at IS NULL) \
+            AND ?2 NOT IN (SELECT id FROM descendants WHERE id IS NOT NULL)",
+    )
+    .bind(id)
+    .bind(parent_id)
+    .bind(MAX_DEPTH)
+    .fetch_one(executor)
+    .await
+    .map_err(db_error)?;
+
+    if !valid {
+        return Err(ApiError::Validation("PLANT_INVALID_PARENT"));
+    }
+    Ok(())
+}
+
+/// List the ancestors of a plant, nearest first, and all its descendants by generation.
+/// Trashed plants are skipped but do not break the chain.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist, or
+/// `ApiError::InternalError` on database failures.
+pub async fn get_lineage(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Lineage>, ApiError> {
+    plant_exists(&pool, id).await?;
+
+    let ancestors = sqlx::query_as::<_, LineagePlant>(
+        "WITH RECURSIVE ancestors(id, depth) AS ( \
+             SELECT parent_id, 1 FROM plants WHERE id = ?1 AND parent_id IS NOT NULL \
+             UNION SELECT p.parent_id, a.depth + 1 FROM plants p \
+             JOIN ancestors a ON p.id = a.id \
+             WHERE p.parent_id IS NOT NULL AND a.depth < ?2 \
+         ) \
+         SELECT p.id, p.name, p.species, p.icon, p.parent_id, p.lifecycle_status, \
+            MIN(a.depth) AS depth \
+         FROM ancestors a JOIN plants p ON p.id = a.id \
+         WHERE p.deleted_at IS NULL AND p.id != ?1 \
+         GROUP BY p.id ORDER BY depth",
+    )
+    .bind(id)
+    .bind(MAX_DEPTH)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+
+    let descendants = sqlx::query_as::<_, LineagePlant>(
+        "WITH RECURSIVE descendants(id, depth) AS ( \
+             SELECT id, 1 FROM plants WHERE parent_id = ?1 \
+             UNION SELECT p.id, d.depth + 1 FROM plants p \
+             JOIN descendants d ON p.parent_id = d.id WHERE d.depth < ?2 \
+         ) \
+         SELECT p.id, p.name, p.species, p.icon, p.parent_id, p.lifecycle_status, \
+            MIN(d.depth) AS depth \
+         FROM descendants d JOIN plants p ON p.id = d.id \
+         WHERE p.deleted_at IS NULL AND p.id != ?1 \
+         GROUP BY p.id ORDER BY depth, p.name",
+    )
+    .bind(id)
+    .bind(MAX_DEPTH)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(Json(Lineage {
+        ancestors,
+        descendants,
+    }))
+}
@@ -2,11 +2,13 @@ use axum::Json;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::SqlitePool;
 
 use tracing::debug;
 
 use super::error::{ApiError, JsonBody, db_error};
+use super::history::{self, ENTITY_LOCATION, ENTITY_PLANT};
 use super::plants::validate_required_name;
 
 #[derive(Serialize)]
@@ -40,7 +42,8 @@ pub async fn list_locations(
 ) -> Result<Json<Vec<Location>>, ApiError> {
     let rows = sqlx::query_as::<_, LocationRow>(
         "SELECT l.id, l.name, COUNT(p.id) AS plant_count \
-         FROM locations l LEFT JOIN plants p ON p.location_id = l.id \
+         FROM locations l \
+         LEFT JOIN plants p ON p.location_id = l.id AND p.deleted_at IS NULL \
          GROUP BY l.id, l.name ORDER BY l.name",
     )
     .fetch_all(&pool)
@@ -83,13 +86,25 @@ pub async fn create_location(
         return Err(ApiError::Conflict("LOCATION_ALREADY_EXISTS"));
     }
 
+    let mut tx = pool.begin().await.map_err(db_error)?;
     let row = sqlx::query_as::<_, LocationRow>(
         "INSERT INTO locations (name) VALUES (?) RETURNING id, name, 0 AS plant_count",
     )
     .bind(&name)
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await
     .map_err(db_error)?;
+    history::record(
+        &mut *tx,
+        ENTITY_LOCATION,
+        row.id,
+        None,
+        "created",
+        history::created(&json!({"name": row.name})),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
     debug!(location_id = row.id, name = %row.name, "Location created");
     Ok((
@@ -113,15 +128,12 @@ pub async fn update_location(
     JsonBody(body): JsonBody<UpdateLocation>,
 ) -> Result<Json<Location>, ApiError> {
     // Check existence
-    let exists = sqlx::query_scalar::<_, i64>("SELECT id FROM locations WHERE id = ?")
+    let old_name = sqlx::query_scalar::<_, String>("SELECT name FROM locations WHERE id = ?")
         .bind(id)
         .fetch_optional(&pool)
         .await
-        .map_err(db_error)?;
-
-    if exists.is_none() {
-        return Err(ApiError::NotFound("LOCATION_NOT_FOUND"));
-    }
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("LOCATION_NOT_FOUND"))?;
 
     let name = body
         .name
@@ -142,20 +154,33 @@ pub async fn update_location(
         return Err(ApiError::Conflict("LOCATION_ALREADY_EXISTS"));
     }
 
+    let mut tx = pool.begin().await.map_err(db_error)?;
     sqlx::query("UPDATE locations SET name = ? WHERE id = ?")
         .bind(&name)
         .bind(id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(db_error)?;
+    history::record(
+        &mut *tx,
+        ENTITY_LOCATION,
+        id,
+        None,
+        "updated",
+        history::diff(&json!({"name": old_name}), &json!({"name": name})),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
     // Get plant count for response
-    let plant_count =
-        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM plants WHERE location_id = ?")
-            .bind(id)
-            .fetch_one(&pool)
-            .await
-            .map_err(db_error)?;
+    let plant_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM plants WHERE location_id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_one(&pool)
+    .await
+    .map_err(db_error)?;
 
     Ok(Json(Location {
         id,
@@ -171,22 +196,52 @@ pub async fn delete_location(
     State(pool): State<SqlitePool>,
     Path(id): Path<i64>,
 ) -> Result<StatusCode, ApiError> {
-    // Nullify plant references
-    sqlx::query("UPDATE plants SET location_id = NULL WHERE location_id = ?")
+    let name = sqlx::query_scalar::<_, String>("SELECT name FROM locations WHERE id = ?")
         .bind(id)
-        .execute(&pool)
+        .fetch_optional(&pool)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("LOCATION_NOT_FOUND"))?;
+
+    let mut tx = pool.begin().await.map_err(db_error)?;
+
+    // Nullify plant references
+    let plant_ids = sqlx::query_scalar::<_, i64>(
+        "UPDATE plants SET location_id = NULL WHERE location_id = ? RETURNING id",
+    )
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    for plant_id in plant_ids {
+        history::record(
+            &mut *tx,
+            ENTITY_PLANT,
+            plant_id,
+            Some(plant_id),
+            "updated",
+            history::diff(&json!({"location_id": id}), &json!({"location_id": null})),
+        )
         .await
         .map_err(db_error)?;
+    }
 
-    let result = sqlx::query("DELETE FROM locations WHERE id = ?")
+    sqlx::query("DELETE FROM locations WHERE id = ?")
         .bind(id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(db_error)?;
-
-    if result.rows_affected() == 0 {
-        return Err(ApiError::NotFound("LOCATION_NOT_FOUND"));
-    }
+    history::record(
+        &mut *tx,
+        ENTITY_LOCATION,
+        id,
+        None,
+        "deleted",
+        history::deleted(&json!({"name": name})),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
     debug!(location_id = id, "Location deleted");
     Ok(StatusCode::NO_CONTENT)
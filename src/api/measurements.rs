@@ -0,0 +1,435 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use chrono::{DateTime, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use tracing::debug;
+
+use super::care_events::{plant_exists, validate_date_range};
+use super::error::{ApiError, JsonBody, db_error};
+use super::photos::normalize_text;
+use super::plants::deserialize_nullable;
+use crate::events::ChangeEvent;
+use crate::state::AppState;
+
+/// A measurable plant property. Every reading of a metric uses its unit.
+pub struct Metric {
+    pub name: &'static str,
+    pub unit: &'static str,
+    /// Readings must be whole numbers.
+    pub whole: bool,
+}
+
+pub const METRICS: &[Metric] = &[
+    Metric {
+        name: "height",
+        unit: "cm",
+        whole: false,
+    },
+    Metric {
+        name: "width",
+        unit: "cm",
+        whole: false,
+    },
+    Metric {
+        name: "leaf_count",
+        unit: "leaves",
+        whole: true,
+    },
+    Metric {
+        name: "pot_size",
+        unit: "cm",
+        whole: false,
+    },
+];
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct Measurement {
+    pub id: i64,
+    pub plant_id: i64,
+    pub care_event_id: Option<i64>,
+    pub metric: String,
+    pub value: f64,
+    #[sqlx(skip)]
+    pub unit: &'static str,
+    pub notes: Option<String>,
+    pub measured_at: String,
+    pub created_at: String,
+}
+
+const MEASUREMENT_SELECT: &str = "SELECT id, plant_id, care_event_id, metric, value, notes, \
+    measured_at, created_at FROM measurements";
+
+#[derive(Deserialize)]
+pub struct CreateMeasurement {
+    pub metric: Option<String>,
+    pub value: Option<f64>,
+    /// RFC 3339 timestamp; defaults to the care event's date, or now.
+    pub measured_at: Option<String>,
+    /// Care event of the same plant during which the reading was taken.
+    pub care_event_id: Option<i64>,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateMeasurement {
+    pub metric: Option<String>,
+    pub value: Option<f64>,
+    pub measured_at: Option<String>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub care_event_id: Option<Option<i64>>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub notes: Option<Option<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct MeasurementQuery {
+    pub metric: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SeriesQuery {
+    pub metric: Option<String>,
+    /// First day (`YYYY-MM-DD`) of `measured_at`, inclusive.
+    pub from: Option<String>,
+    /// Last day (`YYYY-MM-DD`) of `measured_at`, inclusive.
+    pub to: Option<String>,
+}
+
+/// Readings of one metric, oldest first, ready for charting.
+#[derive(Serialize)]
+pub struct MeasurementSeries {
+    pub metric: &'static str,
+    pub unit: &'static str,
+    pub points: Vec<SeriesPoint>,
+}
+
+#[derive(Serialize)]
+pub struct SeriesPoint {
+    pub measured_at: String,
+    pub value: f64,
+}
+
+#[derive(sqlx::FromRow)]
+struct SeriesRow {
+    metric: String,
+    measured_at: String,
+    value: f64,
+}
+
+/// Look up a metric by name.
+///
+/// # Errors
+/// Returns `ApiError::Validation` if the metric is unknown.
+pub fn metric(name: &str) -> Result<&'static Metric, ApiError> {
+    METRICS
+        .iter()
+        .find(|m| m.name == name)
+        .ok_or(ApiError::Validation("MEASUREMENT_INVALID_METRIC"))
+}
+
+/// Check that `value` is a valid reading of the metric `name`.
+///
+/// # Errors
+/// Returns `ApiError::Validation` if the metric is unknown, or the value is negative, not
+/// finite, or fractional for a whole-number metric.
+pub fn validate_measurement(name: &str, value: f64) -> Result<(), ApiError> {
+    let metric = metric(name)?;
+    if !value.is_finite() || value < 0.0 || (metric.whole && value.fract() != 0.0) {
+        return Err(ApiError::Validation("MEASUREMENT_INVALID_VALUE"));
+    }
+    Ok(())
+}
+
+/// Parse an RFC 3339 timestamp and store it in UTC, so readings sort chronologically.
+fn normalize_measured_at(measured_at: &str) -> Result<String, ApiError> {
+    DateTime::parse_from_rfc3339(measured_at.trim())
+        .map(|t| t.to_utc().to_rfc3339_opts(SecondsFormat::Secs, true))
+        .map_err(|_| ApiError::Validation("MEASUREMENT_INVALID_DATE"))
+}
+
+/// `occurred_at` of a live care event of the plant.
+async fn care_event_date(
+    pool: &SqlitePool,
+    plant_id: i64,
+    event_id: i64,
+) -> Result<String, ApiError> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT occurred_at FROM care_events WHERE id = ? AND plant_id = ? AND deleted_at IS NULL",
+    )
+    .bind(event_id)
+    .bind(plant_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::Validation("MEASUREMENT_INVALID_CARE_EVENT"))
+}
+
+fn with_unit(mut measurement: Measurement) -> Measurement {
+    measurement.unit = metric(&measurement.metric).map_or("", |m| m.unit);
+    measurement
+}
+
+async fn fetch_measurement(
+    pool: &SqlitePool,
+    plant_id: i64,
+    id: i64,
+) -> Result<Measurement, ApiError> {
+    let query = format!("{MEASUREMENT_SELECT} WHERE id = ? AND plant_id = ?");
+    sqlx::query_as::<_, Measurement>(sqlx::AssertSqlSafe(query))
+        .bind(id)
+        .bind(plant_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(db_error)?
+        .map(with_unit)
+        .ok_or(ApiError::NotFound("MEASUREMENT_NOT_FOUND"))
+}
+
+/// List a plant's measurements, newest first, optionally of a single metric.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist,
+/// `ApiError::Validation` if the metric is unknown, or
+/// `ApiError::InternalError` on database failures.
+pub async fn list_measurements(
+    State(pool): State<SqlitePool>,
+    Path(plant_id): Path<i64>,
+    Query(params): Query<MeasurementQuery>,
+) -> Result<Json<Vec<Measurement>>, ApiError> {
+    plant_exists(&pool, plant_id).await?;
+    if let Some(name) = &params.metric {
+        metric(name)?;
+    }
+
+    let query = format!(
+        "{MEASUREMENT_SELECT} WHERE plant_id = ? AND (? IS NULL OR metric = ?) \
+         ORDER BY measured_at DESC, id DESC"
+    );
+    let measurements = sqlx::query_as::<_, Measurement>(sqlx::AssertSqlSafe(query))
+        .bind(plant_id)
+        .bind(&params.metric)
+        .bind(&params.metric)
+        .fetch_all(&pool)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(measurements.into_iter().map(with_unit).collect()))
+}
+
+/// Record a measurement.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist,
+/// `ApiError::Validation` for a missing or unknown metric, a missing or invalid value, an
+/// invalid date, or a care event of another plant, or `ApiError::InternalError` on
+/// database failures.
+pub async fn create_measurement(
+    State(state): State<AppState>,
+    Path(plant_id): Path<i64>,
+    JsonBody(body): JsonBody<CreateMeasurement>,
+) -> Result<(StatusCode, Json<Measurement>), ApiError> {
+    plant_exists(&state.pool, plant_id).await?;
+    let metric = body
+        .metric
+        .filter(|m| !m.trim().is_empty())
+        .ok_or(ApiError::Validation("MEASUREMENT_METRIC_REQUIRED"))?;
+    let value = body
+        .value
+        .ok_or(ApiError::Validation("MEASUREMENT_VALUE_REQUIRED"))?;
+    validate_measurement(&metric, value)?;
+
+    let now = chrono::Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    let event_date = match body.care_event_id {
+        Some(event_id) => Some(care_event_date(&state.pool, plant_id, event_id).await?),
+        None => None,
+    };
+    let measured_at = match body.measured_at.as_deref() {
+        Some(measured_at) => normalize_measured_at(measured_at)?,
+        None => event_date.unwrap_or_else(|| now.clone()),
+    };
+
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO measurements (plant_id, care_event_id, metric, value, notes, measured_at, \
+         created_at) VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING id",
+    )
+    .bind(plant_id)
+    .bind(body.care_event_id)
+    .bind(&metric)
+    .bind(value)
+    .bind(normalize_text(body.notes))
+    .bind(&measured_at)
+    .bind(&now)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(db_error)?;
+
+    let measurement = fetch_measurement(&state.pool, plant_id, id).await?;
+    state
+        .events
+        .publish(ChangeEvent::MeasurementChanged { plant_id });
+    debug!(plant_id, metric = %measurement.metric, "Measurement recorded");
+    Ok((StatusCode::CREATED, Json(measurement)))
+}
+
+/// Update a measurement. Omitted fields are kept; `null` clears `care_event_id` or `notes`.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the measurement does not exist for this plant,
+/// `ApiError::Validation` for an unknown metric, invalid value or date, or a care event
+/// of another plant, or `ApiError::InternalError` on database failures.
+pub async fn update_measurement(
+    State(state): State<AppState>,
+    Path((plant_id, id)): Path<(i64, i64)>,
+    JsonBody(body): JsonBody<UpdateMeasurement>,
+) -> Result<Json<Measurement>, ApiError> {
+    let current = fetch_measurement(&state.pool, plant_id, id).await?;
+
+    let metric = body.metric.unwrap_or(current.metric);
+    let value = body.value.unwrap_or(current.value);
+    validate_measurement(&metric, value)?;
+    let measured_at = match body.measured_at.as_deref() {
+        Some(measured_at) => normalize_measured_at(measured_at)?,
+        None => current.measured_at,
+    };
+    let care_event_id = body.care_event_id.unwrap_or(current.care_event_id);
+    if let Some(event_id) = body.care_event_id.flatten() {
+        care_event_date(&state.pool, plant_id, event_id).await?;
+    }
+    let notes = match body.notes {
+        Some(notes) => normalize_text(notes),
+        None => current.notes,
+    };
+
+    sqlx::query(
+        "UPDATE measurements SET metric = ?, value = ?, measured_at = ?, care_event_id = ?, \
+         notes = ? WHERE id = ?",
+    )
+    .bind(&metric)
+    .bind(value)
+    .bind(&measured_at)
+    .bind(care_event_id)
+    .bind(&notes)
+    .bind(id)
+    .execute(&state.pool)
+    .await
+    .map_err(db_error)?;
+
+    let measurement = fetch_measurement(&state.pool, plant_id, id).await?;
+    state
+        .events
+        .publish(ChangeEvent::MeasurementChanged { plant_id });
+    Ok(Json(measurement))
+}
+
+/// Delete a measurement.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the measurement does not exist for this plant, or
+/// `ApiError::InternalError` on database failures.
+pub async fn delete_measurement(
+    State(state): State<AppState>,
+    Path((plant_id, id)): Path<(i64, i64)>,
+) -> Result<StatusCode, ApiError> {
+    let result = sqlx::query("DELETE FROM measurements WHERE id = ? AND plant_id = ?")
+        .bind(id)
+        .bind(plant_id)
+        .execute(&state.pool)
+        .await
+        .map_err(db_error)?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("MEASUREMENT_NOT_FOUND"));
+    }
+
+    state
+        .events
+        .publish(ChangeEvent::MeasurementChanged { plant_id });
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Group readings by metric in `METRICS` order, leaving out metrics without readings.
+fn group_series(rows: Vec<SeriesRow>) -> Vec<MeasurementSeries> {
+    let mut series: Vec<MeasurementSeries> = METRICS
+        .iter()
+        .map(|m| MeasurementSeries {
+            metric: m.name,
+            unit: m.unit,
+            points: Vec::new(),
+        })
+        .collect();
+    for row in rows {
+        if let Some(s) = series.iter_mut().find(|s| s.metric == row.metric) {
+            s.points.push(SeriesPoint {
+                measured_at: row.measured_at,
+                value: row.value,
+            });
+        }
+    }
+    series.retain(|s| !s.points.is_empty());
+    series
+}
+
+/// A plant's readings as one chronological series per metric, for charting.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist,
+/// `ApiError::Validation` for an unknown metric or invalid date range, or
+/// `ApiError::InternalError` on database failures.
+pub async fn measurement_series(
+    State(pool): State<SqlitePool>,
+    Path(plant_id): Path<i64>,
+    Query(params): Query<SeriesQuery>,
+) -> Result<Json<Vec<MeasurementSeries>>, ApiError> {
+    plant_exists(&pool, plant_id).await?;
+    if let Some(name) = &params.metric {
+        metric(name)?;
+    }
+    validate_date_range(
+        params.from.as_deref(),
+        params.to.as_deref(),
+        "MEASUREMENT_INVALID_DATE_RANGE",
+    )?;
+
+    let rows = sqlx::query_as::<_, SeriesRow>(
+        "SELECT metric, measured_at, value FROM measurements WHERE plant_id = ?1 \
+         AND (?2 IS NULL OR metric = ?2) \
+         AND (?3 IS NULL OR measured_at >= ?3) \
+         AND (?4 IS NULL OR measured_at < date(?4, '+1 day')) \
+         ORDER BY measured_at, id",
+    )
+    .bind(plant_id)
+    .bind(&params.metric)
+    .bind(&params.from)
+    .bind(&params.to)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+    Ok(Json(group_series(rows)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_measurement_checks_metric_and_value() {
+        assert!(validate_measurement("height", 42.5).is_ok());
+        assert!(validate_measurement("leaf_count", 12.0).is_ok());
+        assert!(validate_measurement("leaf_count", 12.5).is_err());
+        assert!(validate_measurement("height", -1.0).is_err());
+        assert!(validate_measurement("height", f64::NAN).is_err());
+        assert!(validate_measurement("weight", 1.0).is_err());
+    }
+
+    #[test]
+    fn measured_at_is_normalized_to_utc() {
+        assert_eq!(
+            normalize_measured_at("2026-04-01T12:30:00+02:00").ok(),
+            Some("2026-04-01T10:30:00Z".to_string())
+        );
+        assert!(normalize_measured_at("2026-04-01").is_err());
+    }
+}
@@ -0,0 +1,801 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use super::care_events::validate_event_type;
+use super::error::{ApiError, db_error};
+use super::history::{
+    self, ENTITY_CARE_EVENT, ENTITY_LOCATION, ENTITY_PLANT, ENTITY_TAG, PlantSnapshot,
+};
+use super::measurements::validate_measurement;
+use super::plants::validate_required_name;
+use super::restore::{
+    ArchivePhotos, ImportCareEvent, ImportData, ImportHistoryEntry, ImportMeasurement, ImportPlant,
+    backfill_care_event_photos, backfill_plant_photos, validate_dest_path, validate_filename,
+    validate_plant,
+};
+
+const ENTITY_MEASUREMENT: &str = "measurement";
+
+const ADDED: &str = "added";
+const SKIPPED: &str = "skipped";
+const CONFLICTED: &str = "conflicted";
+
+#[derive(Default, Serialize)]
+pub struct MergeCounts {
+    pub added: usize,
+    pub skipped: usize,
+    pub conflicted: usize,
+}
+
+impl MergeCounts {
+    fn count(&mut self, outcome: &str) {
+        match outcome {
+            ADDED => self.added += 1,
+            SKIPPED => self.skipped += 1,
+            _ => self.conflicted += 1,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MergeItem {
+    /// `location`, `tag`, `plant`, `care_event` or `measurement`.
+    pub entity: &'static str,
+    /// Id of the entry in the archive.
+    pub archive_id: i64,
+    /// Id of the added entry, or of the existing entry it matched.
+    pub id: i64,
+    pub label: String,
+    /// `added`, `skipped` (an identical entry exists) or `conflicted` (a matching entry
+    /// exists but differs; it is kept unchanged).
+    pub outcome: &'static str,
+    /// Fields that differ from the existing entry.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+}
+
+#[derive(Default, Serialize)]
+pub struct MergeReport {
+    pub locations: MergeCounts,
+    pub tags: MergeCounts,
+    pub plants: MergeCounts,
+    pub care_events: MergeCounts,
+    pub measurements: MergeCounts,
+    /// Photo files copied (`conflicted`: copied under a new name because a different file
+    /// had the same name), or skipped as already present or not used by any merged entry.
+    pub photos: MergeCounts,
+    /// Every location, tag and plant of the archive, plus conflicting care events and
+    /// measurements.
+    pub items: Vec<MergeItem>,
+}
+
+impl MergeReport {
+    fn push(&mut self, item: MergeItem) {
+        let counts = match item.entity {
+            ENTITY_LOCATION => &mut self.locations,
+            ENTITY_TAG => &mut self.tags,
+            ENTITY_PLANT => &mut self.plants,
+            ENTITY_CARE_EVENT => &mut self.care_events,
+            _ => &mut self.measurements,
+        };
+        counts.count(item.outcome);
+        // Care events and measurements are numerous; only their conflicts are listed
+        if matches!(item.entity, ENTITY_LOCATION | ENTITY_TAG | ENTITY_PLANT)
+            || item.outcome == CONFLICTED
+        {
+            self.items.push(item);
+        }
+    }
+}
+
+/// Archive ids mapped to the ids of added or matching existing entries.
+#[derive(Default)]
+struct IdMap {
+    ids: HashMap<i64, i64>,
+    added: HashSet<i64>,
+}
+
+impl IdMap {
+    fn get(&self, archive_id: i64) -> Option<i64> {
+        self.ids.get(&archive_id).copied()
+    }
+
+    fn is_added(&self, archive_id: i64) -> bool {
+        self.added.contains(&archive_id)
+    }
+
+    fn insert(&mut self, archive_id: i64, id: i64, outcome: &str) {
+        self.ids.insert(archive_id, id);
+        if outcome == ADDED {
+            self.added.insert(archive_id);
+        }
+    }
+}
+
+fn outcome_of(fields: &[String]) -> &'static str {
+    if fields.is_empty() {
+        SKIPPED
+    } else {
+        CONFLICTED
+    }
+}
+
+/// Copies the archive photos used by merged entries into the uploads directory.
+struct PhotoCopier<'a> {
+    upload_dir: &'a Path,
+    archive: &'a ArchivePhotos,
+    /// Archive filename to the filename used locally.
+    names: HashMap<String, String>,
+    /// Archive and local filenames of the photos to copy, with the outcome to count.
+    copies: Vec<(String, String, &'static str)>,
+}
+
+impl<'a> PhotoCopier<'a> {
+    fn new(upload_dir: &'a Path, archive: &'a ArchivePhotos) -> Self {
+        Self {
+            upload_dir,
+            archive,
+            names: HashMap::new(),
+            copies: Vec::new(),
+        }
+    }
+
+    /// Local filename of a photo referenced by an added entry. A photo whose name is taken
+    /// locally is copied under a new name even if the contents are the same: the existing
+    /// file belongs to other entries, and deleting either would remove the other's photo.
+    async fn resolve(&mut self, name: &str) -> Result<String, ApiError> {
+        validate_filename(name)?;
+        if let Some(local) = self.names.get(name) {
+            return Ok(local.clone());
+        }
+
+        // Referenced but not in the archive: kept as is, like a full import does
+        let mut local = name.to_string();
+        if self.archive.contains(name) {
+            let path = self.upload_dir.join(name);
+            let mut outcome = ADDED;
+            if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                if !self.archive.same_as(name, path).await? {
+                    outcome = CONFLICTED;
+                }
+                let ext = Path::new(name)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("jpg");
+                local = format!("{}.{ext}", uuid::Uuid::new_v4());
+            }
+            self.copies.push((name.to_string(), local.clone(), outcome));
+        }
+        self.names.insert(name.to_string(), local.clone());
+        Ok(local)
+    }
+
+    async fn resolve_optional(&mut self, name: Option<&str>) -> Result<Option<String>, ApiError> {
+        match name {
+            Some(name) => self.resolve(name).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn write(self, counts: &mut MergeCounts) -> Result<(), ApiError> {
+        for (name, local, outcome) in &self.copies {
+            let dest = self.upload_dir.join(local);
+            validate_dest_path(&dest, self.upload_dir)?;
+            self.archive.extract(name, dest).await?;
+            counts.count(outcome);
+        }
+        counts.skipped = self.archive.len() - self.copies.len();
+        Ok(())
+    }
+}
+
+/// Match locations or tags by name, adding the ones that do not exist yet.
+async fn merge_names(
+    conn: &mut SqliteConnection,
+    entity: &'static str,
+    entries: impl Iterator<Item = (i64, &str)>,
+    report: &mut MergeReport,
+) -> Result<IdMap, ApiError> {
+    let (table, required) = if entity == ENTITY_LOCATION {
+        ("locations", "LOCATION_NAME_REQUIRED")
+    } else {
+        ("tags", "TAG_NAME_REQUIRED")
+    };
+
+    let mut map = IdMap::default();
+    for (archive_id, name) in entries {
+        validate_required_name(name, required)?;
+        let name = name.trim();
+
+        let existing = sqlx::query_scalar::<_, i64>(sqlx::AssertSqlSafe(format!(
+            "SELECT id FROM {table} WHERE name = ?"
+        )))
+        .bind(name)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(db_error)?;
+        let (id, outcome) = if let Some(id) = existing {
+            (id, SKIPPED)
+        } else {
+            let id = sqlx::query_scalar::<_, i64>(sqlx::AssertSqlSafe(format!(
+                "INSERT INTO {table} (name) VALUES (?) RETURNING id"
+            )))
+            .bind(name)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(db_error)?;
+            history::record(
+                &mut *conn,
+                entity,
+                id,
+                None,
+                "created",
+                history::created(&serde_json::json!({ "name": name })),
+            )
+            .await
+            .map_err(db_error)?;
+            (id, ADDED)
+        };
+
+        map.insert(archive_id, id, outcome);
+        report.push(MergeItem {
+            entity,
+            archive_id,
+            id,
+            label: name.to_string(),
+            outcome,
+            fields: Vec::new(),
+        });
+    }
+    Ok(map)
+}
+
+/// Fields in which an archive plant differs from the existing plant it matched. Lineage
+/// is not compared, as the parent may not be merged yet.
+async fn plant_conflicts(
+    conn: &mut SqliteConnection,
+    id: i64,
+    trashed: bool,
+    plant: &ImportPlant,
+    location_id: Option<i64>,
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut fields = Vec::new();
+    if let Some(existing) = history::plant_snapshot(&mut *conn, id).await? {
+        let incoming = PlantSnapshot {
+            name: plant.name.clone(),
+            species: plant.species.clone(),
+            icon: plant.icon.clone(),
+            location_id,
+            watering_interval_days: plant.watering_interval_days,
+            light_needs: plant.light_needs.clone(),
+            difficulty: plant.difficulty.clone(),
+            pet_safety: plant.pet_safety.clone(),
+            growth_speed: plant.growth_speed.clone(),
+            soil_type: plant.soil_type.clone(),
+            soil_moisture: plant.soil_moisture.clone(),
+            notes: plant.notes.clone(),
+            lifecycle_status: plant.lifecycle_status.clone(),
+            lifecycle_date: plant.lifecycle_date.clone(),
+            lifecycle_reason: plant.lifecycle_reason.clone(),
+            parent_id: existing.parent_id,
+        };
+        fields.extend(
+            history::diff(&existing, &incoming)
+                .into_iter()
+                .map(|(field, _)| field),
+        );
+    }
+    if trashed != plant.deleted_at.is_some() {
+        fields.push("deleted_at".to_string());
+    }
+    Ok(fields)
+}
+
+async fn insert_plant(
+    conn: &mut SqliteConnection,
+    plant: &ImportPlant,
+    location_id: Option<i64>,
+    photo_path: Option<String>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(
+        "INSERT INTO plants (name, species, icon, photo_path, location_id, \
+         watering_interval_days, light_needs, difficulty, pet_safety, \
+         growth_speed, soil_type, soil_moisture, notes, \
+         lifecycle_status, lifecycle_date, lifecycle_reason, created_at, updated_at, deleted_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+    )
+    .bind(&plant.name)
+    .bind(&plant.species)
+    .bind(&plant.icon)
+    .bind(photo_path)
+    .bind(location_id)
+    .bind(plant.watering_interval_days)
+    .bind(&plant.light_needs)
+    .bind(&plant.difficulty)
+    .bind(&plant.pet_safety)
+    .bind(&plant.growth_speed)
+    .bind(&plant.soil_type)
+    .bind(&plant.soil_moisture)
+    .bind(&plant.notes)
+    .bind(&plant.lifecycle_status)
+    .bind(&plant.lifecycle_date)
+    .bind(&plant.lifecycle_reason)
+    .bind(&plant.created_at)
+    .bind(&plant.updated_at)
+    .bind(&plant.deleted_at)
+    .fetch_one(&mut *conn)
+    .await
+}
+
+/// The existing plant an archive plant was created as: one with the same creation time
+/// that also shares its name, its id or its species, in that order of preference. Plants
+/// renamed since the archive was written are still found this way.
+async fn find_plant(
+    conn: &mut SqliteConnection,
+    plant: &ImportPlant,
+    claimed: &HashSet<i64>,
+) -> Result<Option<(i64, bool)>, sqlx::Error> {
+    let candidates = sqlx::query_as::<_, (i64, String, Option<String>, bool)>(
+        "SELECT id, name, species, deleted_at IS NOT NULL FROM plants WHERE created_at = ?",
+    )
+    .bind(&plant.created_at)
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(candidates
+        .into_iter()
+        .filter(|(id, ..)| !claimed.contains(id))
+        .filter_map(|(id, name, species, trashed)| {
+            let rank = if name == plant.name {
+                0
+            } else if id == plant.id {
+                1
+            } else if species.is_some() && species == plant.species {
+                2
+            } else {
+                return None;
+            };
+            Some((rank, trashed, id))
+        })
+        .min()
+        .map(|(_, trashed, id)| (id, trashed)))
+}
+
+/// Match plants by creation time, adding the ones that do not exist yet.
+async fn merge_plants(
+    conn: &mut SqliteConnection,
+    plants: &[ImportPlant],
+    locations: &IdMap,
+    photos: &mut PhotoCopier<'_>,
+    report: &mut MergeReport,
+) -> Result<IdMap, ApiError> {
+    let mut map = IdMap::default();
+    // Each existing plant stands in for at most one archive plant
+    let mut claimed = HashSet::new();
+    for plant in plants {
+        validate_plant(plant)?;
+        let location_id = plant.location_id.and_then(|id| locations.get(id));
+
+        let existing = find_plant(conn, plant, &claimed).await.map_err(db_error)?;
+        let (id, outcome, fields) = if let Some((id, trashed)) = existing {
+            let fields = plant_conflicts(conn, id, trashed, plant, location_id)
+                .await
+                .map_err(db_error)?;
+            (id, outcome_of(&fields), fields)
+        } else {
+            let photo_path = photos.resolve_optional(plant.photo_path.as_deref()).await?;
+            let id = insert_plant(conn, plant, location_id, photo_path)
+                .await
+                .map_err(db_error)?;
+            (id, ADDED, Vec::new())
+        };
+
+        claimed.insert(id);
+        map.insert(plant.id, id, outcome);
+        report.push(MergeItem {
+            entity: ENTITY_PLANT,
+            archive_id: plant.id,
+            id,
+            label: plant.name.clone(),
+            outcome,
+            fields,
+        });
+    }
+
+    // Parents may be listed after their children
+    for plant in plants.iter().filter(|plant| map.is_added(plant.id)) {
+        if let Some(parent_id) = plant.parent_id.and_then(|id| map.get(id)) {
+            sqlx::query("UPDATE plants SET parent_id = ? WHERE id = ?")
+                .bind(parent_id)
+                .bind(map.get(plant.id))
+                .execute(&mut *conn)
+                .await
+                .map_err(db_error)?;
+        }
+    }
+    Ok(map)
+}
+
+/// Add the tags and gallery photos of the plants this merge added. Matched plants keep their
+/// own, like their other fields.
+async fn merge_plant_tags_and_photos(
+    conn: &mut SqliteConnection,
+    data: &ImportData,
+    plants: &IdMap,
+    tags: &IdMap,
+    photos: &mut PhotoCopier<'_>,
+) -> Result<(), ApiError> {
+    for plant_tag in &data.plant_tags {
+        if !plants.is_added(plant_tag.plant_id) {
+            continue;
+        }
+        if let (Some(plant_id), Some(tag_id)) =
+            (plants.get(plant_tag.plant_id), tags.get(plant_tag.tag_id))
+        {
+            sqlx::query("INSERT OR IGNORE INTO plant_tags (plant_id, tag_id) VALUES (?, ?)")
+                .bind(plant_id)
+                .bind(tag_id)
+                .execute(&mut *conn)
+                .await
+                .map_err(db_error)?;
+        }
+    }
+
+    for photo in &data.plant_photos {
+        let Some(plant_id) = plants
+            .get(photo.plant_id)
+            .filter(|_| plants.is_added(photo.plant_id))
+        else {
+            continue;
+        };
+        let filename = photos.resolve(&photo.filename).await?;
+
+        sqlx::query(
+            "INSERT INTO plant_photos (plant_id, filename, caption, taken_at, position, created_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(plant_id)
+        .bind(&filename)
+        .bind(&photo.caption)
+        .bind(&photo.taken_at)
+        .bind(photo.position)
+        .bind(&photo.created_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+async fn insert_care_event(
+    conn: &mut SqliteConnection,
+    event: &ImportCareEvent,
+    plant_id: i64,
+    child_plant_id: Option<i64>,
+    photo_path: Option<String>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(
+        "INSERT INTO care_events (plant_id, event_type, notes, photo_path, child_plant_id, \
+         occurred_at, created_at, deleted_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+    )
+    .bind(plant_id)
+    .bind(&event.event_type)
+    .bind(&event.notes)
+    .bind(photo_path)
+    .bind(child_plant_id)
+    .bind(&event.occurred_at)
+    .bind(&event.created_at)
+    .bind(&event.deleted_at)
+    .fetch_one(&mut *conn)
+    .await
+}
+
+/// Match care events by plant, type and time, adding the ones that do not exist yet.
+async fn merge_care_events(
+    conn: &mut SqliteConnection,
+    events: &[ImportCareEvent],
+    plants: &IdMap,
+    photos: &mut PhotoCopier<'_>,
+    report: &mut MergeReport,
+) -> Result<IdMap, ApiError> {
+    let mut map = IdMap::default();
+    for event in events {
+        validate_event_type(&event.event_type)?;
+        let plant_id = plants
+            .get(event.plant_id)
+            .ok_or(ApiError::BadRequest("IMPORT_INVALID_DATA"))?;
+
+        // Plants added by this merge have no events yet
+        let existing = if plants.is_added(event.plant_id) {
+            None
+        } else {
+            sqlx::query_as::<_, (i64, Option<String>)>(
+                "SELECT id, notes FROM care_events \
+                 WHERE plant_id = ? AND event_type = ? AND occurred_at = ? ORDER BY id LIMIT 1",
+            )
+            .bind(plant_id)
+            .bind(&event.event_type)
+            .bind(&event.occurred_at)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(db_error)?
+        };
+        let (id, outcome, fields) = if let Some((id, notes)) = existing {
+            let fields = if notes == event.notes {
+                Vec::new()
+            } else {
+                vec!["notes".to_string()]
+            };
+            (id, outcome_of(&fields), fields)
+        } else {
+            let child_plant_id = event.child_plant_id.and_then(|id| plants.get(id));
+            let photo_path = photos.resolve_optional(event.photo_path.as_deref()).await?;
+            let id = insert_care_event(conn, event, plant_id, child_plant_id, photo_path)
+                .await
+                .map_err(db_error)?;
+            (id, ADDED, Vec::new())
+        };
+
+        map.insert(event.id, id, outcome);
+        report.push(MergeItem {
+            entity: ENTITY_CARE_EVENT,
+            archive_id: event.id,
+            id,
+            label: format!("{} {}", event.event_type, event.occurred_at),
+            outcome,
+            fields,
+        });
+    }
+    Ok(map)
+}
+
+async fn merge_care_event_photos(
+    conn: &mut SqliteConnection,
+    data: &ImportData,
+    events: &IdMap,
+    photos: &mut PhotoCopier<'_>,
+) -> Result<(), ApiError> {
+    for photo in &data.care_event_photos {
+        // Photos of matched events are left as they are
+        if !events.is_added(photo.care_event_id) {
+            continue;
+        }
+        let filename = photos.resolve(&photo.filename).await?;
+        sqlx::query(
+            "INSERT INTO care_event_photos (care_event_id, filename, position, created_at) \
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(events.get(photo.care_event_id))
+        .bind(&filename)
+        .bind(photo.position)
+        .bind(&photo.created_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+/// Match measurements by plant, metric and time, adding the ones that do not exist yet.
+async fn merge_measurements(
+    conn: &mut SqliteConnection,
+    measurements: &[ImportMeasurement],
+    plants: &IdMap,
+    events: &IdMap,
+    report: &mut MergeReport,
+) -> Result<(), ApiError> {
+    for m in measurements {
+        validate_measurement(&m.metric, m.value)?;
+        let plant_id = plants
+            .get(m.plant_id)
+            .ok_or(ApiError::BadRequest("IMPORT_INVALID_DATA"))?;
+
+        let existing = if plants.is_added(m.plant_id) {
+            None
+        } else {
+            sqlx::query_as::<_, (i64, f64, Option<String>)>(
+                "SELECT id, value, notes FROM measurements \
+                 WHERE plant_id = ? AND metric = ? AND measured_at = ? ORDER BY id LIMIT 1",
+            )
+            .bind(plant_id)
+            .bind(&m.metric)
+            .bind(&m.measured_at)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(db_error)?
+        };
+        let (id, outcome, fields) = if let Some((id, value, notes)) = existing {
+            let mut fields = Vec::new();
+            if (value - m.value).abs() > f64::EPSILON {
+                fields.push("value".to_string());
+            }
+            if notes != m.notes {
+                fields.push("notes".to_string());
+            }
+            (id, outcome_of(&fields), fields)
+        } else {
+            let id = sqlx::query_scalar::<_, i64>(
+                "INSERT INTO measurements (plant_id, care_event_id, metric, value, notes, \
+                 measured_at, created_at) VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING id",
+            )
+            .bind(plant_id)
+            .bind(m.care_event_id.and_then(|id| events.get(id)))
+            .bind(&m.metric)
+            .bind(m.value)
+            .bind(&m.notes)
+            .bind(&m.measured_at)
+            .bind(&m.created_at)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(db_error)?;
+            (id, ADDED, Vec::new())
+        };
+
+        report.push(MergeItem {
+            entity: ENTITY_MEASUREMENT,
+            archive_id: m.id,
+            id,
+            label: format!("{} {}", m.metric, m.measured_at),
+            outcome,
+            fields,
+        });
+    }
+    Ok(())
+}
+
+/// Insert the history of added plants and care events under their new ids.
+async fn merge_history(
+    conn: &mut SqliteConnection,
+    entries: &[ImportHistoryEntry],
+    plants: &IdMap,
+    events: &IdMap,
+) -> Result<(), sqlx::Error> {
+    for entry in entries {
+        let ids = match entry.entity_type.as_str() {
+            ENTITY_PLANT => plants,
+            ENTITY_CARE_EVENT => events,
+            _ => continue,
+        };
+        if !ids.is_added(entry.entity_id) {
+            continue;
+        }
+
+        sqlx::query(
+            "INSERT INTO change_history (entity_type, entity_id, plant_id, action, changes, changed_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&entry.entity_type)
+        .bind(ids.get(entry.entity_id))
+        .bind(entry.plant_id.and_then(|id| plants.get(id)))
+        .bind(&entry.action)
+        .bind(entry.changes.to_string())
+        .bind(&entry.changed_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Merge an archive into the existing data in one transaction. Archive ids are remapped,
+/// locations and tags are matched by name, and plants, care events and measurements that
+/// already exist are skipped, or reported as conflicts when they differ.
+///
+/// # Errors
+/// Returns `ApiError::BadRequest` or `ApiError::Validation` for invalid archive data, or
+/// `ApiError::InternalError` on database or file system failures.
+pub(crate) async fn merge_archive(
+    pool: &SqlitePool,
+    upload_dir: &Path,
+    data: &ImportData,
+    photos: &ArchivePhotos,
+) -> Result<MergeReport, ApiError> {
+    let mut report = MergeReport::default();
+    let mut photos = PhotoCopier::new(upload_dir, photos);
+    let mut tx = pool.begin().await.map_err(db_error)?;
+
+    let locations = data.locations.iter().map(|l| (l.id, l.name.as_str()));
+    let locations = merge_names(&mut tx, ENTITY_LOCATION, locations, &mut report).await?;
+    let tags = data.tags.iter().map(|t| (t.id, t.name.as_str()));
+    let tags = merge_names(&mut tx, ENTITY_TAG, tags, &mut report).await?;
+
+    let plants = merge_plants(&mut tx, &data.plants, &locations, &mut photos, &mut report).await?;
+    merge_plant_tags_and_photos(&mut tx, data, &plants, &tags, &mut photos).await?;
+    let events = merge_care_events(
+        &mut tx,
+        &data.care_events,
+        &plants,
+        &mut photos,
+        &mut report,
+    )
+    .await?;
+    merge_care_event_photos(&mut tx, data, &events, &mut photos).await?;
+    backfill_plant_photos(&mut tx).await?;
+    backfill_care_event_photos(&mut tx).await?;
+    merge_measurements(&mut tx, &data.measurements, &plants, &events, &mut report).await?;
+    merge_history(&mut tx, &data.history, &plants, &events)
+        .await
+        .map_err(db_error)?;
+
+    // Copy photos before committing, so a failed copy leaves the data unchanged
+    photos.write(&mut report.photos).await?;
+    tx.commit().await.map_err(db_error)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::restore::test_archive;
+
+    #[tokio::test]
+    async fn photos_are_copied_for_added_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("same.jpg"), b"same").unwrap();
+        std::fs::write(dir.path().join("taken.jpg"), b"local").unwrap();
+        let data = serde_json::json!({
+            "version": "1.5.0", "locations": [], "plants": [], "care_events": []
+        });
+        let (_, archive) = test_archive(
+            &data,
+            &[
+                ("same.jpg", b"same"),
+                ("taken.jpg", b"other"),
+                ("new.jpg", b"new"),
+                ("unused.jpg", b"x"),
+            ],
+        );
+        let mut photos = PhotoCopier::new(dir.path(), &archive);
+
+        let copied = photos.resolve("same.jpg").await.ok().unwrap();
+        assert_ne!(copied, "same.jpg");
+        assert_eq!(photos.resolve("new.jpg").await.ok().unwrap(), "new.jpg");
+        let renamed = photos.resolve("taken.jpg").await.ok().unwrap();
+        assert_ne!(renamed, "taken.jpg");
+        assert_eq!(Path::new(&renamed).extension(), Some("jpg".as_ref()));
+        assert_eq!(photos.resolve("taken.jpg").await.ok().unwrap(), renamed);
+        assert_eq!(
+            photos.resolve("missing.jpg").await.ok().unwrap(),
+            "missing.jpg"
+        );
+        assert!(photos.resolve("../escape.jpg").await.is_err());
+
+        let mut counts = MergeCounts::default();
+        photos.write(&mut counts).await.ok().unwrap();
+        assert_eq!((counts.added, counts.skipped, counts.conflicted), (2, 1, 1));
+        assert_eq!(std::fs::read(dir.path().join("same.jpg")).unwrap(), b"same");
+        assert_eq!(std::fs::read(dir.path().join(&copied)).unwrap(), b"same");
+        assert_eq!(
+            std::fs::read(dir.path().join("taken.jpg")).unwrap(),
+            b"local"
+        );
+        assert_eq!(std::fs::read(dir.path().join(&renamed)).unwrap(), b"other");
+        assert_eq!(std::fs::read(dir.path().join("new.jpg")).unwrap(), b"new");
+        assert!(!dir.path().join("unused.jpg").exists());
+    }
+
+    #[test]
+    fn report_lists_named_entries_and_conflicts() {
+        let item = |entity, outcome| MergeItem {
+            entity,
+            archive_id: 1,
+            id: 1,
+            label: String::new(),
+            outcome,
+            fields: Vec::new(),
+        };
+        let mut report = MergeReport::default();
+        report.push(item(ENTITY_PLANT, SKIPPED));
+        report.push(item(ENTITY_CARE_EVENT, ADDED));
+        report.push(item(ENTITY_CARE_EVENT, CONFLICTED));
+        report.push(item(ENTITY_MEASUREMENT, SKIPPED));
+        assert_eq!(report.plants.skipped, 1);
+        assert_eq!(
+            (report.care_events.added, report.care_events.conflicted),
+            (1, 1)
+        );
+        assert_eq!(report.measurements.skipped, 1);
+        assert_eq!(report.items.len(), 2);
+    }
+}
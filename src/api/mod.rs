@@ -1,14 +1,30 @@
 pub mod ai;
 pub mod backup;
+pub mod backup_format;
+pub mod backups;
 pub mod care_events;
+pub mod csv_export;
+pub mod csv_import;
+pub mod duplicate;
 pub mod error;
+pub mod events;
+pub mod heatmap;
+pub mod history;
+pub mod lineage;
 pub mod locations;
+pub mod measurements;
+pub mod merge;
 pub mod mqtt;
 pub mod photos;
 pub mod plants;
 pub mod restore;
+pub mod search;
 pub mod settings;
 pub mod stats;
+pub mod tags;
+pub mod time_zone;
+pub mod timelapse;
+pub mod trash;
 
 use axum::Router;
 use axum::extract::DefaultBodyLimit;
@@ -16,11 +32,82 @@ use axum::routing::{delete, get, post, put};
 
 use crate::state::AppState;
 
+/// Plant gallery, time-lapse and care event photo routes.
+fn photo_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/plants/{id}/photo",
+            post(photos::upload_photo)
+                .delete(photos::delete_photo)
+                .layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
+        )
+        .route(
+            "/plants/{id}/photos",
+            get(photos::list_photos)
+                .post(photos::upload_gallery_photo)
+                .put(photos::reorder_photos)
+                .layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
+        )
+        .route(
+            "/plants/{id}/photos/{photo_id}",
+            put(photos::update_photo).delete(photos::delete_gallery_photo),
+        )
+        .route(
+            "/plants/{id}/photos/{photo_id}/cover",
+            post(photos::set_cover_photo),
+        )
+        .route("/plants/{id}/timelapse", get(timelapse::get_timelapse))
+        .route(
+            "/plants/{id}/care/{event_id}/photo",
+            post(care_events::upload_care_event_photo)
+                .delete(care_events::delete_care_event_photo)
+                .layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
+        )
+        .route(
+            "/plants/{id}/care/{event_id}/photos",
+            put(care_events::reorder_care_event_photos),
+        )
+        .route(
+            "/plants/{id}/care/{event_id}/photos/{photo_id}",
+            delete(care_events::delete_care_event_photo_by_id),
+        )
+}
+
+/// Export, import and stored backup routes.
+fn data_routes() -> Router<AppState> {
+    Router::new()
+        .route("/data/export", get(backup::export_data))
+        .route(
+            "/data/export/plants.csv",
+            get(csv_export::export_plants_csv),
+        )
+        .route(
+            "/data/export/care.csv",
+            get(csv_export::export_care_events_csv),
+        )
+        .route(
+            "/data/import",
+            post(restore::import_data).layer(DefaultBodyLimit::max(100 * 1024 * 1024)),
+        )
+        .route(
+            "/data/import/plants.csv",
+            post(csv_import::import_plants_csv).layer(DefaultBodyLimit::max(5 * 1024 * 1024)),
+        )
+        .route(
+            "/backups",
+            get(backups::list_backups).post(backups::create_backup),
+        )
+        .route("/backups/{name}", get(backups::download_backup))
+        .route("/backups/{name}/restore", post(backups::restore_backup))
+}
+
 pub fn router(state: AppState) -> Router {
     Router::new()
         .route(
             "/plants",
-            get(plants::list_plants).post(plants::create_plant),
+            get(plants::list_plants)
+                .post(plants::create_plant)
+                .patch(plants::bulk_update_plants),
         )
         .route(
             "/plants/{id}",
@@ -29,6 +116,13 @@ pub fn router(state: AppState) -> Router {
                 .delete(plants::delete_plant),
         )
         .route("/plants/{id}/water", post(plants::water_plant))
+        .route("/plants/{id}/duplicate", post(duplicate::duplicate_plant))
+        .route("/plants/{id}/lineage", get(lineage::get_lineage))
+        .route("/plants/{id}/history", get(history::list_plant_history))
+        .route(
+            "/plants/{id}/history/{history_id}/revert",
+            post(history::revert_plant),
+        )
         .route(
             "/plants/{id}/care",
             get(care_events::list_care_events).post(care_events::create_care_event),
@@ -37,8 +131,25 @@ pub fn router(state: AppState) -> Router {
             "/plants/{id}/care/{event_id}",
             delete(care_events::delete_care_event),
         )
+        .route(
+            "/plants/{id}/measurements",
+            get(measurements::list_measurements).post(measurements::create_measurement),
+        )
+        .route(
+            "/plants/{id}/measurements/series",
+            get(measurements::measurement_series),
+        )
+        .route(
+            "/plants/{id}/measurements/{measurement_id}",
+            put(measurements::update_measurement).delete(measurements::delete_measurement),
+        )
         .route("/care", get(care_events::list_all_care_events))
+        .route("/care/heatmap", get(heatmap::get_care_heatmap))
+        .route("/search", get(search::search))
         .route("/stats", get(stats::get_stats))
+        .route("/stats/care", get(stats::get_care_stats))
+        .route("/plants/{id}/stats", get(stats::get_plant_stats))
+        .route("/events", get(events::stream_events))
         .route("/ai/status", get(ai::get_ai_status))
         .route(
             "/ai/identify",
@@ -51,23 +162,8 @@ pub fn router(state: AppState) -> Router {
         .route("/ai/summarize", post(ai::summarize))
         .route("/mqtt/status", get(mqtt::get_mqtt_status))
         .route("/mqtt/repair", post(mqtt::post_mqtt_repair))
-        .route("/data/export", get(backup::export_data))
-        .route(
-            "/data/import",
-            post(restore::import_data).layer(DefaultBodyLimit::max(100 * 1024 * 1024)),
-        )
-        .route(
-            "/plants/{id}/photo",
-            axum::routing::post(photos::upload_photo)
-                .delete(photos::delete_photo)
-                .layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
-        )
-        .route(
-            "/plants/{id}/care/{event_id}/photo",
-            axum::routing::post(care_events::upload_care_event_photo)
-                .delete(care_events::delete_care_event_photo)
-                .layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
-        )
+        .merge(photo_routes())
+        .merge(data_routes())
         .route(
             "/settings",
             get(settings::get_settings).put(settings::update_settings),
@@ -76,6 +172,13 @@ pub fn router(state: AppState) -> Router {
             "/locations",
             get(locations::list_locations).post(locations::create_location),
         )
+        .route("/tags", get(tags::list_tags).post(tags::create_tag))
+        .route("/tags/{id}", put(tags::update_tag).delete(tags::delete_tag))
+        .route("/trash", get(trash::list_trash).delete(trash::empty_trash))
+        .route("/trash/plants/{id}", delete(trash::purge_plant))
+        .route("/trash/plants/{id}/restore", post(trash::restore_plant))
+        .route("/trash/care/{id}", delete(trash::purge_care_event))
+        .route("/trash/care/{id}/restore", post(trash::restore_care_event))
         .route(
             "/locations/{id}",
             put(locations::update_location).delete(locations::delete_location),
@@ -68,6 +68,7 @@ pub async fn post_mqtt_repair(
     let result = mqtt::repair(
         &state.pool,
         client,
+        &state.metrics,
         &state.mqtt_host,
         state.mqtt_port,
         &state.mqtt_prefix,
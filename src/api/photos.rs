@@ -1,14 +1,250 @@
 use axum::Json;
 use axum::extract::{Multipart, Path, State};
 use axum::http::StatusCode;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
 
 use tracing::info;
 
-use super::error::{ApiError, db_error};
-use super::plants::{PLANT_SELECT, Plant, PlantRow};
+use super::error::{ApiError, JsonBody, db_error};
+use super::plants::{Plant, deserialize_nullable, fetch_plant};
+use crate::events::ChangeEvent;
 use crate::images::ImageError;
 use crate::state::AppState;
 
+/// A photo in a plant's gallery. The cover photo is the one shown as the plant's `photo_url`.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct PlantPhoto {
+    pub id: i64,
+    pub plant_id: i64,
+    pub url: String,
+    pub caption: Option<String>,
+    pub taken_at: Option<String>,
+    pub position: i64,
+    pub is_cover: bool,
+    pub created_at: String,
+}
+
+const PHOTO_SELECT: &str = "SELECT pp.id, pp.plant_id, '/uploads/' || pp.filename AS url, \
+    pp.caption, pp.taken_at, pp.position, pp.filename IS p.photo_path AS is_cover, pp.created_at \
+    FROM plant_photos pp JOIN plants p ON p.id = pp.plant_id";
+
+#[derive(Deserialize)]
+pub struct UpdatePhoto {
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub caption: Option<Option<String>>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub taken_at: Option<Option<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct ReorderPhotos {
+    pub ids: Vec<i64>,
+}
+
+pub(crate) fn image_error(e: &ImageError) -> ApiError {
+    match e {
+        ImageError::InvalidContentType => ApiError::Validation("PHOTO_INVALID_TYPE"),
+        ImageError::TooLarge => ApiError::Validation("PHOTO_TOO_LARGE"),
+        ImageError::Io(io_err) => {
+            tracing::error!("Photo save failed: {io_err}");
+            ApiError::InternalError("PHOTO_SAVE_FAILED")
+        }
+    }
+}
+
+/// Trim optional free text such as captions and notes, dropping it when empty.
+pub(crate) fn normalize_text(text: Option<String>) -> Option<String> {
+    text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+fn validate_taken_at(taken_at: Option<&str>) -> Result<(), ApiError> {
+    match taken_at {
+        Some(date) if date.parse::<NaiveDate>().is_err() => {
+            Err(ApiError::Validation("PHOTO_INVALID_TAKEN_AT"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The current cover photo of a plant that is not in the trash.
+async fn current_cover(pool: &SqlitePool, id: i64) -> Result<Option<String>, ApiError> {
+    sqlx::query_scalar::<_, Option<String>>(
+        "SELECT photo_path FROM plants WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))
+}
+
+async fn fetch_photos(pool: &SqlitePool, plant_id: i64) -> Result<Vec<PlantPhoto>, sqlx::Error> {
+    let query = format!("{PHOTO_SELECT} WHERE pp.plant_id = ? ORDER BY pp.position, pp.id");
+    sqlx::query_as::<_, PlantPhoto>(sqlx::AssertSqlSafe(query))
+        .bind(plant_id)
+        .fetch_all(pool)
+        .await
+}
+
+async fn fetch_photo(
+    pool: &SqlitePool,
+    plant_id: i64,
+    photo_id: i64,
+) -> Result<PlantPhoto, ApiError> {
+    let query =
+        format!("{PHOTO_SELECT} WHERE pp.id = ? AND pp.plant_id = ? AND p.deleted_at IS NULL");
+    sqlx::query_as::<_, PlantPhoto>(sqlx::AssertSqlSafe(query))
+        .bind(photo_id)
+        .bind(plant_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("PHOTO_NOT_FOUND"))
+}
+
+/// Append a photo to the end of a plant's gallery.
+pub(crate) async fn insert_photo(
+    conn: &mut SqliteConnection,
+    plant_id: i64,
+    filename: &str,
+    caption: Option<&str>,
+    taken_at: Option<&str>,
+    now: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(
+        "INSERT INTO plant_photos (plant_id, filename, caption, taken_at, position, created_at) \
+         SELECT ?1, ?2, ?3, ?4, COALESCE(MAX(position) + 1, 0), ?5 \
+         FROM plant_photos WHERE plant_id = ?1 RETURNING id",
+    )
+    .bind(plant_id)
+    .bind(filename)
+    .bind(caption)
+    .bind(taken_at)
+    .bind(now)
+    .fetch_one(&mut *conn)
+    .await
+}
+
+async fn set_cover(
+    conn: &mut SqliteConnection,
+    plant_id: i64,
+    filename: Option<&str>,
+    now: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE plants SET photo_path = ?, updated_at = ? WHERE id = ?")
+        .bind(filename)
+        .bind(now)
+        .bind(plant_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// Save an uploaded file and add it to the gallery, making it the cover if requested
+/// or if the plant has none yet.
+async fn add_photo(
+    state: &AppState,
+    plant_id: i64,
+    data: &[u8],
+    content_type: &str,
+    caption: Option<String>,
+    taken_at: Option<String>,
+    cover: bool,
+) -> Result<i64, ApiError> {
+    let filename = state
+        .image_store
+        .save(data, content_type)
+        .await
+        .map_err(|e| image_error(&e))?;
+
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let taken_at = taken_at.unwrap_or_else(|| chrono::Utc::now().date_naive().to_string());
+    let inserted = async {
+        let mut tx = state.pool.begin().await?;
+        let photo_id = insert_photo(
+            &mut tx,
+            plant_id,
+            &filename,
+            caption.as_deref(),
+            Some(&taken_at),
+            &now,
+        )
+        .await?;
+        if cover {
+            set_cover(&mut tx, plant_id, Some(&filename), &now).await?;
+        }
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(photo_id)
+    }
+    .await;
+
+    match inserted {
+        Ok(photo_id) => {
+            info!(plant_id, photo_id, filename = %filename, "Photo uploaded");
+            state.events.publish(ChangeEvent::PhotoChanged {
+                plant_id,
+                event_id: None,
+            });
+            Ok(photo_id)
+        }
+        Err(e) => {
+            state.image_store.delete(&filename).await;
+            Err(db_error(e))
+        }
+    }
+}
+
+/// Remove a photo from the gallery and disk. If it was the cover, the first remaining
+/// photo becomes the cover.
+async fn remove_photo(state: &AppState, plant_id: i64, photo_id: i64) -> Result<(), ApiError> {
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let filename = sqlx::query_scalar::<_, String>(
+        "DELETE FROM plant_photos WHERE id = ? AND plant_id = ? RETURNING filename",
+    )
+    .bind(photo_id)
+    .bind(plant_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::NotFound("PHOTO_NOT_FOUND"))?;
+
+    sqlx::query(
+        "UPDATE plants SET photo_path = (SELECT filename FROM plant_photos \
+         WHERE plant_id = ?1 ORDER BY position, id LIMIT 1), updated_at = ?2 \
+         WHERE id = ?1 AND photo_path IS ?3",
+    )
+    .bind(plant_id)
+    .bind(&now)
+    .bind(&filename)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    state.image_store.delete(&filename).await;
+
+    info!(plant_id, photo_id, "Photo deleted");
+    state.events.publish(ChangeEvent::PhotoChanged {
+        plant_id,
+        event_id: None,
+    });
+    Ok(())
+}
+
+async fn plant_response(pool: &SqlitePool, id: i64) -> Result<Plant, ApiError> {
+    fetch_plant(pool, id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))
+}
+
+/// Upload a photo and make it the plant's cover. Earlier photos stay in the gallery.
+///
 /// # Errors
 /// Returns `ApiError::NotFound` if the plant does not exist,
 /// `ApiError::Validation` for invalid file types or oversized files, or
@@ -18,13 +254,7 @@ pub async fn upload_photo(
     Path(id): Path<i64>,
     mut multipart: Multipart,
 ) -> Result<Json<Plant>, ApiError> {
-    let current_photo =
-        sqlx::query_scalar::<_, Option<String>>("SELECT photo_path FROM plants WHERE id = ?")
-            .bind(id)
-            .fetch_optional(&state.pool)
-            .await
-            .map_err(db_error)?
-            .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+    current_cover(&state.pool, id).await?;
 
     let field = multipart
         .next_field()
@@ -38,71 +268,231 @@ pub async fn upload_photo(
         .await
         .map_err(|_| ApiError::BadRequest("INVALID_REQUEST_BODY"))?;
 
-    let filename = state
-        .image_store
-        .save(&data, &content_type)
-        .await
-        .map_err(|e| match e {
-            ImageError::InvalidContentType => ApiError::Validation("PHOTO_INVALID_TYPE"),
-            ImageError::TooLarge => ApiError::Validation("PHOTO_TOO_LARGE"),
-            ImageError::Io(ref io_err) => {
-                tracing::error!("Photo save failed: {io_err}");
-                ApiError::InternalError("PHOTO_SAVE_FAILED")
-            }
-        })?;
+    add_photo(&state, id, &data, &content_type, None, None, true).await?;
 
-    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
-    sqlx::query("UPDATE plants SET photo_path = ?, updated_at = ? WHERE id = ?")
-        .bind(&filename)
-        .bind(&now)
-        .bind(id)
-        .execute(&state.pool)
-        .await
-        .map_err(db_error)?;
+    Ok(Json(plant_response(&state.pool, id).await?))
+}
 
-    if let Some(ref old_filename) = current_photo {
-        state.image_store.delete(old_filename).await;
+/// Delete the plant's cover photo. The next photo in the gallery, if any, becomes the cover.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist or has no photo, or
+/// `ApiError::InternalError` on database failures.
+pub async fn delete_photo(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    let filename = current_cover(&state.pool, id)
+        .await?
+        .ok_or(ApiError::NotFound("PHOTO_NOT_FOUND"))?;
+
+    let photo_id = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM plant_photos WHERE plant_id = ? AND filename = ?",
+    )
+    .bind(id)
+    .bind(&filename)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::NotFound("PHOTO_NOT_FOUND"))?;
+
+    remove_photo(&state, id, photo_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List a plant's photos in gallery order.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist, or
+/// `ApiError::InternalError` on database failures.
+pub async fn list_photos(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<PlantPhoto>>, ApiError> {
+    current_cover(&state.pool, id).await?;
+    let photos = fetch_photos(&state.pool, id).await.map_err(db_error)?;
+    Ok(Json(photos))
+}
+
+/// Add a photo to the gallery from a multipart form with a `file` field and optional
+/// `caption`, `taken_at` (YYYY-MM-DD, defaults to today) and `cover` (`true`) fields.
+/// The first photo of a plant always becomes its cover.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist,
+/// `ApiError::Validation` for a missing, invalid or oversized file or an invalid date, or
+/// `ApiError::InternalError` on multipart parsing or database failures.
+pub async fn upload_gallery_photo(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<PlantPhoto>), ApiError> {
+    let current = current_cover(&state.pool, id).await?;
+
+    let mut file = None;
+    let mut caption = None;
+    let mut taken_at = None;
+    let mut cover = false;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| ApiError::BadRequest("INVALID_REQUEST_BODY"))?
+    {
+        let name = field.name().unwrap_or("").to_string();
+        if name == "file" {
+            let content_type = field.content_type().unwrap_or("").to_string();
+            let data = field
+                .bytes()
+                .await
+                .map_err(|_| ApiError::BadRequest("INVALID_REQUEST_BODY"))?;
+            file = Some((data, content_type));
+            continue;
+        }
+        let text = field
+            .text()
+            .await
+            .map_err(|_| ApiError::BadRequest("INVALID_REQUEST_BODY"))?;
+        match name.as_str() {
+            "caption" => caption = normalize_text(Some(text)),
+            "taken_at" if !text.trim().is_empty() => taken_at = Some(text.trim().to_string()),
+            "cover" => cover = text == "true",
+            _ => {}
+        }
     }
+    let (data, content_type) = file.ok_or(ApiError::Validation("PHOTO_NO_FILE"))?;
+    validate_taken_at(taken_at.as_deref())?;
 
-    info!(plant_id = id, filename = %filename, "Photo uploaded");
+    let cover = cover || current.is_none();
+    let photo_id = add_photo(&state, id, &data, &content_type, caption, taken_at, cover).await?;
+    let photo = fetch_photo(&state.pool, id, photo_id).await?;
+    Ok((StatusCode::CREATED, Json(photo)))
+}
 
-    let query = format!("{PLANT_SELECT} WHERE p.id = ?");
-    let row = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(query.as_str()))
-        .bind(id)
-        .fetch_one(&state.pool)
+/// Change a gallery photo's caption or taken-at date.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant or photo does not exist,
+/// `ApiError::Validation` for an invalid date, or
+/// `ApiError::InternalError` on database failures.
+pub async fn update_photo(
+    State(state): State<AppState>,
+    Path((id, photo_id)): Path<(i64, i64)>,
+    JsonBody(body): JsonBody<UpdatePhoto>,
+) -> Result<Json<PlantPhoto>, ApiError> {
+    let current = fetch_photo(&state.pool, id, photo_id).await?;
+
+    let caption = match body.caption {
+        Some(caption) => normalize_text(caption),
+        None => current.caption,
+    };
+    let taken_at = match body.taken_at {
+        Some(taken_at) => taken_at,
+        None => current.taken_at,
+    };
+    validate_taken_at(taken_at.as_deref())?;
+
+    sqlx::query("UPDATE plant_photos SET caption = ?, taken_at = ? WHERE id = ?")
+        .bind(&caption)
+        .bind(&taken_at)
+        .bind(photo_id)
+        .execute(&state.pool)
         .await
         .map_err(db_error)?;
 
-    Ok(Json(Plant::from(row)))
+    state.events.publish(ChangeEvent::PhotoChanged {
+        plant_id: id,
+        event_id: None,
+    });
+    Ok(Json(fetch_photo(&state.pool, id, photo_id).await?))
 }
 
+/// Delete a gallery photo. Deleting the cover makes the next photo the cover.
+///
 /// # Errors
-/// Returns `ApiError::NotFound` if the plant does not exist or has no photo, or
+/// Returns `ApiError::NotFound` if the plant or photo does not exist, or
 /// `ApiError::InternalError` on database failures.
-pub async fn delete_photo(
+pub async fn delete_gallery_photo(
     State(state): State<AppState>,
-    Path(id): Path<i64>,
+    Path((id, photo_id)): Path<(i64, i64)>,
 ) -> Result<StatusCode, ApiError> {
-    let photo_path =
-        sqlx::query_scalar::<_, Option<String>>("SELECT photo_path FROM plants WHERE id = ?")
-            .bind(id)
-            .fetch_optional(&state.pool)
-            .await
-            .map_err(db_error)?
-            .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
-
-    let filename = photo_path.ok_or(ApiError::NotFound("PHOTO_NOT_FOUND"))?;
+    current_cover(&state.pool, id).await?;
+    remove_photo(&state, id, photo_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
-    sqlx::query("UPDATE plants SET photo_path = NULL, updated_at = ? WHERE id = ?")
+/// Make a gallery photo the plant's cover, which is shown as its `photo_url`.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant or photo does not exist, or
+/// `ApiError::InternalError` on database failures.
+pub async fn set_cover_photo(
+    State(state): State<AppState>,
+    Path((id, photo_id)): Path<(i64, i64)>,
+) -> Result<Json<Plant>, ApiError> {
+    let photo = fetch_photo(&state.pool, id, photo_id).await?;
+    if !photo.is_cover {
+        let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        sqlx::query(
+            "UPDATE plants SET photo_path = (SELECT filename FROM plant_photos WHERE id = ?), \
+             updated_at = ? WHERE id = ?",
+        )
+        .bind(photo_id)
         .bind(&now)
         .bind(id)
         .execute(&state.pool)
         .await
         .map_err(db_error)?;
 
-    state.image_store.delete(&filename).await;
+        info!(plant_id = id, photo_id, "Cover photo changed");
+        state.events.publish(ChangeEvent::PhotoChanged {
+            plant_id: id,
+            event_id: None,
+        });
+    }
+    Ok(Json(plant_response(&state.pool, id).await?))
+}
 
-    info!(plant_id = id, "Photo deleted");
-    Ok(StatusCode::NO_CONTENT)
+/// Reorder a plant's gallery. `ids` must list every photo of the plant exactly once.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist,
+/// `ApiError::Validation` if `ids` does not match the plant's photos, or
+/// `ApiError::InternalError` on database failures.
+pub async fn reorder_photos(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    JsonBody(body): JsonBody<ReorderPhotos>,
+) -> Result<Json<Vec<PlantPhoto>>, ApiError> {
+    current_cover(&state.pool, id).await?;
+
+    let mut current: Vec<i64> = fetch_photos(&state.pool, id)
+        .await
+        .map_err(db_error)?
+        .iter()
+        .map(|photo| photo.id)
+        .collect();
+    let mut requested = body.ids.clone();
+    current.sort_unstable();
+    requested.sort_unstable();
+    if current != requested {
+        return Err(ApiError::Validation("PHOTO_ORDER_MISMATCH"));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    for (position, photo_id) in (0_i64..).zip(&body.ids) {
+        sqlx::query("UPDATE plant_photos SET position = ? WHERE id = ?")
+            .bind(position)
+            .bind(photo_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+    }
+    tx.commit().await.map_err(db_error)?;
+
+    state.events.publish(ChangeEvent::PhotoChanged {
+        plant_id: id,
+        event_id: None,
+    });
+    let photos = fetch_photos(&state.pool, id).await.map_err(db_error)?;
+    Ok(Json(photos))
 }
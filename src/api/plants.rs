@@ -1,18 +1,25 @@
 use axum::Json;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use axum::response::IntoResponse;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 
 use tracing::{debug, info};
 
 use super::error::{ApiError, JsonBody, db_error};
+use super::history::{self, ENTITY_PLANT, PlantSnapshot};
+use super::lineage;
+use super::tags::{self, normalize_tag_names};
+use crate::events::ChangeEvent;
 use crate::mqtt;
 use crate::state::AppState;
 
 #[allow(clippy::option_option)]
-fn deserialize_nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+pub(crate) fn deserialize_nullable<'de, T, D>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error>
 where
     T: serde::Deserialize<'de>,
     D: serde::Deserializer<'de>,
@@ -41,6 +48,11 @@ pub struct Plant {
     pub soil_type: Option<String>,
     pub soil_moisture: Option<String>,
     pub notes: Option<String>,
+    pub lifecycle_status: String,
+    pub lifecycle_date: Option<String>,
+    pub lifecycle_reason: Option<String>,
+    pub parent_id: Option<i64>,
+    pub tags: Vec<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -63,6 +75,12 @@ pub(crate) struct PlantRow {
     pub(crate) soil_type: Option<String>,
     pub(crate) soil_moisture: Option<String>,
     pub(crate) notes: Option<String>,
+    pub(crate) lifecycle_status: String,
+    pub(crate) lifecycle_date: Option<String>,
+    pub(crate) lifecycle_reason: Option<String>,
+    pub(crate) parent_id: Option<i64>,
+    /// Tag names as a JSON array.
+    pub(crate) tags: String,
     pub(crate) created_at: String,
     pub(crate) updated_at: String,
 }
@@ -103,6 +121,9 @@ const VALID_PET_SAFETY: &[&str] = &["safe", "caution", "toxic"];
 const VALID_GROWTH_SPEED: &[&str] = &["slow", "moderate", "fast"];
 const VALID_SOIL_TYPE: &[&str] = &["standard", "cactus-mix", "orchid-bark", "peat-moss"];
 const VALID_SOIL_MOISTURE: &[&str] = &["dry", "moderate", "moist"];
+const VALID_LIFECYCLE_STATUS: &[&str] = &["active", "archived", "gifted", "deceased"];
+
+pub(crate) const LIFECYCLE_ACTIVE: &str = "active";
 
 /// # Errors
 /// Returns `ApiError::Validation` if the name is empty or whitespace-only.
@@ -171,6 +192,118 @@ pub fn validate_all_care_info(
     Ok(())
 }
 
+/// # Errors
+/// Returns `ApiError::Validation` if the value is not a lifecycle status.
+pub fn validate_lifecycle_status(value: &str) -> Result<(), ApiError> {
+    if !VALID_LIFECYCLE_STATUS.contains(&value) {
+        return Err(ApiError::Validation("PLANT_INVALID_LIFECYCLE_STATUS"));
+    }
+    Ok(())
+}
+
+/// Lifecycle columns after applying an update.
+struct Lifecycle {
+    status: String,
+    date: Option<String>,
+    reason: Option<String>,
+}
+
+/// Merge a lifecycle update into the current values. Active plants carry no date or
+/// reason; leaving `active` without a date records today.
+fn resolve_lifecycle(current: &PlantRow, body: &UpdatePlant) -> Result<Lifecycle, ApiError> {
+    let status = body
+        .lifecycle_status
+        .clone()
+        .unwrap_or_else(|| current.lifecycle_status.clone());
+    validate_lifecycle_status(&status)?;
+
+    if status == LIFECYCLE_ACTIVE {
+        return Ok(Lifecycle {
+            status,
+            date: None,
+            reason: None,
+        });
+    }
+
+    let changed = status != current.lifecycle_status;
+    let date = match &body.lifecycle_date {
+        Some(date) => date.clone(),
+        None if changed => None,
+        None => current.lifecycle_date.clone(),
+    };
+    if let Some(date) = &date
+        && date.parse::<NaiveDate>().is_err()
+    {
+        return Err(ApiError::Validation("PLANT_INVALID_LIFECYCLE_DATE"));
+    }
+    let date = date.or_else(|| Some(chrono::Utc::now().date_naive().to_string()));
+    let reason = match &body.lifecycle_reason {
+        Some(reason) => reason.clone(),
+        None if changed => None,
+        None => current.lifecycle_reason.clone(),
+    };
+
+    Ok(Lifecycle {
+        status,
+        date,
+        reason,
+    })
+}
+
+/// Create a child plant of `parent_id` that inherits the parent's care profile and record
+/// its creation in the history. Returns the id of the child.
+pub(crate) async fn insert_child_plant(
+    conn: &mut SqliteConnection,
+    parent_id: i64,
+    name: Option<&str>,
+    now: &str,
+) -> Result<i64, sqlx::Error> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO plants (name, species, icon, location_id, watering_interval_days, light_needs, \
+         difficulty, pet_safety, growth_speed, soil_type, soil_moisture, parent_id, \
+         created_at, updated_at) \
+         SELECT COALESCE(?1, name), species, icon, location_id, watering_interval_days, \
+         light_needs, difficulty, pet_safety, growth_speed, soil_type, soil_moisture, id, ?2, ?2 \
+         FROM plants WHERE id = ?3 RETURNING id",
+    )
+    .bind(name)
+    .bind(now)
+    .bind(parent_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if let Some(snapshot) = history::plant_snapshot(&mut *conn, id).await? {
+        history::record(
+            &mut *conn,
+            ENTITY_PLANT,
+            id,
+            Some(id),
+            "created",
+            history::created(&snapshot),
+        )
+        .await?;
+    }
+    Ok(id)
+}
+
+/// Merge a parent update into the current parent, validating a newly set parent.
+#[allow(clippy::option_option)]
+async fn resolve_parent(
+    conn: &mut SqliteConnection,
+    id: i64,
+    current: Option<i64>,
+    update: Option<Option<i64>>,
+) -> Result<Option<i64>, ApiError> {
+    match update {
+        None => Ok(current),
+        Some(Some(parent_id)) if Some(parent_id) != current => {
+            lineage::validate_parent(conn, Some(id), parent_id).await?;
+            Ok(Some(parent_id))
+        }
+        Some(parent_id) => Ok(parent_id),
+    }
+}
+
 impl From<PlantRow> for Plant {
     fn from(row: PlantRow) -> Self {
         let (watering_status, next_due) =
@@ -195,6 +328,11 @@ impl From<PlantRow> for Plant {
             soil_type: row.soil_type,
             soil_moisture: row.soil_moisture,
             notes: row.notes,
+            lifecycle_status: row.lifecycle_status,
+            lifecycle_date: row.lifecycle_date,
+            lifecycle_reason: row.lifecycle_reason,
+            parent_id: row.parent_id,
+            tags: serde_json::from_str(&row.tags).unwrap_or_default(),
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
@@ -205,9 +343,22 @@ pub(crate) const PLANT_SELECT: &str = "SELECT p.id, p.name, p.species, p.icon, p
     p.location_id, l.name AS location_name, p.watering_interval_days, \
     lw.last_watered, \
     p.light_needs, p.difficulty, p.pet_safety, p.growth_speed, p.soil_type, p.soil_moisture, \
-    p.notes, p.created_at, p.updated_at \
+    p.notes, p.lifecycle_status, p.lifecycle_date, p.lifecycle_reason, p.parent_id, \
+    (SELECT json_group_array(name) FROM (SELECT t.name FROM plant_tags pt \
+     JOIN tags t ON t.id = pt.tag_id WHERE pt.plant_id = p.id ORDER BY t.name)) AS tags, \
+    p.created_at, p.updated_at \
     FROM plants p LEFT JOIN locations l ON p.location_id = l.id \
-    LEFT JOIN plant_last_watered lw ON lw.plant_id = p.id";
+    LEFT JOIN plant_last_watered lw ON lw.plant_id = p.id \
+    WHERE p.deleted_at IS NULL";
+
+/// Fetch a plant that is not in the trash.
+pub(crate) async fn fetch_plant(pool: &SqlitePool, id: i64) -> Result<Option<Plant>, sqlx::Error> {
+    sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!("{PLANT_SELECT} AND p.id = ?")))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map(|row| row.map(Plant::from))
+}
 
 #[derive(Deserialize)]
 pub struct CreatePlant {
@@ -223,6 +374,8 @@ pub struct CreatePlant {
     pub soil_type: Option<String>,
     pub soil_moisture: Option<String>,
     pub notes: Option<String>,
+    pub parent_id: Option<i64>,
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
@@ -255,18 +408,224 @@ pub struct UpdatePlant {
     #[allow(clippy::option_option)]
     #[serde(default, deserialize_with = "deserialize_nullable")]
     pub notes: Option<Option<String>>,
+    pub lifecycle_status: Option<String>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub lifecycle_date: Option<Option<String>>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub lifecycle_reason: Option<Option<String>>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub parent_id: Option<Option<i64>>,
+    pub tags: Option<Vec<String>>,
 }
 
+/// Fields that can be set on several plants at once. Plant-specific fields such as the
+/// name, notes or parent are rejected rather than copied to every plant.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BulkPlantChanges {
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub location_id: Option<Option<i64>>,
+    pub watering_interval_days: Option<i64>,
+    pub light_needs: Option<String>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub difficulty: Option<Option<String>>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub pet_safety: Option<Option<String>>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub growth_speed: Option<Option<String>>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub soil_type: Option<Option<String>>,
+    #[allow(clippy::option_option)]
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub soil_moisture: Option<Option<String>>,
+    pub tags: Option<Vec<String>>,
+}
+
+impl From<BulkPlantChanges> for UpdatePlant {
+    fn from(changes: BulkPlantChanges) -> Self {
+        Self {
+            name: None,
+            species: None,
+            icon: None,
+            location_id: changes.location_id,
+            watering_interval_days: changes.watering_interval_days,
+            light_needs: changes.light_needs,
+            difficulty: changes.difficulty,
+            pet_safety: changes.pet_safety,
+            growth_speed: changes.growth_speed,
+            soil_type: changes.soil_type,
+            soil_moisture: changes.soil_moisture,
+            notes: None,
+            lifecycle_status: None,
+            lifecycle_date: None,
+            lifecycle_reason: None,
+            parent_id: None,
+            tags: changes.tags,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BulkUpdatePlants {
+    pub ids: Vec<i64>,
+    pub changes: BulkPlantChanges,
+}
+
+#[derive(Deserialize)]
+pub struct PlantListQuery {
+    /// Lifecycle status to list, `all` for every plant. Defaults to active plants.
+    pub lifecycle: Option<String>,
+    /// Only list plants carrying the tag with this name.
+    pub tag: Option<String>,
+    pub location_id: Option<i64>,
+    /// Watering status: `ok`, `due` or `overdue`.
+    pub status: Option<String>,
+    pub light_needs: Option<String>,
+    pub pet_safety: Option<String>,
+    pub difficulty: Option<String>,
+    /// `name` (default), `next_due`, `last_watered` or `created_at`.
+    pub sort: Option<String>,
+    /// `asc` (default) or `desc`.
+    pub order: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+const VALID_WATERING_STATUS: &[&str] = &["ok", "due", "overdue"];
+
+const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// Largest page of plants returned when `limit` is given.
+const MAX_PLANT_PAGE: i64 = 500;
+
+/// SQL mirror of [`compute_watering_status`]: next due date, NULL if never watered.
+const NEXT_DUE_SQL: &str =
+    "date(substr(lw.last_watered, 1, 10), '+' || p.watering_interval_days || ' days')";
+
+/// Filters shared by the plant list and its total count, binding parameters `?1`–`?7`.
+fn plant_list_filter() -> String {
+    format!(
+        "AND (?1 = 'all' OR p.lifecycle_status = ?1) \
+         AND (?2 IS NULL OR EXISTS (SELECT 1 FROM plant_tags pt JOIN tags t ON t.id = pt.tag_id \
+         WHERE pt.plant_id = p.id AND t.name = ?2)) \
+         AND (?3 IS NULL OR p.location_id = ?3) \
+         AND (?4 IS NULL OR (CASE WHEN {NEXT_DUE_SQL} IS NULL THEN 'due' \
+              WHEN date('now') > {NEXT_DUE_SQL} THEN 'overdue' \
+              WHEN date('now') = {NEXT_DUE_SQL} THEN 'due' ELSE 'ok' END) = ?4) \
+         AND (?5 IS NULL OR p.light_needs = ?5) \
+         AND (?6 IS NULL OR p.pet_safety = ?6) \
+         AND (?7 IS NULL OR p.difficulty = ?7)"
+    )
+}
+
+fn validate_list_filters(params: &PlantListQuery) -> Result<(), ApiError> {
+    if let Some(lifecycle) = params.lifecycle.as_deref()
+        && lifecycle != "all"
+    {
+        validate_lifecycle_status(lifecycle)?;
+    }
+    validate_care_info(
+        params.status.as_deref(),
+        VALID_WATERING_STATUS,
+        "PLANT_INVALID_WATERING_STATUS",
+    )?;
+    if let Some(light_needs) = params.light_needs.as_deref() {
+        validate_light_needs(light_needs)?;
+    }
+    if params
+        .limit
+        .is_some_and(|limit| !(1..=MAX_PLANT_PAGE).contains(&limit))
+        || params.offset.is_some_and(|offset| offset < 0)
+    {
+        return Err(ApiError::Validation("PLANT_INVALID_PAGINATION"));
+    }
+    validate_all_care_info(
+        params.difficulty.as_deref(),
+        params.pet_safety.as_deref(),
+        None,
+        None,
+        None,
+    )
+}
+
+/// Map the `sort` and `order` parameters to an ORDER BY clause, ties broken by name.
+fn plant_list_order(sort: Option<&str>, order: Option<&str>) -> Result<String, ApiError> {
+    let column = match sort.unwrap_or("name") {
+        "name" => "p.name",
+        "next_due" => NEXT_DUE_SQL,
+        "last_watered" => "lw.last_watered",
+        "created_at" => "p.created_at",
+        _ => return Err(ApiError::Validation("PLANT_INVALID_SORT")),
+    };
+    let direction = match order.unwrap_or("asc") {
+        "asc" => "ASC",
+        "desc" => "DESC",
+        _ => return Err(ApiError::Validation("PLANT_INVALID_SORT")),
+    };
+    Ok(format!(
+        "ORDER BY {column} {direction}, p.name {direction}, p.id"
+    ))
+}
+
+/// List plants, optionally filtered, sorted and paginated. The `X-Total-Count` header
+/// carries the number of plants matching the filters regardless of `limit` and `offset`.
+///
 /// # Errors
-/// Returns `ApiError::InternalError` on database failures.
-pub async fn list_plants(State(pool): State<SqlitePool>) -> Result<Json<Vec<Plant>>, ApiError> {
-    let query = format!("{PLANT_SELECT} ORDER BY p.name");
-    let rows = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(query.as_str()))
+/// Returns `ApiError::Validation` if a filter, the sort or the page is invalid, or
+/// `ApiError::InternalError` on database failures.
+pub async fn list_plants(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<PlantListQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    validate_list_filters(&params)?;
+    let order_by = plant_list_order(params.sort.as_deref(), params.order.as_deref())?;
+    let lifecycle = params.lifecycle.as_deref().unwrap_or(LIFECYCLE_ACTIVE);
+    // A negative LIMIT means no limit in SQLite
+    let limit = params.limit.unwrap_or(-1);
+    let offset = params.offset.unwrap_or(0);
+    let filter = plant_list_filter();
+
+    let query = format!("{PLANT_SELECT} {filter} {order_by} LIMIT ?8 OFFSET ?9");
+    let rows = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(query))
+        .bind(lifecycle)
+        .bind(&params.tag)
+        .bind(params.location_id)
+        .bind(&params.status)
+        .bind(&params.light_needs)
+        .bind(&params.pet_safety)
+        .bind(&params.difficulty)
+        .bind(limit)
+        .bind(offset)
         .fetch_all(&pool)
         .await
         .map_err(db_error)?;
 
-    Ok(Json(rows.into_iter().map(Plant::from).collect()))
+    let count_query = format!(
+        "SELECT COUNT(*) FROM plants p LEFT JOIN plant_last_watered lw ON lw.plant_id = p.id \
+         WHERE p.deleted_at IS NULL {filter}"
+    );
+    let total = sqlx::query_scalar::<_, i64>(sqlx::AssertSqlSafe(count_query))
+        .bind(lifecycle)
+        .bind(&params.tag)
+        .bind(params.location_id)
+        .bind(&params.status)
+        .bind(&params.light_needs)
+        .bind(&params.pet_safety)
+        .bind(&params.difficulty)
+        .fetch_one(&pool)
+        .await
+        .map_err(db_error)?;
+
+    let plants: Vec<Plant> = rows.into_iter().map(Plant::from).collect();
+    Ok(([(TOTAL_COUNT_HEADER, total.to_string())], Json(plants)))
 }
 
 /// # Errors
@@ -276,7 +635,7 @@ pub async fn get_plant(
     State(pool): State<SqlitePool>,
     Path(id): Path<i64>,
 ) -> Result<Json<Plant>, ApiError> {
-    let query = format!("{PLANT_SELECT} WHERE p.id = ?");
+    let query = format!("{PLANT_SELECT} AND p.id = ?");
     let row = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(query.as_str()))
         .bind(id)
         .fetch_optional(&pool)
@@ -319,13 +678,19 @@ pub async fn create_plant(
         body.soil_type.as_deref(),
         body.soil_moisture.as_deref(),
     )?;
+    if let Some(parent_id) = body.parent_id {
+        lineage::validate_parent(&state.pool, None, parent_id).await?;
+    }
+    let tags = normalize_tag_names(body.tags.as_deref().unwrap_or_default())?;
 
     let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
 
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
     let id = sqlx::query_scalar::<_, i64>(
         "INSERT INTO plants (name, species, icon, location_id, watering_interval_days, light_needs, \
-         difficulty, pet_safety, growth_speed, soil_type, soil_moisture, notes, created_at, updated_at) \
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+         difficulty, pet_safety, growth_speed, soil_type, soil_moisture, notes, parent_id, \
+         created_at, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
     )
     .bind(&name)
     .bind(&body.species)
@@ -339,13 +704,33 @@ pub async fn create_plant(
     .bind(&body.soil_type)
     .bind(&body.soil_moisture)
     .bind(&body.notes)
+    .bind(body.parent_id)
     .bind(&now)
     .bind(&now)
-    .fetch_one(&state.pool)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    tags::assign_plant_tags(&mut tx, id, &tags)
+        .await
+        .map_err(db_error)?;
+
+    let snapshot = history::plant_snapshot(&mut *tx, id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::InternalError("INTERNAL_ERROR"))?;
+    history::record(
+        &mut *tx,
+        ENTITY_PLANT,
+        id,
+        Some(id),
+        "created",
+        history::created(&snapshot),
+    )
     .await
     .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
-    let query = format!("{PLANT_SELECT} WHERE p.id = ?");
+    let query = format!("{PLANT_SELECT} AND p.id = ?");
     let row = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(query.as_str()))
         .bind(id)
         .fetch_one(&state.pool)
@@ -354,34 +739,80 @@ pub async fn create_plant(
 
     let plant = Plant::from(row);
     info!(plant_id = id, name = %plant.name, "Plant created");
+    state
+        .events
+        .publish(ChangeEvent::PlantCreated { plant_id: id });
 
-    mqtt::publish_discovery(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        &plant.name,
-    )
-    .await;
-    mqtt::publish_state(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        &plant.watering_status,
-    )
-    .await;
-    mqtt::publish_attributes(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        plant.last_watered.as_deref(),
-        plant.next_due.as_deref(),
-        plant.watering_interval_days,
-    )
-    .await;
+    publish_plant_mqtt(&state, &plant).await;
 
     Ok((StatusCode::CREATED, Json(plant)))
 }
 
+/// Apply an update to the current state of plant `id` and validate the result.
+async fn merge_update(
+    conn: &mut SqliteConnection,
+    id: i64,
+    current: PlantRow,
+    body: &UpdatePlant,
+) -> Result<PlantSnapshot, ApiError> {
+    let lifecycle = resolve_lifecycle(&current, body)?;
+    let after = PlantSnapshot {
+        parent_id: resolve_parent(conn, id, current.parent_id, body.parent_id).await?,
+        name: body.name.clone().unwrap_or(current.name),
+        species: body.species.clone().unwrap_or(current.species),
+        icon: body.icon.clone().unwrap_or(current.icon),
+        location_id: body.location_id.unwrap_or(current.location_id),
+        watering_interval_days: body
+            .watering_interval_days
+            .unwrap_or(current.watering_interval_days),
+        light_needs: body.light_needs.clone().unwrap_or(current.light_needs),
+        difficulty: body.difficulty.clone().unwrap_or(current.difficulty),
+        pet_safety: body.pet_safety.clone().unwrap_or(current.pet_safety),
+        growth_speed: body.growth_speed.clone().unwrap_or(current.growth_speed),
+        soil_type: body.soil_type.clone().unwrap_or(current.soil_type),
+        soil_moisture: body.soil_moisture.clone().unwrap_or(current.soil_moisture),
+        notes: body.notes.clone().unwrap_or(current.notes),
+        lifecycle_status: lifecycle.status,
+        lifecycle_date: lifecycle.date,
+        lifecycle_reason: lifecycle.reason,
+    };
+    validate_watering_interval(after.watering_interval_days)?;
+    validate_light_needs(&after.light_needs)?;
+    validate_all_care_info(
+        after.difficulty.as_deref(),
+        after.pet_safety.as_deref(),
+        after.growth_speed.as_deref(),
+        after.soil_type.as_deref(),
+        after.soil_moisture.as_deref(),
+    )?;
+    Ok(after)
+}
+
+/// Write a merged update with its history entry, replacing the tags when given.
+async fn write_update(
+    conn: &mut SqliteConnection,
+    id: i64,
+    before: &PlantSnapshot,
+    after: &PlantSnapshot,
+    tags: Option<&[String]>,
+    now: &str,
+) -> Result<(), sqlx::Error> {
+    history::write_plant_snapshot(conn, id, after, now).await?;
+    history::record(
+        &mut *conn,
+        ENTITY_PLANT,
+        id,
+        Some(id),
+        "updated",
+        history::diff(before, after),
+    )
+    .await?;
+    if let Some(tags) = tags {
+        tags::set_plant_tags(conn, id, tags).await?;
+    }
+    Ok(())
+}
+
 /// # Errors
 /// Returns `ApiError::NotFound` if the plant does not exist,
 /// `ApiError::Validation` if care info values are invalid, or
@@ -391,104 +822,127 @@ pub async fn update_plant(
     Path(id): Path<i64>,
     JsonBody(body): JsonBody<UpdatePlant>,
 ) -> Result<Json<Plant>, ApiError> {
-    // Fetch current plant
-    let current = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!(
-        "{PLANT_SELECT} WHERE p.id = ?"
-    )))
-    .bind(id)
-    .fetch_optional(&state.pool)
-    .await
-    .map_err(db_error)?
-    .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
-
-    let name = body.name.unwrap_or(current.name);
-    let species = body.species.unwrap_or(current.species);
-    let icon = body.icon.unwrap_or(current.icon);
-    let location_id = body.location_id.unwrap_or(current.location_id);
-    let watering_interval_days = body
-        .watering_interval_days
-        .unwrap_or(current.watering_interval_days);
-    validate_watering_interval(watering_interval_days)?;
-    let light_needs = body.light_needs.unwrap_or(current.light_needs);
-    validate_light_needs(&light_needs)?;
-    let difficulty = body.difficulty.unwrap_or(current.difficulty);
-    let pet_safety = body.pet_safety.unwrap_or(current.pet_safety);
-    let growth_speed = body.growth_speed.unwrap_or(current.growth_speed);
-    let soil_type = body.soil_type.unwrap_or(current.soil_type);
-    let soil_moisture = body.soil_moisture.unwrap_or(current.soil_moisture);
-    let notes = body.notes.unwrap_or(current.notes);
+    let tags = body.tags.as_deref().map(normalize_tag_names).transpose()?;
 
-    validate_all_care_info(
-        difficulty.as_deref(),
-        pet_safety.as_deref(),
-        growth_speed.as_deref(),
-        soil_type.as_deref(),
-        soil_moisture.as_deref(),
-    )?;
+    // Read the current plant in the same transaction as the write, so a concurrent
+    // update cannot be overwritten unnoticed
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let current =
+        sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!("{PLANT_SELECT} AND p.id = ?")))
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?
+            .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
 
-    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
-    sqlx::query(
-        "UPDATE plants SET name = ?, species = ?, icon = ?, location_id = ?, \
-         watering_interval_days = ?, light_needs = ?, \
-         difficulty = ?, pet_safety = ?, growth_speed = ?, soil_type = ?, \
-         soil_moisture = ?, notes = ?, updated_at = ? WHERE id = ?",
-    )
-    .bind(&name)
-    .bind(&species)
-    .bind(&icon)
-    .bind(location_id)
-    .bind(watering_interval_days)
-    .bind(&light_needs)
-    .bind(&difficulty)
-    .bind(&pet_safety)
-    .bind(&growth_speed)
-    .bind(&soil_type)
-    .bind(&soil_moisture)
-    .bind(&notes)
-    .bind(&now)
-    .bind(id)
-    .execute(&state.pool)
-    .await
-    .map_err(db_error)?;
+    let (previous_status, _) = compute_watering_status(
+        current.last_watered.as_deref(),
+        current.watering_interval_days,
+    );
+    let before = history::plant_snapshot(&mut *tx, id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+    let after = merge_update(&mut tx, id, current, &body).await?;
 
-    let row = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!(
-        "{PLANT_SELECT} WHERE p.id = ?"
-    )))
-    .bind(id)
-    .fetch_one(&state.pool)
-    .await
-    .map_err(db_error)?;
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    write_update(&mut tx, id, &before, &after, tags.as_deref(), &now)
+        .await
+        .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
-    let plant = Plant::from(row);
+    let plant = fetch_plant(&state.pool, id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
     debug!(plant_id = id, "Plant updated");
-
-    mqtt::publish_discovery(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        &plant.name,
-    )
-    .await;
-    mqtt::publish_state(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        &plant.watering_status,
-    )
-    .await;
-    mqtt::publish_attributes(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        plant.last_watered.as_deref(),
-        plant.next_due.as_deref(),
-        plant.watering_interval_days,
-    )
-    .await;
+    state
+        .events
+        .publish(ChangeEvent::PlantUpdated { plant_id: id });
+    state
+        .events
+        .publish_status_change(id, Some(&previous_status), &plant.watering_status);
+    publish_plant_mqtt(&state, &plant).await;
 
     Ok(Json(plant))
 }
 
+/// Apply the same changes to several plants in one transaction, so either all plants are
+/// updated or none.
+///
+/// # Errors
+/// Returns `ApiError::Validation` if no ids are given or a change is invalid,
+/// `ApiError::NotFound` if any plant does not exist, or
+/// `ApiError::InternalError` on database failures.
+pub async fn bulk_update_plants(
+    State(state): State<AppState>,
+    JsonBody(body): JsonBody<BulkUpdatePlants>,
+) -> Result<Json<Vec<Plant>>, ApiError> {
+    let mut ids = body.ids;
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.is_empty() {
+        return Err(ApiError::Validation("PLANT_BULK_IDS_REQUIRED"));
+    }
+    let changes = UpdatePlant::from(body.changes);
+    let tags = changes
+        .tags
+        .as_deref()
+        .map(normalize_tag_names)
+        .transpose()?;
+
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let mut updates = Vec::with_capacity(ids.len());
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    for &id in &ids {
+        let current = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!(
+            "{PLANT_SELECT} AND p.id = ?"
+        )))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+        let (previous_status, _) = compute_watering_status(
+            current.last_watered.as_deref(),
+            current.watering_interval_days,
+        );
+        let before = history::plant_snapshot(&mut *tx, id)
+            .await
+            .map_err(db_error)?
+            .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+        let after = merge_update(&mut tx, id, current, &changes).await?;
+        write_update(&mut tx, id, &before, &after, tags.as_deref(), &now)
+            .await
+            .map_err(db_error)?;
+        updates.push((id, previous_status, before, after));
+    }
+    tx.commit().await.map_err(db_error)?;
+
+    let mut plants = Vec::with_capacity(updates.len());
+    for (id, previous_status, before, after) in updates {
+        let plant = fetch_plant(&state.pool, id)
+            .await
+            .map_err(db_error)?
+            .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+        state
+            .events
+            .publish(ChangeEvent::PlantUpdated { plant_id: id });
+        state
+            .events
+            .publish_status_change(id, Some(&previous_status), &plant.watering_status);
+
+        // Discovery carries the name and lifecycle, which bulk changes cannot touch
+        if before.watering_interval_days != after.watering_interval_days {
+            publish_watering_mqtt(&state, &plant).await;
+        }
+        plants.push(plant);
+    }
+
+    info!(count = plants.len(), "Plants updated in bulk");
+    Ok(Json(plants))
+}
+
 /// # Errors
 /// Returns `ApiError::NotFound` if the plant does not exist, or
 /// `ApiError::InternalError` on database failures.
@@ -496,57 +950,72 @@ pub async fn water_plant(
     State(state): State<AppState>,
     Path(id): Path<i64>,
 ) -> Result<Json<Plant>, ApiError> {
-    // Verify the plant exists
+    // Verify the plant exists and remember its status for transition events
+    let previous =
+        sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!("{PLANT_SELECT} AND p.id = ?")))
+            .bind(id)
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(db_error)?
+            .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+    let (previous_status, _) = compute_watering_status(
+        previous.last_watered.as_deref(),
+        previous.watering_interval_days,
+    );
+
     let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
-    let result = sqlx::query("UPDATE plants SET updated_at = ? WHERE id = ?")
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    sqlx::query("UPDATE plants SET updated_at = ? WHERE id = ?")
         .bind(&now)
         .bind(id)
-        .execute(&state.pool)
+        .execute(&mut *tx)
         .await
         .map_err(db_error)?;
 
-    if result.rows_affected() == 0 {
-        return Err(ApiError::NotFound("PLANT_NOT_FOUND"));
-    }
-
     // Record the watering care event -- last_watered is computed from this
-    sqlx::query(
-        "INSERT INTO care_events (plant_id, event_type, occurred_at, created_at) VALUES (?, 'watered', ?, ?)",
+    let event_id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO care_events (plant_id, event_type, occurred_at, created_at) VALUES (?, 'watered', ?, ?) RETURNING id",
     )
     .bind(id)
     .bind(&now)
     .bind(&now)
-    .execute(&state.pool)
+    .fetch_one(&mut *tx)
     .await
     .map_err(db_error)?;
 
-    let row = sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!(
-        "{PLANT_SELECT} WHERE p.id = ?"
-    )))
-    .bind(id)
-    .fetch_one(&state.pool)
+    let event = history::care_event_snapshot(&mut *tx, event_id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::InternalError("INTERNAL_ERROR"))?;
+    history::record(
+        &mut *tx,
+        history::ENTITY_CARE_EVENT,
+        event_id,
+        Some(id),
+        "created",
+        history::created(&event),
+    )
     .await
     .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    let row =
+        sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!("{PLANT_SELECT} AND p.id = ?")))
+            .bind(id)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(db_error)?;
 
     let plant = Plant::from(row);
     debug!(plant_id = id, "Plant watered");
-
-    mqtt::publish_state(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        &plant.watering_status,
-    )
-    .await;
-    mqtt::publish_attributes(
-        state.mqtt_client.as_ref(),
-        &state.mqtt_prefix,
-        plant.id,
-        plant.last_watered.as_deref(),
-        plant.next_due.as_deref(),
-        plant.watering_interval_days,
-    )
-    .await;
+    state.events.publish(ChangeEvent::CareEventCreated {
+        plant_id: id,
+        event_id,
+    });
+    state
+        .events
+        .publish_status_change(id, Some(&previous_status), &plant.watering_status);
+    publish_watering_mqtt(&state, &plant).await;
 
     Ok(Json(plant))
 }
@@ -558,32 +1027,100 @@ pub async fn delete_plant(
     State(state): State<AppState>,
     Path(id): Path<i64>,
 ) -> Result<StatusCode, ApiError> {
-    // Check for photo to clean up
-    let photo_path =
-        sqlx::query_scalar::<_, Option<String>>("SELECT photo_path FROM plants WHERE id = ?")
+    let snapshot = history::plant_snapshot(&state.pool, id)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+
+    // Move to the trash -- care events and photos are kept until the plant is purged
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let result =
+        sqlx::query("UPDATE plants SET deleted_at = ? WHERE id = ? AND deleted_at IS NULL")
+            .bind(&now)
             .bind(id)
-            .fetch_optional(&state.pool)
+            .execute(&mut *tx)
             .await
-            .map_err(db_error)?
-            .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
-
-    let result = sqlx::query("DELETE FROM plants WHERE id = ?")
-        .bind(id)
-        .execute(&state.pool)
-        .await
-        .map_err(db_error)?;
+            .map_err(db_error)?;
 
     if result.rows_affected() == 0 {
         return Err(ApiError::NotFound("PLANT_NOT_FOUND"));
     }
 
-    // Delete photo file if exists
-    if let Some(filename) = photo_path {
-        state.image_store.delete(&filename).await;
-    }
+    history::record(
+        &mut *tx,
+        ENTITY_PLANT,
+        id,
+        Some(id),
+        "deleted",
+        history::deleted(&snapshot),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
 
-    mqtt::remove_plant(state.mqtt_client.as_ref(), &state.mqtt_prefix, id).await;
+    mqtt::remove_plant(
+        state.mqtt_client.as_ref(),
+        &state.metrics,
+        &state.mqtt_prefix,
+        id,
+    )
+    .await;
+    state
+        .events
+        .publish(ChangeEvent::PlantDeleted { plant_id: id });
 
-    info!(plant_id = id, "Plant deleted");
+    info!(plant_id = id, "Plant moved to trash");
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// Publish discovery, state and attributes for an active plant to MQTT, or remove the
+/// plant's entities once it is no longer active.
+pub(crate) async fn publish_plant_mqtt(state: &AppState, plant: &Plant) {
+    if plant.lifecycle_status != LIFECYCLE_ACTIVE {
+        mqtt::remove_plant(
+            state.mqtt_client.as_ref(),
+            &state.metrics,
+            &state.mqtt_prefix,
+            plant.id,
+        )
+        .await;
+        return;
+    }
+
+    mqtt::publish_discovery(
+        state.mqtt_client.as_ref(),
+        &state.metrics,
+        &state.mqtt_prefix,
+        plant.id,
+        &plant.name,
+    )
+    .await;
+    publish_watering_mqtt(state, plant).await;
+}
+
+/// Publish watering state and attributes of an active plant to MQTT.
+pub(crate) async fn publish_watering_mqtt(state: &AppState, plant: &Plant) {
+    if plant.lifecycle_status != LIFECYCLE_ACTIVE {
+        return;
+    }
+
+    mqtt::publish_state(
+        state.mqtt_client.as_ref(),
+        &state.metrics,
+        &state.mqtt_prefix,
+        plant.id,
+        &plant.watering_status,
+    )
+    .await;
+    mqtt::publish_attributes(
+        state.mqtt_client.as_ref(),
+        &state.metrics,
+        &state.mqtt_prefix,
+        plant.id,
+        plant.last_watered.as_deref(),
+        plant.next_due.as_deref(),
+        plant.watering_interval_days,
+    )
+    .await;
+}
@@ -1,65 +1,160 @@
+use std::collections::BTreeSet;
+use std::fs::File;
 use std::io::Read;
+use std::path::PathBuf;
 use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex, PoisonError};
 
 use axum::Json;
-use axum::extract::{Multipart, State};
+use axum::extract::multipart::Field;
+use axum::extract::{Multipart, Query, State};
+use axum::response::{IntoResponse, Response};
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use zip::ZipArchive;
 
 use tracing::info;
 
+use super::backup_format::{FORMAT_VERSION, upgrade};
 use super::care_events::validate_event_type;
 use super::error::{ApiError, db_error};
+use super::measurements::validate_measurement;
+use super::merge::merge_archive;
 use super::plants::{
-    validate_all_care_info, validate_light_needs, validate_required_name,
-    validate_watering_interval,
+    validate_all_care_info, validate_lifecycle_status, validate_light_needs,
+    validate_required_name, validate_watering_interval,
 };
+use crate::events::ChangeEvent;
 use crate::mqtt;
 use crate::state::AppState;
 
 #[derive(Deserialize)]
-struct ImportData {
-    version: String,
-    locations: Vec<ImportLocation>,
-    plants: Vec<ImportPlant>,
-    care_events: Vec<ImportCareEvent>,
+pub(crate) struct ImportData {
+    pub(crate) version: String,
+    pub(crate) locations: Vec<ImportLocation>,
+    pub(crate) plants: Vec<ImportPlant>,
+    pub(crate) care_events: Vec<ImportCareEvent>,
+    pub(crate) history: Vec<ImportHistoryEntry>,
+    pub(crate) tags: Vec<ImportTag>,
+    pub(crate) plant_tags: Vec<ImportPlantTag>,
+    pub(crate) plant_photos: Vec<ImportPlantPhoto>,
+    pub(crate) care_event_photos: Vec<ImportCareEventPhoto>,
+    pub(crate) measurements: Vec<ImportMeasurement>,
 }
 
 #[derive(Deserialize)]
-struct ImportLocation {
-    id: i64,
-    name: String,
+pub(crate) struct ImportMeasurement {
+    pub(crate) id: i64,
+    pub(crate) plant_id: i64,
+    pub(crate) care_event_id: Option<i64>,
+    pub(crate) metric: String,
+    pub(crate) value: f64,
+    pub(crate) notes: Option<String>,
+    pub(crate) measured_at: String,
+    pub(crate) created_at: String,
 }
 
 #[derive(Deserialize)]
-struct ImportPlant {
-    id: i64,
-    name: String,
-    species: Option<String>,
-    icon: String,
-    photo_path: Option<String>,
-    location_id: Option<i64>,
-    watering_interval_days: i64,
-    light_needs: String,
-    difficulty: Option<String>,
-    pet_safety: Option<String>,
-    growth_speed: Option<String>,
-    soil_type: Option<String>,
-    soil_moisture: Option<String>,
-    notes: Option<String>,
-    created_at: String,
-    updated_at: String,
+pub(crate) struct ImportCareEventPhoto {
+    pub(crate) id: i64,
+    pub(crate) care_event_id: i64,
+    pub(crate) filename: String,
+    pub(crate) position: i64,
+    pub(crate) created_at: String,
 }
 
 #[derive(Deserialize)]
-struct ImportCareEvent {
-    id: i64,
-    plant_id: i64,
-    event_type: String,
-    notes: Option<String>,
+pub(crate) struct ImportPlantPhoto {
+    pub(crate) id: i64,
+    pub(crate) plant_id: i64,
+    pub(crate) filename: String,
+    pub(crate) caption: Option<String>,
+    pub(crate) taken_at: Option<String>,
+    pub(crate) position: i64,
+    pub(crate) created_at: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ImportTag {
+    pub(crate) id: i64,
+    pub(crate) name: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ImportPlantTag {
+    pub(crate) plant_id: i64,
+    pub(crate) tag_id: i64,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ImportLocation {
+    pub(crate) id: i64,
+    pub(crate) name: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ImportPlant {
+    pub(crate) id: i64,
+    pub(crate) name: String,
+    pub(crate) species: Option<String>,
+    pub(crate) icon: String,
+    pub(crate) photo_path: Option<String>,
+    pub(crate) location_id: Option<i64>,
+    pub(crate) watering_interval_days: i64,
+    pub(crate) light_needs: String,
+    pub(crate) difficulty: Option<String>,
+    pub(crate) pet_safety: Option<String>,
+    pub(crate) growth_speed: Option<String>,
+    pub(crate) soil_type: Option<String>,
+    pub(crate) soil_moisture: Option<String>,
+    pub(crate) notes: Option<String>,
+    pub(crate) lifecycle_status: String,
+    #[serde(default)]
+    pub(crate) lifecycle_date: Option<String>,
+    #[serde(default)]
+    pub(crate) lifecycle_reason: Option<String>,
+    #[serde(default)]
+    pub(crate) parent_id: Option<i64>,
+    pub(crate) created_at: String,
+    pub(crate) updated_at: String,
     #[serde(default)]
-    photo_path: Option<String>,
-    occurred_at: String,
-    created_at: String,
+    pub(crate) deleted_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ImportCareEvent {
+    pub(crate) id: i64,
+    pub(crate) plant_id: i64,
+    pub(crate) event_type: String,
+    pub(crate) notes: Option<String>,
+    #[serde(default)]
+    pub(crate) photo_path: Option<String>,
+    #[serde(default)]
+    pub(crate) child_plant_id: Option<i64>,
+    pub(crate) occurred_at: String,
+    pub(crate) created_at: String,
+    #[serde(default)]
+    pub(crate) deleted_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ImportHistoryEntry {
+    pub(crate) id: i64,
+    pub(crate) entity_type: String,
+    pub(crate) entity_id: i64,
+    pub(crate) plant_id: Option<i64>,
+    pub(crate) action: String,
+    pub(crate) changes: serde_json::Value,
+    pub(crate) changed_at: String,
+}
+
+const MODE_REPLACE: &str = "replace";
+const MODE_MERGE: &str = "merge";
+
+#[derive(Deserialize)]
+pub struct ImportQuery {
+    /// `replace` (default) swaps all data for the archive, `merge` adds it to the existing data.
+    pub mode: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -70,30 +165,14 @@ pub struct ImportResult {
     pub photos: usize,
 }
 
-fn check_version(archive_version: &str) -> Result<(), ApiError> {
-    let server_version = env!("CARGO_PKG_VERSION");
-    let server_parts: Vec<&str> = server_version.split('.').collect();
-    let archive_parts: Vec<&str> = archive_version.split('.').collect();
-
-    if server_parts.len() < 2 || archive_parts.len() < 2 {
-        return Err(ApiError::BadRequest("IMPORT_VERSION_MISMATCH"));
-    }
-
-    if server_parts[0] != archive_parts[0] || server_parts[1] != archive_parts[1] {
-        return Err(ApiError::BadRequest("IMPORT_VERSION_MISMATCH"));
-    }
-
-    Ok(())
-}
-
-fn validate_filename(name: &str) -> Result<(), ApiError> {
+pub(crate) fn validate_filename(name: &str) -> Result<(), ApiError> {
     if name.contains("..") || name.starts_with('/') || name.starts_with('\\') {
         return Err(ApiError::BadRequest("IMPORT_INVALID_FILENAME"));
     }
     Ok(())
 }
 
-fn validate_dest_path(
+pub(crate) fn validate_dest_path(
     dest: &std::path::Path,
     upload_dir: &std::path::Path,
 ) -> Result<(), ApiError> {
@@ -123,24 +202,91 @@ fn validate_dest_path(
     Ok(())
 }
 
-type PhotoEntry = (String, Vec<u8>);
+const MAX_JSON_SIZE: u64 = 50 * 1024 * 1024; // 50 MB
+const MAX_PHOTO_SIZE: u64 = 5 * 1024 * 1024; // 5 MB -- matches upload limit
 
-/// Parse and validate the ZIP archive synchronously, returning the data and extracted photos.
-/// This keeps all `ZipArchive` usage in a non-async context so the future remains Send.
-fn parse_archive(bytes: &[u8]) -> Result<(ImportData, Vec<PhotoEntry>), ApiError> {
-    const MAX_JSON_SIZE: u64 = 50 * 1024 * 1024; // 50 MB
-    const MAX_PHOTO_SIZE: usize = 5 * 1024 * 1024; // 5 MB -- matches upload limit
+/// Photos of an uploaded archive, read from the spooled archive file when needed.
+pub(crate) struct ArchivePhotos {
+    archive: Arc<Mutex<ZipArchive<File>>>,
+    names: BTreeSet<String>,
+}
+
+impl ArchivePhotos {
+    pub(crate) fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+
+    /// Run `read` on the photo's archive entry. All `ZipArchive` usage happens on a
+    /// blocking thread, so the import future remains Send.
+    async fn with_photo<T, F>(&self, name: &str, read: F) -> Result<T, ApiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut dyn Read) -> std::io::Result<T> + Send + 'static,
+    {
+        let archive = Arc::clone(&self.archive);
+        let entry_name = format!("photos/{name}");
+        tokio::task::spawn_blocking(move || {
+            let mut archive = archive.lock().unwrap_or_else(PoisonError::into_inner);
+            let entry = archive
+                .by_name(&entry_name)
+                .map_err(|_| ApiError::BadRequest("IMPORT_INVALID_ARCHIVE"))?;
+            read(&mut entry.take(MAX_PHOTO_SIZE)).map_err(|e| {
+                tracing::error!("Failed to copy {entry_name}: {e}");
+                ApiError::InternalError("INTERNAL_ERROR")
+            })
+        })
+        .await
+        .map_err(|_| ApiError::InternalError("INTERNAL_ERROR"))?
+    }
 
-    let cursor = std::io::Cursor::new(bytes);
+    /// Copy the photo to `dest`, overwriting a file of the same name.
+    pub(crate) async fn extract(&self, name: &str, dest: PathBuf) -> Result<(), ApiError> {
+        self.with_photo(name, move |photo| {
+            let mut file = File::create(dest)?;
+            std::io::copy(photo, &mut file).map(|_| ())
+        })
+        .await
+    }
+
+    /// Whether the photo has the same contents as the file at `path`.
+    pub(crate) async fn same_as(&self, name: &str, path: PathBuf) -> Result<bool, ApiError> {
+        self.with_photo(name, move |photo| {
+            let Ok(existing) = std::fs::read(path) else {
+                return Ok(false);
+            };
+            let mut contents = Vec::new();
+            photo.read_to_end(&mut contents)?;
+            Ok(contents == existing)
+        })
+        .await
+    }
+}
+
+/// Parse and validate the spooled ZIP archive, returning the data and its photos, which
+/// stay in the archive until they are extracted.
+pub(crate) fn parse_archive(file: File) -> Result<(ImportData, ArchivePhotos), ApiError> {
     let mut archive =
-        zip::ZipArchive::new(cursor).map_err(|_| ApiError::BadRequest("IMPORT_INVALID_ARCHIVE"))?;
+        ZipArchive::new(file).map_err(|_| ApiError::BadRequest("IMPORT_INVALID_ARCHIVE"))?;
 
-    // Validate all filenames
+    // Validate all filenames and photo sizes
+    let mut names = BTreeSet::new();
     for i in 0..archive.len() {
         let file = archive
             .by_index(i)
             .map_err(|_| ApiError::BadRequest("IMPORT_INVALID_ARCHIVE"))?;
         validate_filename(file.name())?;
+        if let Some(filename) = file.name().strip_prefix("photos/")
+            && !filename.is_empty()
+        {
+            if file.size() > MAX_PHOTO_SIZE {
+                return Err(ApiError::BadRequest("IMPORT_FILE_TOO_LARGE"));
+            }
+            names.insert(filename.to_string());
+        }
     }
 
     // Read and parse data.json
@@ -157,56 +303,296 @@ fn parse_archive(bytes: &[u8]) -> Result<(ImportData, Vec<PhotoEntry>), ApiError
         if json_bytes.len() as u64 > MAX_JSON_SIZE {
             return Err(ApiError::BadRequest("IMPORT_FILE_TOO_LARGE"));
         }
-        let json_str = String::from_utf8(json_bytes)
+        let mut document: serde_json::Value = serde_json::from_slice(&json_bytes)
             .map_err(|_| ApiError::BadRequest("IMPORT_INVALID_DATA"))?;
+        let format = upgrade(&mut document)?;
+        if format < FORMAT_VERSION {
+            info!(format, "Upgraded archive to the current format");
+        }
 
-        serde_json::from_str(&json_str).map_err(|_| ApiError::BadRequest("IMPORT_INVALID_DATA"))?
+        serde_json::from_value(document).map_err(|_| ApiError::BadRequest("IMPORT_INVALID_DATA"))?
     };
 
-    check_version(&data.version)?;
+    let photos = ArchivePhotos {
+        archive: Arc::new(Mutex::new(archive)),
+        names,
+    };
+    Ok((data, photos))
+}
 
-    // Extract photo files into memory
-    let mut photos = Vec::new();
-    for i in 0..archive.len() {
-        let file = archive
-            .by_index(i)
-            .map_err(|_| ApiError::BadRequest("IMPORT_INVALID_ARCHIVE"))?;
+/// Parse an archive of `data` and `photos` written to a temporary file.
+#[cfg(test)]
+pub(crate) fn test_archive(
+    data: &serde_json::Value,
+    photos: &[(&str, &[u8])],
+) -> (ImportData, ArchivePhotos) {
+    use std::io::Write;
 
-        let name = file.name().to_string();
-        if let Some(filename) = name.strip_prefix("photos/") {
-            if filename.is_empty() {
-                continue;
-            }
-            let mut contents = Vec::new();
-            file.take(MAX_PHOTO_SIZE as u64 + 1)
-                .read_to_end(&mut contents)
-                .map_err(|_| ApiError::BadRequest("IMPORT_INVALID_DATA"))?;
-            if contents.len() > MAX_PHOTO_SIZE {
-                return Err(ApiError::BadRequest("IMPORT_FILE_TOO_LARGE"));
-            }
-            photos.push((filename.to_string(), contents));
+    let mut zip = zip::ZipWriter::new(tempfile::tempfile().unwrap());
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file("data.json", options).unwrap();
+    zip.write_all(data.to_string().as_bytes()).unwrap();
+    for (name, contents) in photos {
+        zip.start_file(format!("photos/{name}"), options).unwrap();
+        zip.write_all(contents).unwrap();
+    }
+    parse_archive(zip.finish().unwrap()).ok().unwrap()
+}
+
+/// An uploaded archive in a temporary file, removed when dropped.
+struct Spool(PathBuf);
+
+impl Drop for Spool {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Write the uploaded archive to a temporary file as it arrives, so it is never held in
+/// memory as a whole.
+async fn spool_upload(field: &mut Field<'_>) -> Result<(Spool, u64), ApiError> {
+    let write_error = |e: std::io::Error| {
+        tracing::error!("Failed to spool import upload: {e}");
+        ApiError::InternalError("INTERNAL_ERROR")
+    };
+
+    let spool =
+        Spool(std::env::temp_dir().join(format!("flowl-import-{}.zip", uuid::Uuid::new_v4())));
+    let mut file = tokio::fs::File::create(&spool.0)
+        .await
+        .map_err(write_error)?;
+    let mut size = 0;
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|_| ApiError::BadRequest("INVALID_REQUEST_BODY"))?
+    {
+        size += chunk.len() as u64;
+        file.write_all(&chunk).await.map_err(write_error)?;
+    }
+    file.flush().await.map_err(write_error)?;
+    Ok((spool, size))
+}
+
+/// Set parent references once all plants exist, since a parent may be listed after its child.
+async fn link_parents(
+    conn: &mut sqlx::SqliteConnection,
+    plants: &[ImportPlant],
+) -> Result<(), sqlx::Error> {
+    for plant in plants {
+        if let Some(parent_id) = plant.parent_id {
+            sqlx::query("UPDATE plants SET parent_id = ? WHERE id = ?")
+                .bind(parent_id)
+                .bind(plant.id)
+                .execute(&mut *conn)
+                .await?;
         }
     }
+    Ok(())
+}
 
-    Ok((data, photos))
+async fn insert_tags(conn: &mut sqlx::SqliteConnection, data: &ImportData) -> Result<(), ApiError> {
+    for tag in &data.tags {
+        validate_required_name(&tag.name, "TAG_NAME_REQUIRED")?;
+
+        sqlx::query("INSERT INTO tags (id, name) VALUES (?, ?)")
+            .bind(tag.id)
+            .bind(&tag.name)
+            .execute(&mut *conn)
+            .await
+            .map_err(db_error)?;
+    }
+    for plant_tag in &data.plant_tags {
+        sqlx::query("INSERT INTO plant_tags (plant_id, tag_id) VALUES (?, ?)")
+            .bind(plant_tag.plant_id)
+            .bind(plant_tag.tag_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(db_error)?;
+    }
+    Ok(())
 }
 
-async fn replace_database(pool: &sqlx::SqlitePool, data: &ImportData) -> Result<(), ApiError> {
-    let mut tx = pool.begin().await.map_err(db_error)?;
+/// Insert gallery photos, then add the cover of any plant missing from the gallery, as
+/// backups from before galleries only have `photo_path`.
+async fn insert_plant_photos(
+    conn: &mut sqlx::SqliteConnection,
+    photos: &[ImportPlantPhoto],
+) -> Result<(), ApiError> {
+    for photo in photos {
+        validate_filename(&photo.filename)?;
 
-    // Delete in correct FK order
-    sqlx::query("DELETE FROM care_events")
-        .execute(&mut *tx)
+        sqlx::query(
+            "INSERT INTO plant_photos (id, plant_id, filename, caption, taken_at, position, created_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(photo.id)
+        .bind(photo.plant_id)
+        .bind(&photo.filename)
+        .bind(&photo.caption)
+        .bind(&photo.taken_at)
+        .bind(photo.position)
+        .bind(&photo.created_at)
+        .execute(&mut *conn)
         .await
         .map_err(db_error)?;
-    sqlx::query("DELETE FROM plants")
-        .execute(&mut *tx)
+    }
+    backfill_plant_photos(conn).await
+}
+
+/// Add the cover of any plant missing from its gallery as its only gallery photo.
+pub(crate) async fn backfill_plant_photos(
+    conn: &mut sqlx::SqliteConnection,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        "INSERT INTO plant_photos (plant_id, filename, taken_at, created_at) \
+         SELECT id, photo_path, substr(updated_at, 1, 10), updated_at FROM plants p \
+         WHERE photo_path IS NOT NULL \
+         AND NOT EXISTS (SELECT 1 FROM plant_photos pp WHERE pp.filename = p.photo_path)",
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(db_error)?;
+    Ok(())
+}
+
+/// Insert care event photos, then add the photo of any event missing from them, as
+/// backups from before multiple photos only have `photo_path`.
+async fn insert_care_event_photos(
+    conn: &mut sqlx::SqliteConnection,
+    photos: &[ImportCareEventPhoto],
+) -> Result<(), ApiError> {
+    for photo in photos {
+        validate_filename(&photo.filename)?;
+
+        sqlx::query(
+            "INSERT INTO care_event_photos (id, care_event_id, filename, position, created_at) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(photo.id)
+        .bind(photo.care_event_id)
+        .bind(&photo.filename)
+        .bind(photo.position)
+        .bind(&photo.created_at)
+        .execute(&mut *conn)
         .await
         .map_err(db_error)?;
-    sqlx::query("DELETE FROM locations")
-        .execute(&mut *tx)
+    }
+    backfill_care_event_photos(conn).await
+}
+
+/// Add the photo of any event missing from its photos and point each event's
+/// `photo_path` at its first photo.
+pub(crate) async fn backfill_care_event_photos(
+    conn: &mut sqlx::SqliteConnection,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        "INSERT INTO care_event_photos (care_event_id, filename, created_at) \
+         SELECT id, photo_path, created_at FROM care_events ce \
+         WHERE photo_path IS NOT NULL \
+         AND NOT EXISTS (SELECT 1 FROM care_event_photos cep WHERE cep.filename = ce.photo_path)",
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(db_error)?;
+
+    // photo_path always mirrors the first photo
+    sqlx::query(
+        "UPDATE care_events SET photo_path = (SELECT filename FROM care_event_photos \
+         WHERE care_event_id = care_events.id ORDER BY position, id LIMIT 1)",
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(db_error)?;
+    Ok(())
+}
+
+async fn insert_measurements(
+    conn: &mut sqlx::SqliteConnection,
+    measurements: &[ImportMeasurement],
+) -> Result<(), ApiError> {
+    for m in measurements {
+        validate_measurement(&m.metric, m.value)?;
+
+        sqlx::query(
+            "INSERT INTO measurements (id, plant_id, care_event_id, metric, value, notes, \
+             measured_at, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(m.id)
+        .bind(m.plant_id)
+        .bind(m.care_event_id)
+        .bind(&m.metric)
+        .bind(m.value)
+        .bind(&m.notes)
+        .bind(&m.measured_at)
+        .bind(&m.created_at)
+        .execute(&mut *conn)
         .await
         .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+async fn insert_history(
+    conn: &mut sqlx::SqliteConnection,
+    entries: &[ImportHistoryEntry],
+) -> Result<(), sqlx::Error> {
+    for entry in entries {
+        sqlx::query(
+            "INSERT INTO change_history (id, entity_type, entity_id, plant_id, action, changes, changed_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(entry.id)
+        .bind(&entry.entity_type)
+        .bind(entry.entity_id)
+        .bind(entry.plant_id)
+        .bind(&entry.action)
+        .bind(entry.changes.to_string())
+        .bind(&entry.changed_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+async fn clear_database(conn: &mut sqlx::SqliteConnection) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM change_history")
+        .execute(&mut *conn)
+        .await?;
+
+    // Delete in correct FK order
+    for statement in [
+        "DELETE FROM tags",
+        "DELETE FROM plant_photos",
+        "DELETE FROM care_event_photos",
+        "DELETE FROM measurements",
+        "DELETE FROM care_events",
+        "DELETE FROM plants",
+        "DELETE FROM locations",
+    ] {
+        sqlx::query(statement).execute(&mut *conn).await?;
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_plant(plant: &ImportPlant) -> Result<(), ApiError> {
+    validate_required_name(&plant.name, "PLANT_NAME_REQUIRED")?;
+    validate_watering_interval(plant.watering_interval_days)?;
+    validate_light_needs(&plant.light_needs)?;
+    validate_all_care_info(
+        plant.difficulty.as_deref(),
+        plant.pet_safety.as_deref(),
+        plant.growth_speed.as_deref(),
+        plant.soil_type.as_deref(),
+        plant.soil_moisture.as_deref(),
+    )?;
+    validate_lifecycle_status(&plant.lifecycle_status)
+}
+
+async fn replace_database(pool: &sqlx::SqlitePool, data: &ImportData) -> Result<(), ApiError> {
+    let mut tx = pool.begin().await.map_err(db_error)?;
+
+    clear_database(&mut tx).await.map_err(db_error)?;
 
     for loc in &data.locations {
         validate_required_name(&loc.name, "LOCATION_NAME_REQUIRED")?;
@@ -220,22 +606,14 @@ async fn replace_database(pool: &sqlx::SqlitePool, data: &ImportData) -> Result<
     }
 
     for plant in &data.plants {
-        validate_required_name(&plant.name, "PLANT_NAME_REQUIRED")?;
-        validate_watering_interval(plant.watering_interval_days)?;
-        validate_light_needs(&plant.light_needs)?;
-        validate_all_care_info(
-            plant.difficulty.as_deref(),
-            plant.pet_safety.as_deref(),
-            plant.growth_speed.as_deref(),
-            plant.soil_type.as_deref(),
-            plant.soil_moisture.as_deref(),
-        )?;
+        validate_plant(plant)?;
 
         sqlx::query(
             "INSERT INTO plants (id, name, species, icon, photo_path, location_id, \
              watering_interval_days, light_needs, difficulty, pet_safety, \
-             growth_speed, soil_type, soil_moisture, notes, created_at, updated_at) \
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+             growth_speed, soil_type, soil_moisture, notes, \
+             lifecycle_status, lifecycle_date, lifecycle_reason, created_at, updated_at, deleted_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(plant.id)
         .bind(&plant.name)
@@ -251,60 +629,143 @@ async fn replace_database(pool: &sqlx::SqlitePool, data: &ImportData) -> Result<
         .bind(&plant.soil_type)
         .bind(&plant.soil_moisture)
         .bind(&plant.notes)
+        .bind(&plant.lifecycle_status)
+        .bind(&plant.lifecycle_date)
+        .bind(&plant.lifecycle_reason)
         .bind(&plant.created_at)
         .bind(&plant.updated_at)
+        .bind(&plant.deleted_at)
         .execute(&mut *tx)
         .await
         .map_err(db_error)?;
     }
 
+    link_parents(&mut tx, &data.plants)
+        .await
+        .map_err(db_error)?;
+    insert_tags(&mut tx, data).await?;
+    insert_plant_photos(&mut tx, &data.plant_photos).await?;
+
     for event in &data.care_events {
         validate_event_type(&event.event_type)?;
 
         sqlx::query(
-            "INSERT INTO care_events (id, plant_id, event_type, notes, photo_path, occurred_at, created_at) \
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO care_events (id, plant_id, event_type, notes, photo_path, child_plant_id, \
+             occurred_at, created_at, deleted_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(event.id)
         .bind(event.plant_id)
         .bind(&event.event_type)
         .bind(&event.notes)
         .bind(&event.photo_path)
+        .bind(event.child_plant_id)
         .bind(&event.occurred_at)
         .bind(&event.created_at)
+        .bind(&event.deleted_at)
         .execute(&mut *tx)
         .await
         .map_err(db_error)?;
     }
 
+    insert_care_event_photos(&mut tx, &data.care_event_photos).await?;
+    insert_measurements(&mut tx, &data.measurements).await?;
+
+    insert_history(&mut tx, &data.history)
+        .await
+        .map_err(db_error)?;
+
     tx.commit().await.map_err(db_error)?;
 
     Ok(())
 }
 
+/// Generate thumbnails for imported photos, republish the MQTT state of all plants and tell
+/// connected clients to reload everything.
+async fn finish_import(state: &AppState) {
+    state
+        .image_store
+        .generate_missing_thumbnails(&state.pool)
+        .await;
+
+    if !state.mqtt_disabled {
+        let connected = state
+            .mqtt_connected
+            .as_ref()
+            .is_some_and(|b| b.load(Ordering::Relaxed));
+
+        if connected && let Some(client) = state.mqtt_client.as_ref() {
+            mqtt::repair(
+                &state.pool,
+                client,
+                &state.metrics,
+                &state.mqtt_host,
+                state.mqtt_port,
+                &state.mqtt_prefix,
+            )
+            .await;
+        }
+    }
+
+    state.events.publish(ChangeEvent::Resync);
+}
+
+/// Whether the import merges into the existing data rather than replacing it.
+///
+/// # Errors
+/// Returns `ApiError::BadRequest` for an unknown mode.
+pub(crate) fn import_mode(params: &ImportQuery) -> Result<bool, ApiError> {
+    match params.mode.as_deref() {
+        None | Some(MODE_REPLACE) => Ok(false),
+        Some(MODE_MERGE) => Ok(true),
+        Some(_) => Err(ApiError::BadRequest("IMPORT_INVALID_MODE")),
+    }
+}
+
 /// # Errors
-/// Returns `ApiError::BadRequest` for malformed uploads, invalid archives,
-/// or version mismatches, or `ApiError::InternalError` on database failures.
+/// Returns `ApiError::BadRequest` for malformed uploads, invalid archives, unknown
+/// modes or version mismatches, or `ApiError::InternalError` on database failures.
 pub async fn import_data(
     State(state): State<AppState>,
+    Query(params): Query<ImportQuery>,
     mut multipart: Multipart,
-) -> Result<Json<ImportResult>, ApiError> {
+) -> Result<Response, ApiError> {
+    let merge = import_mode(&params)?;
+
     // Extract file from multipart
-    let field = multipart
+    let mut field = multipart
         .next_field()
         .await
         .map_err(|_| ApiError::BadRequest("INVALID_REQUEST_BODY"))?
         .ok_or(ApiError::BadRequest("IMPORT_NO_FILE"))?;
 
-    let bytes = field
-        .bytes()
-        .await
-        .map_err(|_| ApiError::BadRequest("INVALID_REQUEST_BODY"))?;
+    let (spool, size_bytes) = spool_upload(&mut field).await?;
 
-    info!(size_bytes = bytes.len(), "Data import started");
+    info!(size_bytes, "Data import started");
+
+    import_archive(&state, spool.0.clone(), merge).await
+}
 
-    // Phase 1: Parse and validate the archive (synchronous -- keeps future Send)
-    let (data, photos) = parse_archive(&bytes)?;
+/// Restore the export archive at `path`, either replacing all data or merging into it.
+/// Responds with the import counts or the merge report.
+///
+/// # Errors
+/// Returns `ApiError::BadRequest` for invalid archives or version mismatches, or
+/// `ApiError::InternalError` on database failures.
+pub(crate) async fn import_archive(
+    state: &AppState,
+    path: PathBuf,
+    merge: bool,
+) -> Result<Response, ApiError> {
+    // Phase 1: Parse and validate the archive
+    let (data, photos) = tokio::task::spawn_blocking(move || {
+        let file = File::open(&path).map_err(|e| {
+            tracing::error!("Failed to open import archive: {e}");
+            ApiError::InternalError("INTERNAL_ERROR")
+        })?;
+        parse_archive(file)
+    })
+    .await
+    .map_err(|_| ApiError::InternalError("INTERNAL_ERROR"))??;
 
     info!(
         version = %data.version,
@@ -312,22 +773,35 @@ pub async fn import_data(
         plants = data.plants.len(),
         care_events = data.care_events.len(),
         photos = photos.len(),
-        "Archive parsed, replacing database"
+        merge,
+        "Archive parsed"
     );
 
+    if merge {
+        let report =
+            merge_archive(&state.pool, state.image_store.upload_dir(), &data, &photos).await?;
+        finish_import(state).await;
+        info!(
+            plants_added = report.plants.added,
+            plants_skipped = report.plants.skipped,
+            plants_conflicted = report.plants.conflicted,
+            care_events_added = report.care_events.added,
+            photos_added = report.photos.added,
+            "Data merge complete"
+        );
+        return Ok(Json(report).into_response());
+    }
+
     // Phase 2: Write new photos to disk (may overwrite same-named files)
     // Clear thumbnails so they are regenerated in Phase 4b (e.g. after orientation fixes).
     // Original photos are preserved so a failed DB transaction doesn't lose data.
     state.image_store.clear_thumbnails().await;
     let photos_count = photos.len();
     let upload_dir = state.image_store.upload_dir();
-    for (filename, contents) in &photos {
+    for filename in &photos.names {
         let dest = upload_dir.join(filename);
         validate_dest_path(&dest, upload_dir)?;
-        tokio::fs::write(&dest, contents).await.map_err(|e| {
-            tracing::error!("Failed to write {filename}: {e}");
-            ApiError::InternalError("INTERNAL_ERROR")
-        })?;
+        photos.extract(filename, dest).await?;
     }
 
     // Phase 3: Replace database data in a transaction
@@ -336,30 +810,8 @@ pub async fn import_data(
     // Phase 4: Clean up photos no longer referenced by the new database
     state.image_store.cleanup_orphans(&state.pool).await;
 
-    // Phase 4b: Generate thumbnails for imported photos
-    state
-        .image_store
-        .generate_missing_thumbnails(&state.pool)
-        .await;
-
-    // Phase 5: Trigger MQTT repair
-    if !state.mqtt_disabled {
-        let connected = state
-            .mqtt_connected
-            .as_ref()
-            .is_some_and(|b| b.load(Ordering::Relaxed));
-
-        if connected && let Some(client) = state.mqtt_client.as_ref() {
-            mqtt::repair(
-                &state.pool,
-                client,
-                &state.mqtt_host,
-                state.mqtt_port,
-                &state.mqtt_prefix,
-            )
-            .await;
-        }
-    }
+    // Phase 5: Generate thumbnails for imported photos and trigger MQTT repair
+    finish_import(state).await;
 
     info!(
         locations = data.locations.len(),
@@ -374,13 +826,12 @@ pub async fn import_data(
         plants: data.plants.len(),
         care_events: data.care_events.len(),
         photos: photos_count,
-    }))
+    })
+    .into_response())
 }
 
 #[cfg(test)]
 mod tests {
-    use std::io::Write;
-
     use crate::images::ImageStore;
 
     fn tiny_jpeg() -> Vec<u8> {
@@ -428,45 +879,41 @@ mod tests {
         assert!(dir.path().join("restored_600.jpg").exists());
     }
 
-    #[test]
-    fn parse_archive_extracts_photos_from_zip() {
+    #[tokio::test]
+    async fn parse_archive_leaves_photos_in_the_archive() {
         let jpeg_data = tiny_jpeg();
-        let mut buf = Vec::new();
-        {
-            let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut buf));
-            let options = zip::write::SimpleFileOptions::default();
-
-            let data_json = serde_json::json!({
-                "version": env!("CARGO_PKG_VERSION"),
-                "locations": [],
-                "plants": [{
-                    "id": 1, "name": "Fern", "species": null, "icon": "🌿",
-                    "photo_path": "test.jpg", "location_id": null,
-                    "watering_interval_days": 7, "light_needs": "indirect",
-                    "difficulty": null, "pet_safety": null, "growth_speed": null,
-                    "soil_type": null, "soil_moisture": null, "notes": null,
-                    "created_at": "2024-01-01T00:00:00Z",
-                    "updated_at": "2024-01-01T00:00:00Z"
-                }],
-                "care_events": []
-            });
-
-            zip.start_file("data.json", options).unwrap();
-            zip.write_all(data_json.to_string().as_bytes()).unwrap();
-
-            zip.start_file("photos/test.jpg", options).unwrap();
-            zip.write_all(&jpeg_data).unwrap();
-
-            zip.finish().unwrap();
-        }
-
-        let Ok((data, photos)) = super::parse_archive(&buf) else {
-            panic!("parse_archive failed");
-        };
+        let data_json = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "locations": [],
+            "plants": [{
+                "id": 1, "name": "Fern", "species": null, "icon": "🌿",
+                "photo_path": "test.jpg", "location_id": null,
+                "watering_interval_days": 7, "light_needs": "indirect",
+                "difficulty": null, "pet_safety": null, "growth_speed": null,
+                "soil_type": null, "soil_moisture": null, "notes": null,
+                "created_at": "2024-01-01T00:00:00Z",
+                "updated_at": "2024-01-01T00:00:00Z"
+            }],
+            "care_events": []
+        });
+
+        let (data, photos) = super::test_archive(&data_json, &[("test.jpg", &jpeg_data)]);
         assert_eq!(data.plants.len(), 1);
         assert_eq!(photos.len(), 1);
-        assert_eq!(photos[0].0, "test.jpg");
-        assert_eq!(photos[0].1, jpeg_data);
+        assert!(photos.contains("test.jpg"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("test.jpg");
+        photos.extract("test.jpg", dest.clone()).await.ok().unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), jpeg_data);
+        assert!(photos.same_as("test.jpg", dest).await.ok().unwrap());
+        assert!(
+            !photos
+                .same_as("test.jpg", dir.path().join("missing.jpg"))
+                .await
+                .ok()
+                .unwrap()
+        );
     }
 
     #[test]
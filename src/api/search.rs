@@ -0,0 +1,239 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use super::error::{ApiError, db_error};
+
+/// Private-use delimiters passed to FTS5 `highlight()`/`snippet()`, swapped for `<mark>` tags
+/// after the surrounding text has been HTML-escaped.
+const MATCH_START: &str = "\u{e000}";
+const MATCH_END: &str = "\u{e001}";
+
+/// Tokens around a match in note snippets.
+const SNIPPET_TOKENS: i64 = 12;
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+    /// Maximum hits per group.
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct SearchResults {
+    pub plants: Vec<PlantHit>,
+    pub care_events: Vec<CareEventHit>,
+}
+
+/// A matching plant. `*_highlight` and `notes_snippet` are HTML-escaped with matches
+/// wrapped in `<mark>`; `notes_snippet` is only set when the notes themselves matched.
+#[derive(Serialize)]
+pub struct PlantHit {
+    pub id: i64,
+    pub name: String,
+    pub species: Option<String>,
+    pub icon: String,
+    pub lifecycle_status: String,
+    pub name_highlight: String,
+    pub species_highlight: Option<String>,
+    pub notes_snippet: Option<String>,
+}
+
+/// A care event whose notes matched, with an HTML-escaped `<mark>`ed snippet.
+#[derive(Serialize)]
+pub struct CareEventHit {
+    pub id: i64,
+    pub plant_id: i64,
+    pub plant_name: String,
+    pub event_type: String,
+    pub occurred_at: String,
+    pub notes_snippet: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct PlantHitRow {
+    id: i64,
+    name: String,
+    species: Option<String>,
+    icon: String,
+    lifecycle_status: String,
+    name_highlight: String,
+    species_highlight: Option<String>,
+    notes_snippet: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct CareEventHitRow {
+    id: i64,
+    plant_id: i64,
+    plant_name: String,
+    event_type: String,
+    occurred_at: String,
+    notes_snippet: String,
+}
+
+/// Turn free text into an FTS5 query: every word is quoted so operators and punctuation
+/// are taken literally, matched as a prefix, and all words must match.
+pub(crate) fn fts_query(q: &str) -> Option<String> {
+    let terms: Vec<String> = q
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// HTML-escape FTS5 output and replace the match delimiters with `<mark>` tags.
+fn mark_up(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out.replace(MATCH_START, "<mark>")
+        .replace(MATCH_END, "</mark>")
+}
+
+fn matched_snippet(snippet: Option<String>) -> Option<String> {
+    snippet
+        .filter(|s| s.contains(MATCH_START))
+        .map(|s| mark_up(&s))
+}
+
+async fn search_plants(
+    pool: &SqlitePool,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<PlantHit>, sqlx::Error> {
+    // Name matches weigh most, then species, then notes
+    let rows = sqlx::query_as::<_, PlantHitRow>(
+        "SELECT p.id, p.name, p.species, p.icon, p.lifecycle_status, \
+            highlight(plants_fts, 0, ?2, ?3) AS name_highlight, \
+            highlight(plants_fts, 1, ?2, ?3) AS species_highlight, \
+            snippet(plants_fts, 2, ?2, ?3, '…', ?4) AS notes_snippet \
+         FROM plants_fts JOIN plants p ON p.id = plants_fts.rowid \
+         WHERE plants_fts MATCH ?1 AND p.deleted_at IS NULL \
+         ORDER BY bm25(plants_fts, 10.0, 5.0, 1.0), p.name LIMIT ?5",
+    )
+    .bind(query)
+    .bind(MATCH_START)
+    .bind(MATCH_END)
+    .bind(SNIPPET_TOKENS)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| PlantHit {
+            id: row.id,
+            name: row.name,
+            species: row.species,
+            icon: row.icon,
+            lifecycle_status: row.lifecycle_status,
+            name_highlight: mark_up(&row.name_highlight),
+            species_highlight: row.species_highlight.as_deref().map(mark_up),
+            notes_snippet: matched_snippet(row.notes_snippet),
+        })
+        .collect())
+}
+
+async fn search_care_events(
+    pool: &SqlitePool,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<CareEventHit>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, CareEventHitRow>(
+        "SELECT ce.id, ce.plant_id, p.name AS plant_name, ce.event_type, ce.occurred_at, \
+            snippet(care_events_fts, 0, ?2, ?3, '…', ?4) AS notes_snippet \
+         FROM care_events_fts \
+         JOIN care_events ce ON ce.id = care_events_fts.rowid \
+         JOIN plants p ON p.id = ce.plant_id \
+         WHERE care_events_fts MATCH ?1 \
+         AND ce.deleted_at IS NULL AND p.deleted_at IS NULL \
+         ORDER BY bm25(care_events_fts), ce.occurred_at DESC LIMIT ?5",
+    )
+    .bind(query)
+    .bind(MATCH_START)
+    .bind(MATCH_END)
+    .bind(SNIPPET_TOKENS)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| CareEventHit {
+            id: row.id,
+            plant_id: row.plant_id,
+            plant_name: row.plant_name,
+            event_type: row.event_type,
+            occurred_at: row.occurred_at,
+            notes_snippet: mark_up(&row.notes_snippet),
+        })
+        .collect())
+}
+
+/// Search plant names, species and notes and care event notes, best matches first.
+/// Plants of every lifecycle status are included; trashed plants and events are not.
+///
+/// # Errors
+/// Returns `ApiError::Validation` if `q` has no search terms, or
+/// `ApiError::InternalError` on database failures.
+pub async fn search(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<SearchQuery>,
+) -> Result<Json<SearchResults>, ApiError> {
+    let query = params
+        .q
+        .as_deref()
+        .and_then(fts_query)
+        .ok_or(ApiError::Validation("SEARCH_QUERY_REQUIRED"))?;
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+
+    let plants = search_plants(&pool, &query, limit)
+        .await
+        .map_err(db_error)?;
+    let care_events = search_care_events(&pool, &query, limit)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(SearchResults {
+        plants,
+        care_events,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fts_query_quotes_terms_as_prefixes() {
+        assert_eq!(
+            fts_query("spider mites").as_deref(),
+            Some("\"spider\"* \"mites\"*")
+        );
+        assert_eq!(
+            fts_query("say \"hi\" OR").as_deref(),
+            Some("\"say\"* \"\"\"hi\"\"\"* \"OR\"*")
+        );
+        assert_eq!(fts_query("   "), None);
+    }
+
+    #[test]
+    fn mark_up_escapes_html_around_matches() {
+        let text = format!("a <b> & {MATCH_START}mites{MATCH_END}");
+        assert_eq!(mark_up(&text), "a &lt;b&gt; &amp; <mark>mites</mark>");
+    }
+}
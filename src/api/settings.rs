@@ -1,5 +1,6 @@
 use axum::Json;
 use axum::extract::State;
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
@@ -7,33 +8,36 @@ use super::error::{ApiError, JsonBody, db_error};
 
 const VALID_THEMES: &[&str] = &["light", "dark", "system"];
 const VALID_LOCALES: &[&str] = &["en", "de", "es"];
+const SETTINGS_SELECT: &str = "SELECT theme, locale, time_zone FROM user_settings WHERE id = 1";
 
 #[derive(sqlx::FromRow, Serialize)]
 pub struct UserSettings {
     pub theme: String,
     pub locale: String,
+    /// IANA time zone (e.g. `Europe/Berlin`) used to assign events to calendar days.
+    pub time_zone: String,
 }
 
 #[derive(Deserialize)]
 pub struct UpdateSettings {
     pub theme: Option<String>,
     pub locale: Option<String>,
+    pub time_zone: Option<String>,
 }
 
 /// # Errors
 /// Returns `ApiError::InternalError` on database failures.
 pub async fn get_settings(State(pool): State<SqlitePool>) -> Result<Json<UserSettings>, ApiError> {
-    let row =
-        sqlx::query_as::<_, UserSettings>("SELECT theme, locale FROM user_settings WHERE id = 1")
-            .fetch_one(&pool)
-            .await
-            .map_err(db_error)?;
+    let row = sqlx::query_as::<_, UserSettings>(SETTINGS_SELECT)
+        .fetch_one(&pool)
+        .await
+        .map_err(db_error)?;
 
     Ok(Json(row))
 }
 
 /// # Errors
-/// Returns `ApiError::Validation` for invalid theme or locale values, or
+/// Returns `ApiError::Validation` for invalid theme, locale or time zone values, or
 /// `ApiError::InternalError` on database failures.
 pub async fn update_settings(
     State(pool): State<SqlitePool>,
@@ -51,20 +55,27 @@ pub async fn update_settings(
         return Err(ApiError::Validation("SETTINGS_INVALID_LOCALE"));
     }
 
+    if let Some(ref time_zone) = body.time_zone
+        && time_zone.parse::<Tz>().is_err()
+    {
+        return Err(ApiError::Validation("SETTINGS_INVALID_TIME_ZONE"));
+    }
+
     sqlx::query(
-        "UPDATE user_settings SET theme = COALESCE(?, theme), locale = COALESCE(?, locale) WHERE id = 1",
+        "UPDATE user_settings SET theme = COALESCE(?, theme), locale = COALESCE(?, locale), \
+         time_zone = COALESCE(?, time_zone) WHERE id = 1",
     )
     .bind(&body.theme)
     .bind(&body.locale)
+    .bind(&body.time_zone)
     .execute(&pool)
     .await
     .map_err(db_error)?;
 
-    let row =
-        sqlx::query_as::<_, UserSettings>("SELECT theme, locale FROM user_settings WHERE id = 1")
-            .fetch_one(&pool)
-            .await
-            .map_err(db_error)?;
+    let row = sqlx::query_as::<_, UserSettings>(SETTINGS_SELECT)
+        .fetch_one(&pool)
+        .await
+        .map_err(db_error)?;
 
     Ok(Json(row))
 }
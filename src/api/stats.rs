@@ -1,9 +1,11 @@
 use axum::Json;
-use axum::extract::State;
-use serde::Serialize;
+use axum::extract::{Path, Query, State};
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
 use super::error::{ApiError, db_error};
+use super::time_zone::{configured_time_zone, local_time_sql, offset_spans};
 
 #[derive(Serialize)]
 #[allow(clippy::struct_field_names)]
@@ -17,20 +19,28 @@ pub struct Stats {
 /// # Errors
 /// Returns `ApiError::InternalError` on database failures.
 pub async fn get_stats(State(pool): State<SqlitePool>) -> Result<Json<Stats>, ApiError> {
-    let plant_count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM plants")
-        .fetch_one(&pool)
-        .await
-        .map_err(db_error)?;
-    let care_event_count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM care_events")
-        .fetch_one(&pool)
-        .await
-        .map_err(db_error)?;
+    let plant_count =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM plants WHERE deleted_at IS NULL")
+            .fetch_one(&pool)
+            .await
+            .map_err(db_error)?;
+    let care_event_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM care_events ce JOIN plants p ON ce.plant_id = p.id \
+         WHERE ce.deleted_at IS NULL AND p.deleted_at IS NULL",
+    )
+    .fetch_one(&pool)
+    .await
+    .map_err(db_error)?;
     let location_count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM locations")
         .fetch_one(&pool)
         .await
         .map_err(db_error)?;
     let photo_count = sqlx::query_scalar::<_, i64>(
-        "SELECT (SELECT COUNT(*) FROM plants WHERE photo_path IS NOT NULL) + (SELECT COUNT(*) FROM care_events WHERE photo_path IS NOT NULL)",
+        "SELECT (SELECT COUNT(*) FROM plant_photos pp JOIN plants p ON pp.plant_id = p.id \
+            WHERE p.deleted_at IS NULL) \
+         + (SELECT COUNT(*) FROM care_event_photos cep \
+            JOIN care_events ce ON cep.care_event_id = ce.id JOIN plants p ON ce.plant_id = p.id \
+            WHERE ce.deleted_at IS NULL AND p.deleted_at IS NULL)",
     )
     .fetch_one(&pool)
     .await
@@ -43,3 +53,512 @@ pub async fn get_stats(State(pool): State<SqlitePool>) -> Result<Json<Stats>, Ap
         photo_count,
     }))
 }
+
+/// Longest range the care statistics cover.
+const MAX_RANGE_DAYS: i64 = 3660;
+/// Window, ending at the end of the range, in which overdue days are counted.
+const OVERDUE_WINDOW_DAYS: i64 = 90;
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+#[derive(Deserialize)]
+pub struct StatsRange {
+    /// First day (`YYYY-MM-DD`), inclusive. Defaults to one year before `to`.
+    pub from: Option<String>,
+    /// Last day (`YYYY-MM-DD`), inclusive. Defaults to today.
+    pub to: Option<String>,
+}
+
+/// How well waterings kept to the plant's `watering_interval_days`. A watering is on time
+/// when it follows the previous one within the interval; only waterings in the range that
+/// follow an earlier watering are judged.
+#[derive(Serialize)]
+pub struct WateringAdherence {
+    pub judged_waterings: i64,
+    /// Share of judged waterings that were on time, or `null` without judged waterings.
+    pub on_time_percent: Option<f64>,
+    /// Average days past due of the late waterings, or `null` if none was late.
+    pub average_days_overdue: Option<f64>,
+    /// Most consecutive on-time waterings.
+    pub longest_on_time_streak: i64,
+    /// Days the plant was overdue in the 90 days up to the end of the range (for the
+    /// collection, summed over plants).
+    pub days_overdue_last_90: i64,
+}
+
+#[derive(Serialize)]
+pub struct MonthCount {
+    /// `YYYY-MM`
+    pub month: String,
+    pub count: i64,
+}
+
+#[derive(Serialize)]
+pub struct WeekdayCount {
+    pub weekday: &'static str,
+    pub count: i64,
+}
+
+#[derive(Serialize)]
+pub struct CareStats {
+    pub from: String,
+    pub to: String,
+    pub watering: WateringAdherence,
+    /// Care events of every month in the range, oldest first.
+    pub events_per_month: Vec<MonthCount>,
+    /// Care events per weekday in the configured time zone, busiest first.
+    pub busiest_weekdays: Vec<WeekdayCount>,
+}
+
+#[derive(Serialize)]
+pub struct PlantAdherence {
+    pub plant_id: i64,
+    pub plant_name: String,
+    #[serde(flatten)]
+    pub watering: WateringAdherence,
+}
+
+#[derive(Serialize)]
+pub struct CollectionCareStats {
+    #[serde(flatten)]
+    pub stats: CareStats,
+    /// Adherence of every active plant, least punctual first.
+    pub plants: Vec<PlantAdherence>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AdherencePlant {
+    id: i64,
+    name: String,
+    watering_interval_days: i64,
+}
+
+/// Inclusive date range with its overdue window.
+struct Range {
+    from: NaiveDate,
+    to: NaiveDate,
+    /// Days after this one are not counted as overdue yet.
+    today: NaiveDate,
+}
+
+#[derive(Default)]
+struct Tally {
+    judged: i64,
+    on_time: i64,
+    late: i64,
+    days_late: i64,
+    longest_streak: i64,
+    days_overdue: i64,
+}
+
+impl Tally {
+    fn add(&mut self, other: &Tally) {
+        self.judged += other.judged;
+        self.on_time += other.on_time;
+        self.late += other.late;
+        self.days_late += other.days_late;
+        self.longest_streak = self.longest_streak.max(other.longest_streak);
+        self.days_overdue += other.days_overdue;
+    }
+
+    #[allow(clippy::cast_precision_loss)] // counts of care events
+    fn adherence(&self) -> WateringAdherence {
+        let round = |value: f64| (value * 10.0).round() / 10.0;
+        WateringAdherence {
+            judged_waterings: self.judged,
+            on_time_percent: (self.judged > 0)
+                .then(|| round(self.on_time as f64 * 100.0 / self.judged as f64)),
+            average_days_overdue: (self.late > 0)
+                .then(|| round(self.days_late as f64 / self.late as f64)),
+            longest_on_time_streak: self.longest_streak,
+            days_overdue_last_90: self.days_overdue,
+        }
+    }
+}
+
+fn parse_range(params: &StatsRange) -> Result<Range, ApiError> {
+    let invalid = || ApiError::Validation("STATS_INVALID_DATE_RANGE");
+    let parse = |date: Option<&str>| date.map(str::parse::<NaiveDate>).transpose();
+
+    let today = chrono::Utc::now().date_naive();
+    let to = parse(params.to.as_deref())
+        .map_err(|_| invalid())?
+        .unwrap_or(today);
+    let from = parse(params.from.as_deref())
+        .map_err(|_| invalid())?
+        .unwrap_or_else(|| to - Days::new(365));
+    if from > to || (to - from).num_days() > MAX_RANGE_DAYS {
+        return Err(invalid());
+    }
+    Ok(Range { from, to, today })
+}
+
+/// Judge the waterings of one plant. `waterings` holds every watering date up to the end
+/// of the range in ascending order, so the first watering in the range is judged against
+/// the one before it.
+fn tally_waterings(waterings: &[NaiveDate], interval: i64, range: &Range) -> Tally {
+    let mut tally = Tally::default();
+    let mut streak = 0;
+    for pair in waterings.windows(2) {
+        let (previous, current) = (pair[0], pair[1]);
+        if current < range.from || current > range.to {
+            continue;
+        }
+        tally.judged += 1;
+        let overdue = (current - previous).num_days() - interval;
+        if overdue <= 0 {
+            tally.on_time += 1;
+            streak += 1;
+            tally.longest_streak = tally.longest_streak.max(streak);
+        } else {
+            tally.late += 1;
+            tally.days_late += overdue;
+            streak = 0;
+        }
+    }
+
+    // A plant is overdue on the days after its due date until it is watered again
+    let window_end = range.to.min(range.today);
+    let window_start = range.to - Days::new(OVERDUE_WINDOW_DAYS.cast_unsigned() - 1);
+    for (i, watered) in waterings.iter().enumerate() {
+        let first_overdue = *watered + Days::new(interval.max(0).cast_unsigned() + 1);
+        let last_overdue = waterings
+            .get(i + 1)
+            .map_or(window_end, |next| *next - Days::new(1));
+        let start = first_overdue.max(window_start);
+        let end = last_overdue.min(window_end);
+        if start <= end {
+            tally.days_overdue += (end - start).num_days() + 1;
+        }
+    }
+    tally
+}
+
+/// Every month from `from` to `to` with its count, zero for months without events.
+fn fill_months(range: &Range, counts: &[(String, i64)]) -> Vec<MonthCount> {
+    let mut months = Vec::new();
+    let mut month = range.from.with_day(1).unwrap_or(range.from);
+    while month <= range.to {
+        let key = month.format("%Y-%m").to_string();
+        let count = counts
+            .iter()
+            .find(|(m, _)| *m == key)
+            .map_or(0, |(_, c)| *c);
+        months.push(MonthCount { month: key, count });
+        month = month + Months::new(1);
+    }
+    months
+}
+
+/// Weekday counts, busiest first and Monday to Sunday among equals. `counts` is keyed by
+/// `strftime('%w')` (0 = Sunday).
+fn rank_weekdays(counts: &[(i64, i64)]) -> Vec<WeekdayCount> {
+    let mut weekdays: Vec<WeekdayCount> = WEEKDAYS
+        .iter()
+        .enumerate()
+        .map(|(i, weekday)| {
+            let sqlite_day = (i64::try_from(i).unwrap_or_default() + 1) % 7;
+            let count = counts
+                .iter()
+                .find(|(d, _)| *d == sqlite_day)
+                .map_or(0, |(_, c)| *c);
+            WeekdayCount { weekday, count }
+        })
+        .collect();
+    weekdays.sort_by_key(|w| std::cmp::Reverse(w.count));
+    weekdays
+}
+
+/// Month and weekday histograms of care events in the range, optionally of one plant.
+/// Events are assigned to days in the configured time zone, like in the heatmap.
+async fn event_histograms(
+    pool: &SqlitePool,
+    range: &Range,
+    plant_id: Option<i64>,
+) -> Result<(Vec<MonthCount>, Vec<WeekdayCount>), sqlx::Error> {
+    let zone = configured_time_zone(pool).await?;
+    let spans = offset_spans(zone, range.from, range.to);
+    let local = local_time_sql("ce.occurred_at", "?4");
+    let filter = format!(
+        "FROM care_events ce JOIN plants p ON p.id = ce.plant_id \
+         WHERE ce.deleted_at IS NULL AND p.deleted_at IS NULL \
+         AND (?1 IS NULL OR ce.plant_id = ?1) \
+         AND ce.occurred_at >= date(?2, '-1 day') AND ce.occurred_at < date(?3, '+2 days') \
+         AND date({local}) BETWEEN ?2 AND ?3"
+    );
+    let from = range.from.to_string();
+    let to = range.to.to_string();
+
+    let months = sqlx::query_as::<_, (String, i64)>(sqlx::AssertSqlSafe(format!(
+        "SELECT strftime('%Y-%m', {local}) AS month, COUNT(*) {filter} GROUP BY month"
+    )))
+    .bind(plant_id)
+    .bind(&from)
+    .bind(&to)
+    .bind(&spans)
+    .fetch_all(pool)
+    .await?;
+    let weekdays = sqlx::query_as::<_, (i64, i64)>(sqlx::AssertSqlSafe(format!(
+        "SELECT CAST(strftime('%w', {local}) AS INTEGER) AS weekday, COUNT(*) {filter} \
+         GROUP BY weekday"
+    )))
+    .bind(plant_id)
+    .bind(&from)
+    .bind(&to)
+    .bind(&spans)
+    .fetch_all(pool)
+    .await?;
+    Ok((fill_months(range, &months), rank_weekdays(&weekdays)))
+}
+
+/// Watering dates up to the end of the range, optionally of one plant, grouped by plant.
+async fn watering_dates(
+    pool: &SqlitePool,
+    range: &Range,
+    plant_id: Option<i64>,
+) -> Result<Vec<(i64, NaiveDate)>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (i64, String)>(
+        "SELECT plant_id, date(occurred_at) FROM care_events \
+         WHERE event_type = 'watered' AND deleted_at IS NULL \
+         AND (?1 IS NULL OR plant_id = ?1) AND occurred_at < date(?2, '+1 day') \
+         ORDER BY plant_id, occurred_at",
+    )
+    .bind(plant_id)
+    .bind(range.to.to_string())
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, date)| Some((id, date.parse().ok()?)))
+        .collect())
+}
+
+fn plant_waterings(waterings: &[(i64, NaiveDate)], plant_id: i64) -> Vec<NaiveDate> {
+    waterings
+        .iter()
+        .filter(|(id, _)| *id == plant_id)
+        .map(|(_, date)| *date)
+        .collect()
+}
+
+/// Watering adherence and care activity of the whole collection, with the adherence of
+/// every active plant. Waterings are judged against each plant's current interval.
+///
+/// # Errors
+/// Returns `ApiError::Validation` for an invalid date range, or
+/// `ApiError::InternalError` on database failures.
+pub async fn get_care_stats(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<StatsRange>,
+) -> Result<Json<CollectionCareStats>, ApiError> {
+    let range = parse_range(&params)?;
+
+    let active = sqlx::query_as::<_, AdherencePlant>(
+        "SELECT id, name, watering_interval_days FROM plants \
+         WHERE deleted_at IS NULL AND lifecycle_status = 'active' ORDER BY name, id",
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+    let waterings = watering_dates(&pool, &range, None)
+        .await
+        .map_err(db_error)?;
+
+    let mut total = Tally::default();
+    let mut plants: Vec<PlantAdherence> = active
+        .into_iter()
+        .map(|plant| {
+            let dates = plant_waterings(&waterings, plant.id);
+            let tally = tally_waterings(&dates, plant.watering_interval_days, &range);
+            total.add(&tally);
+            PlantAdherence {
+                plant_id: plant.id,
+                plant_name: plant.name,
+                watering: tally.adherence(),
+            }
+        })
+        .collect();
+    plants.sort_by(|a, b| {
+        let punctuality = |p: &PlantAdherence| p.watering.on_time_percent.unwrap_or(100.0);
+        punctuality(a).total_cmp(&punctuality(b)).then(
+            b.watering
+                .days_overdue_last_90
+                .cmp(&a.watering.days_overdue_last_90),
+        )
+    });
+
+    let (events_per_month, busiest_weekdays) = event_histograms(&pool, &range, None)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(CollectionCareStats {
+        stats: CareStats {
+            from: range.from.to_string(),
+            to: range.to.to_string(),
+            watering: total.adherence(),
+            events_per_month,
+            busiest_weekdays,
+        },
+        plants,
+    }))
+}
+
+/// Watering adherence and care activity of one plant.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist,
+/// `ApiError::Validation` for an invalid date range, or
+/// `ApiError::InternalError` on database failures.
+pub async fn get_plant_stats(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Query(params): Query<StatsRange>,
+) -> Result<Json<CareStats>, ApiError> {
+    let range = parse_range(&params)?;
+
+    let interval = sqlx::query_scalar::<_, i64>(
+        "SELECT watering_interval_days FROM plants WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+    let waterings = watering_dates(&pool, &range, Some(id))
+        .await
+        .map_err(db_error)?;
+    let tally = tally_waterings(&plant_waterings(&waterings, id), interval, &range);
+
+    let (events_per_month, busiest_weekdays) = event_histograms(&pool, &range, Some(id))
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(CareStats {
+        from: range.from.to_string(),
+        to: range.to.to_string(),
+        watering: tally.adherence(),
+        events_per_month,
+        busiest_weekdays,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn range(from: &str, to: &str) -> Range {
+        Range {
+            from: date(from),
+            to: date(to),
+            today: date("2026-12-31"),
+        }
+    }
+
+    #[test]
+    fn waterings_are_judged_against_the_interval() {
+        let waterings: Vec<NaiveDate> = [
+            "2026-01-01",
+            "2026-01-08",
+            "2026-01-14",
+            "2026-01-25",
+            "2026-02-01",
+            "2026-02-08",
+        ]
+        .map(date)
+        .to_vec();
+        let tally = tally_waterings(&waterings, 7, &range("2026-01-01", "2026-02-08"));
+        assert_eq!(tally.judged, 5);
+        assert_eq!(tally.on_time, 4);
+        // 2026-01-14 -> 2026-01-25 is 11 days, 4 days late
+        assert_eq!(tally.late, 1);
+        assert_eq!(tally.days_late, 4);
+        assert_eq!(tally.longest_streak, 2);
+
+        let adherence = tally.adherence();
+        assert_eq!(adherence.on_time_percent, Some(80.0));
+        assert_eq!(adherence.average_days_overdue, Some(4.0));
+    }
+
+    #[test]
+    fn first_watering_in_range_is_judged_against_the_one_before() {
+        let waterings = ["2025-12-20", "2026-01-05"].map(date).to_vec();
+        let tally = tally_waterings(&waterings, 7, &range("2026-01-01", "2026-01-31"));
+        assert_eq!(tally.judged, 1);
+        assert_eq!(tally.late, 1);
+        assert_eq!(tally.days_late, 9);
+    }
+
+    #[test]
+    fn overdue_days_are_counted_in_the_last_90_days() {
+        // Due on 2026-03-08, watered on 2026-03-12: overdue on the 9th, 10th and 11th.
+        // Due again on 2026-03-19, overdue from the 20th to the end of the range.
+        let waterings = ["2026-03-01", "2026-03-12"].map(date).to_vec();
+        let tally = tally_waterings(&waterings, 7, &range("2026-01-01", "2026-03-31"));
+        assert_eq!(tally.days_overdue, 3 + 12);
+
+        // The window ends today, not at a future end of the range
+        let mut future = range("2026-01-01", "2026-03-31");
+        future.today = date("2026-03-21");
+        assert_eq!(tally_waterings(&waterings, 7, &future).days_overdue, 3 + 2);
+    }
+
+    #[test]
+    fn never_watered_plant_has_no_adherence() {
+        let adherence = tally_waterings(&[], 7, &range("2026-01-01", "2026-03-31")).adherence();
+        assert_eq!(adherence.judged_waterings, 0);
+        assert_eq!(adherence.on_time_percent, None);
+        assert_eq!(adherence.average_days_overdue, None);
+        assert_eq!(adherence.days_overdue_last_90, 0);
+    }
+
+    #[test]
+    fn months_without_events_are_filled_with_zero() {
+        let months = fill_months(
+            &range("2025-11-15", "2026-02-03"),
+            &[("2025-12".to_string(), 4)],
+        );
+        let months: Vec<(&str, i64)> = months.iter().map(|m| (m.month.as_str(), m.count)).collect();
+        assert_eq!(
+            months,
+            [
+                ("2025-11", 0),
+                ("2025-12", 4),
+                ("2026-01", 0),
+                ("2026-02", 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn weekdays_are_ranked_busiest_first() {
+        // SQLite: 0 = Sunday, 3 = Wednesday
+        let ranked = rank_weekdays(&[(0, 5), (3, 7)]);
+        assert_eq!(ranked[0].weekday, "wednesday");
+        assert_eq!(ranked[1].weekday, "sunday");
+        assert_eq!(ranked[2].weekday, "monday");
+        assert_eq!(ranked.len(), 7);
+    }
+
+    #[test]
+    fn range_is_validated() {
+        let params = |from: &str, to: &str| StatsRange {
+            from: Some(from.to_string()),
+            to: Some(to.to_string()),
+        };
+        assert!(parse_range(&params("2026-01-01", "2026-03-31")).is_ok());
+        assert!(parse_range(&params("2026-03-31", "2026-01-01")).is_err());
+        assert!(parse_range(&params("1990-01-01", "2026-01-01")).is_err());
+        assert!(parse_range(&params("March", "2026-01-01")).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use tracing::debug;
+
+use super::error::{ApiError, JsonBody, db_error};
+use super::history::{self, ENTITY_PLANT, ENTITY_TAG};
+use super::plants::validate_required_name;
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub plant_count: i64,
+}
+
+#[derive(Deserialize)]
+pub struct CreateTag {
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateTag {
+    pub name: Option<String>,
+}
+
+/// Trim, de-duplicate and sort tag names for assignment.
+///
+/// # Errors
+/// Returns `ApiError::Validation` if any name is empty or whitespace-only.
+pub fn normalize_tag_names(names: &[String]) -> Result<Vec<String>, ApiError> {
+    let mut normalized = Vec::with_capacity(names.len());
+    for name in names {
+        validate_required_name(name, "TAG_NAME_REQUIRED")?;
+        normalized.push(name.trim().to_string());
+    }
+    normalized.sort();
+    normalized.dedup();
+    Ok(normalized)
+}
+
+async fn plant_tag_names(
+    conn: &mut SqliteConnection,
+    plant_id: i64,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT t.name FROM plant_tags pt JOIN tags t ON t.id = pt.tag_id \
+         WHERE pt.plant_id = ? ORDER BY t.name",
+    )
+    .bind(plant_id)
+    .fetch_all(&mut *conn)
+    .await
+}
+
+/// Replace the tags of a plant with `names` (already normalized), creating missing tags.
+pub(crate) async fn assign_plant_tags(
+    conn: &mut SqliteConnection,
+    plant_id: i64,
+    names: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM plant_tags WHERE plant_id = ?")
+        .bind(plant_id)
+        .execute(&mut *conn)
+        .await?;
+    for name in names {
+        sqlx::query("INSERT INTO tags (name) VALUES (?) ON CONFLICT (name) DO NOTHING")
+            .bind(name)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(
+            "INSERT INTO plant_tags (plant_id, tag_id) SELECT ?, id FROM tags WHERE name = ?",
+        )
+        .bind(plant_id)
+        .bind(name)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Replace the tags of a plant like [`assign_plant_tags`] and record the change in the
+/// plant's history.
+pub(crate) async fn set_plant_tags(
+    conn: &mut SqliteConnection,
+    plant_id: i64,
+    names: &[String],
+) -> Result<(), sqlx::Error> {
+    let old = plant_tag_names(conn, plant_id).await?;
+    if old == names {
+        return Ok(());
+    }
+
+    assign_plant_tags(conn, plant_id, names).await?;
+    history::record(
+        &mut *conn,
+        ENTITY_PLANT,
+        plant_id,
+        Some(plant_id),
+        "updated",
+        history::diff(&json!({"tags": old}), &json!({"tags": names})),
+    )
+    .await
+}
+
+/// # Errors
+/// Returns `ApiError::InternalError` on database failures.
+pub async fn list_tags(State(pool): State<SqlitePool>) -> Result<Json<Vec<Tag>>, ApiError> {
+    let tags = sqlx::query_as::<_, Tag>(
+        "SELECT t.id, t.name, COUNT(p.id) AS plant_count \
+         FROM tags t \
+         LEFT JOIN plant_tags pt ON pt.tag_id = t.id \
+         LEFT JOIN plants p ON p.id = pt.plant_id AND p.deleted_at IS NULL \
+         GROUP BY t.id, t.name ORDER BY t.name",
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(Json(tags))
+}
+
+async fn ensure_unique_name(
+    pool: &SqlitePool,
+    name: &str,
+    id: Option<i64>,
+) -> Result<(), ApiError> {
+    let duplicate = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM tags WHERE name = ? AND (? IS NULL OR id != ?)",
+    )
+    .bind(name)
+    .bind(id)
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_error)?;
+
+    if duplicate.is_some() {
+        return Err(ApiError::Conflict("TAG_ALREADY_EXISTS"));
+    }
+    Ok(())
+}
+
+/// # Errors
+/// Returns `ApiError::Validation` if name is missing,
+/// `ApiError::Conflict` if a tag with the same name exists, or
+/// `ApiError::InternalError` on database failures.
+pub async fn create_tag(
+    State(pool): State<SqlitePool>,
+    JsonBody(body): JsonBody<CreateTag>,
+) -> Result<(StatusCode, Json<Tag>), ApiError> {
+    let name = body.name.ok_or(ApiError::Validation("TAG_NAME_REQUIRED"))?;
+    validate_required_name(&name, "TAG_NAME_REQUIRED")?;
+    let name = name.trim().to_string();
+    ensure_unique_name(&pool, &name, None).await?;
+
+    let mut tx = pool.begin().await.map_err(db_error)?;
+    let tag = sqlx::query_as::<_, Tag>(
+        "INSERT INTO tags (name) VALUES (?) RETURNING id, name, 0 AS plant_count",
+    )
+    .bind(&name)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    history::record(
+        &mut *tx,
+        ENTITY_TAG,
+        tag.id,
+        None,
+        "created",
+        history::created(&json!({"name": tag.name})),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    debug!(tag_id = tag.id, name = %tag.name, "Tag created");
+    Ok((StatusCode::CREATED, Json(tag)))
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if the tag does not exist,
+/// `ApiError::Validation` if name is missing,
+/// `ApiError::Conflict` if a tag with the same name exists, or
+/// `ApiError::InternalError` on database failures.
+pub async fn update_tag(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    JsonBody(body): JsonBody<UpdateTag>,
+) -> Result<Json<Tag>, ApiError> {
+    let old_name = sqlx::query_scalar::<_, String>("SELECT name FROM tags WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("TAG_NOT_FOUND"))?;
+
+    let name = body.name.ok_or(ApiError::Validation("TAG_NAME_REQUIRED"))?;
+    validate_required_name(&name, "TAG_NAME_REQUIRED")?;
+    let name = name.trim().to_string();
+    ensure_unique_name(&pool, &name, Some(id)).await?;
+
+    let mut tx = pool.begin().await.map_err(db_error)?;
+    sqlx::query("UPDATE tags SET name = ? WHERE id = ?")
+        .bind(&name)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    history::record(
+        &mut *tx,
+        ENTITY_TAG,
+        id,
+        None,
+        "updated",
+        history::diff(&json!({"name": old_name}), &json!({"name": name})),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    let plant_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM plant_tags pt JOIN plants p ON p.id = pt.plant_id \
+         WHERE pt.tag_id = ? AND p.deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_one(&pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(Json(Tag {
+        id,
+        name,
+        plant_count,
+    }))
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if the tag does not exist, or
+/// `ApiError::InternalError` on database failures.
+pub async fn delete_tag(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    let name = sqlx::query_scalar::<_, String>("SELECT name FROM tags WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(db_error)?
+        .ok_or(ApiError::NotFound("TAG_NOT_FOUND"))?;
+
+    let mut tx = pool.begin().await.map_err(db_error)?;
+
+    // Record the removal on every tagged plant before the assignments cascade away
+    let plant_ids =
+        sqlx::query_scalar::<_, i64>("SELECT plant_id FROM plant_tags WHERE tag_id = ?")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(db_error)?;
+    for plant_id in plant_ids {
+        let old = plant_tag_names(&mut tx, plant_id).await.map_err(db_error)?;
+        let new: Vec<&String> = old.iter().filter(|n| **n != name).collect();
+        history::record(
+            &mut *tx,
+            ENTITY_PLANT,
+            plant_id,
+            Some(plant_id),
+            "updated",
+            history::diff(&json!({"tags": old}), &json!({"tags": new})),
+        )
+        .await
+        .map_err(db_error)?;
+    }
+
+    sqlx::query("DELETE FROM tags WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    history::record(
+        &mut *tx,
+        ENTITY_TAG,
+        id,
+        None,
+        "deleted",
+        history::deleted(&json!({"name": name})),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    debug!(tag_id = id, "Tag deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,139 @@
+use chrono::offset::LocalResult;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+use sqlx::SqlitePool;
+
+/// Format of the UTC times in the offset spans, as returned by `datetime()`.
+const SPAN_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+/// Start of the first offset span, before any stored time.
+const FIRST_SPAN_START: &str = "0000-01-01 00:00:00";
+
+/// The configured IANA time zone. An unknown stored name falls back to UTC.
+pub(crate) async fn configured_time_zone(pool: &SqlitePool) -> Result<Tz, sqlx::Error> {
+    let name = sqlx::query_scalar::<_, String>("SELECT time_zone FROM user_settings WHERE id = 1")
+        .fetch_one(pool)
+        .await?;
+    Ok(name.parse().unwrap_or(Tz::UTC))
+}
+
+/// UTC time of a wall-clock time in `zone`. Repeated times map to their first occurrence,
+/// and times skipped when the clocks go forward are read in the offset before the change.
+pub(crate) fn to_utc(zone: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    match zone.from_local_datetime(&local) {
+        LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => time.with_timezone(&Utc),
+        LocalResult::None => {
+            let before = zone.offset_from_utc_datetime(&(local - TimeDelta::days(1)));
+            (local - before.fix()).and_utc()
+        }
+    }
+}
+
+fn offset_modifier(zone: Tz, utc: NaiveDateTime) -> String {
+    let seconds = zone.offset_from_utc_datetime(&utc).fix().local_minus_utc();
+    format!("{:+} minutes", seconds / 60)
+}
+
+/// The UTC offsets of `zone` from a day before `from` to a day after `to`, as a JSON array
+/// of `[since, modifier]` pairs for [`local_time_sql`]. `since` is a UTC time and the first
+/// span starts before any stored time, so every time falls into one.
+pub(crate) fn offset_spans(zone: Tz, from: NaiveDate, to: NaiveDate) -> String {
+    let mut time = from.pred_opt().unwrap_or(from).and_time(NaiveTime::MIN);
+    let end = to
+        .succ_opt()
+        .and_then(|day| day.succ_opt())
+        .unwrap_or(to)
+        .and_time(NaiveTime::MIN);
+    let mut modifier = offset_modifier(zone, time);
+    let mut spans = vec![(FIRST_SPAN_START.to_string(), modifier.clone())];
+
+    // Clock changes are hours or more apart, so stepping by the hour finds each one, and
+    // the minute it happens at is then found by bisection
+    while time < end {
+        let next = time + TimeDelta::hours(1);
+        let next_modifier = offset_modifier(zone, next);
+        if next_modifier != modifier {
+            let (mut before, mut after) = (0, 60);
+            while after - before > 1 {
+                let middle = i64::midpoint(before, after);
+                if offset_modifier(zone, time + TimeDelta::minutes(middle)) == modifier {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            let since = time + TimeDelta::minutes(after);
+            spans.push((
+                since.format(SPAN_TIME_FORMAT).to_string(),
+                next_modifier.clone(),
+            ));
+            modifier = next_modifier;
+        }
+        time = next;
+    }
+
+    serde_json::to_string(&spans).unwrap_or_default()
+}
+
+/// SQL for the wall-clock `datetime()` of the UTC time in `column`, given the bound
+/// [`offset_spans`] as `spans`.
+pub(crate) fn local_time_sql(column: &str, spans: &str) -> String {
+    format!(
+        "datetime({column}, (SELECT json_extract(s.value, '$[1]') FROM json_each({spans}) s \
+         WHERE json_extract(s.value, '$[0]') <= datetime({column}) \
+         ORDER BY s.key DESC LIMIT 1))"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, SPAN_TIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn spans_follow_daylight_saving_time() {
+        let spans = offset_spans(
+            chrono_tz::Europe::Berlin,
+            date("2026-01-01"),
+            date("2026-12-31"),
+        );
+        assert_eq!(
+            spans,
+            r#"[["0000-01-01 00:00:00","+60 minutes"],["2026-03-29 01:00:00","+120 minutes"],["2026-10-25 01:00:00","+60 minutes"]]"#
+        );
+    }
+
+    #[test]
+    fn zones_without_clock_changes_have_one_span() {
+        let spans = offset_spans(
+            chrono_tz::Asia::Kolkata,
+            date("2026-01-01"),
+            date("2026-12-31"),
+        );
+        assert_eq!(spans, r#"[["0000-01-01 00:00:00","+330 minutes"]]"#);
+    }
+
+    #[test]
+    fn local_times_are_converted_to_utc() {
+        let berlin = chrono_tz::Europe::Berlin;
+        assert_eq!(
+            to_utc(berlin, time("2026-07-01 03:30:00")).naive_utc(),
+            time("2026-07-01 01:30:00")
+        );
+        // Skipped when the clocks go forward, repeated when they go back
+        assert_eq!(
+            to_utc(berlin, time("2026-03-29 02:30:00")).naive_utc(),
+            time("2026-03-29 01:30:00")
+        );
+        assert_eq!(
+            to_utc(berlin, time("2026-10-25 02:30:00")).naive_utc(),
+            time("2026-10-25 00:30:00")
+        );
+    }
+}
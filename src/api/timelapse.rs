@@ -0,0 +1,66 @@
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use sqlx::SqlitePool;
+
+use super::care_events::plant_exists;
+use super::error::{ApiError, db_error};
+use super::photos::image_error;
+use crate::state::AppState;
+
+/// Every photo of a plant -- gallery photos and photos of live care events -- oldest first.
+/// Gallery photos are dated by `taken_at`, care event photos by the event's `occurred_at`.
+const TIMELAPSE_PHOTOS: &str = "SELECT filename FROM ( \
+    SELECT pp.filename, COALESCE(pp.taken_at, pp.created_at) AS taken, 0 AS source, \
+    pp.position, pp.id FROM plant_photos pp WHERE pp.plant_id = ?1 \
+    UNION ALL \
+    SELECT cep.filename, ce.occurred_at, 1, cep.position, cep.id FROM care_event_photos cep \
+    JOIN care_events ce ON ce.id = cep.care_event_id \
+    WHERE ce.plant_id = ?1 AND ce.deleted_at IS NULL) \
+    ORDER BY taken, source, position, id";
+
+async fn timelapse_photos(pool: &SqlitePool, plant_id: i64) -> Result<Vec<String>, ApiError> {
+    plant_exists(pool, plant_id).await?;
+    sqlx::query_scalar::<_, String>(TIMELAPSE_PHOTOS)
+        .bind(plant_id)
+        .fetch_all(pool)
+        .await
+        .map_err(db_error)
+}
+
+/// Render a plant's photos, oldest first, as a JPEG contact sheet of square frames.
+/// The sheet is cached on disk and rendered again once photos are added, removed or
+/// reordered.
+///
+/// # Errors
+/// Returns `ApiError::NotFound` if the plant does not exist or has no usable photos, or
+/// `ApiError::InternalError` on database or file failures.
+pub async fn get_timelapse(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Response, ApiError> {
+    let photos = timelapse_photos(&state.pool, id).await?;
+    if photos.is_empty() {
+        return Err(ApiError::NotFound("TIMELAPSE_NO_PHOTOS"));
+    }
+
+    let path = state
+        .image_store
+        .timelapse(id, &photos)
+        .await
+        .map_err(|e| image_error(&e))?
+        .ok_or(ApiError::NotFound("TIMELAPSE_NO_PHOTOS"))?;
+    let data = tokio::fs::read(&path).await.map_err(|e| {
+        tracing::error!("Time-lapse read failed: {e}");
+        ApiError::InternalError("INTERNAL_ERROR")
+    })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/jpeg"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        data,
+    )
+        .into_response())
+}
@@ -0,0 +1,252 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::Serialize;
+use serde_json::json;
+
+use tracing::info;
+
+use super::care_events::{CARE_EVENT_SELECT, CareEvent, publish_plant_watering_mqtt};
+use super::error::{ApiError, db_error};
+use super::history::{self, ENTITY_CARE_EVENT, ENTITY_PLANT};
+use super::plants::{PLANT_SELECT, Plant, PlantRow, publish_plant_mqtt};
+use crate::events::ChangeEvent;
+use crate::state::AppState;
+use crate::trash;
+
+#[derive(Serialize)]
+pub struct Trash {
+    pub plants: Vec<TrashedPlant>,
+    pub care_events: Vec<TrashedCareEvent>,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct TrashedPlant {
+    pub id: i64,
+    pub name: String,
+    pub species: Option<String>,
+    pub icon: String,
+    pub photo_url: Option<String>,
+    pub care_event_count: i64,
+    pub deleted_at: String,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct TrashedCareEvent {
+    pub id: i64,
+    pub plant_id: i64,
+    pub plant_name: String,
+    pub event_type: String,
+    pub notes: Option<String>,
+    pub photo_url: Option<String>,
+    pub occurred_at: String,
+    pub deleted_at: String,
+}
+
+/// List trashed plants and trashed care events of plants that are not trashed themselves,
+/// most recently deleted first.
+///
+/// # Errors
+/// Returns `ApiError::InternalError` on database failures.
+pub async fn list_trash(State(state): State<AppState>) -> Result<Json<Trash>, ApiError> {
+    let plants = sqlx::query_as::<_, TrashedPlant>(
+        "SELECT p.id, p.name, p.species, p.icon, \
+         CASE WHEN p.photo_path IS NOT NULL THEN '/uploads/' || p.photo_path END AS photo_url, \
+         (SELECT COUNT(*) FROM care_events ce \
+          WHERE ce.plant_id = p.id AND ce.deleted_at IS NULL) AS care_event_count, \
+         p.deleted_at \
+         FROM plants p WHERE p.deleted_at IS NOT NULL \
+         ORDER BY p.deleted_at DESC, p.id DESC",
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(db_error)?;
+
+    let care_events = sqlx::query_as::<_, TrashedCareEvent>(
+        "SELECT ce.id, ce.plant_id, p.name AS plant_name, ce.event_type, ce.notes, \
+         CASE WHEN ce.photo_path IS NOT NULL THEN '/uploads/' || ce.photo_path END AS photo_url, \
+         ce.occurred_at, ce.deleted_at \
+         FROM care_events ce JOIN plants p ON ce.plant_id = p.id \
+         WHERE ce.deleted_at IS NOT NULL AND p.deleted_at IS NULL \
+         ORDER BY ce.deleted_at DESC, ce.id DESC",
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(Json(Trash {
+        plants,
+        care_events,
+    }))
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if no trashed plant with this id exists, or
+/// `ApiError::InternalError` on database failures.
+pub async fn restore_plant(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<Plant>, ApiError> {
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    let deleted_at = sqlx::query_scalar::<_, String>(
+        "SELECT deleted_at FROM plants WHERE id = ? AND deleted_at IS NOT NULL",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::NotFound("PLANT_NOT_FOUND"))?;
+    sqlx::query("UPDATE plants SET deleted_at = NULL WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    history::record(
+        &mut *tx,
+        ENTITY_PLANT,
+        id,
+        Some(id),
+        "restored",
+        history::diff(
+            &json!({"deleted_at": deleted_at}),
+            &json!({"deleted_at": null}),
+        ),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    let row =
+        sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!("{PLANT_SELECT} AND p.id = ?")))
+            .bind(id)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(db_error)?;
+
+    let plant = Plant::from(row);
+    info!(plant_id = id, "Plant restored from trash");
+    // The plant keeps its id and history, so clients see it as updated rather than new
+    state
+        .events
+        .publish(ChangeEvent::PlantUpdated { plant_id: id });
+    publish_plant_mqtt(&state, &plant).await;
+
+    Ok(Json(plant))
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if no trashed care event with this id exists or its plant
+/// is trashed, or `ApiError::InternalError` on database failures.
+pub async fn restore_care_event(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<CareEvent>, ApiError> {
+    let (plant_id, event_type, deleted_at) = sqlx::query_as::<_, (i64, String, String)>(
+        "SELECT ce.plant_id, ce.event_type, ce.deleted_at \
+         FROM care_events ce JOIN plants p ON ce.plant_id = p.id \
+         WHERE ce.id = ? AND ce.deleted_at IS NOT NULL AND p.deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(db_error)?
+    .ok_or(ApiError::NotFound("CARE_EVENT_NOT_FOUND"))?;
+
+    let previous_status = if event_type == "watered" {
+        sqlx::query_as::<_, PlantRow>(sqlx::AssertSqlSafe(format!("{PLANT_SELECT} AND p.id = ?")))
+            .bind(plant_id)
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(db_error)?
+            .map(|row| Plant::from(row).watering_status)
+    } else {
+        None
+    };
+
+    let mut tx = state.pool.begin().await.map_err(db_error)?;
+    sqlx::query("UPDATE care_events SET deleted_at = NULL WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    history::record(
+        &mut *tx,
+        ENTITY_CARE_EVENT,
+        id,
+        Some(plant_id),
+        "restored",
+        history::diff(
+            &json!({"deleted_at": deleted_at}),
+            &json!({"deleted_at": null}),
+        ),
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    let event = sqlx::query_as::<_, CareEvent>(sqlx::AssertSqlSafe(format!(
+        "{CARE_EVENT_SELECT} AND ce.id = ?"
+    )))
+    .bind(id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(db_error)?;
+
+    info!(plant_id, event_id = id, "Care event restored from trash");
+    state.events.publish(ChangeEvent::CareEventCreated {
+        plant_id,
+        event_id: id,
+    });
+    if event_type == "watered" {
+        publish_plant_watering_mqtt(&state, plant_id, previous_status.as_deref()).await;
+    }
+
+    Ok(Json(event))
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if no trashed plant with this id exists, or
+/// `ApiError::InternalError` on database failures.
+pub async fn purge_plant(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    if !trash::purge_plant(&state.pool, &state.image_store, id)
+        .await
+        .map_err(db_error)?
+    {
+        return Err(ApiError::NotFound("PLANT_NOT_FOUND"));
+    }
+
+    info!(plant_id = id, "Plant permanently deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// # Errors
+/// Returns `ApiError::NotFound` if no trashed care event with this id exists, or
+/// `ApiError::InternalError` on database failures.
+pub async fn purge_care_event(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    if !trash::purge_care_event(&state.pool, &state.image_store, id)
+        .await
+        .map_err(db_error)?
+    {
+        return Err(ApiError::NotFound("CARE_EVENT_NOT_FOUND"));
+    }
+
+    info!(event_id = id, "Care event permanently deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// # Errors
+/// Returns `ApiError::InternalError` on database failures.
+pub async fn empty_trash(State(state): State<AppState>) -> Result<StatusCode, ApiError> {
+    let purged = trash::purge_trashed_before(&state.pool, &state.image_store, None)
+        .await
+        .map_err(db_error)?;
+
+    info!(purged, "Trash emptied");
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,601 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::api::backup::{collect_export_data, write_archive};
+use crate::api::time_zone::{configured_time_zone, to_utc};
+use crate::images::ImageStore;
+use crate::state::AppState;
+
+const NAME_PREFIX: &str = "flowl-backup-";
+const NAME_SUFFIX: &str = ".zip";
+/// UTC time a backup was taken, as it appears in its file name.
+const NAME_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Days searched for the next run before a schedule is considered to never match.
+const MAX_SEARCH_DAYS: u32 = 5 * 366;
+
+/// A cron-like schedule of five fields: minute, hour, day of month, month and day of week
+/// (0 or 7 is Sunday). Each field is `*`, a value, a range `a-b`, a step `*/n`, `a/n` or
+/// `a-b/n`, or a comma-separated list of these. `@hourly`, `@daily`, `@weekly` and
+/// `@monthly` are shorthands. As in cron, when both day fields are restricted a day
+/// matching either of them is due.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl Schedule {
+    /// # Errors
+    /// Returns a description of the problem if the expression is not a valid schedule.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let expanded = match expression {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!("expected 5 fields, got {}", fields.len()));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7)?;
+        if has(weekdays, 7) {
+            weekdays |= 1;
+        }
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days: parse_field(day, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            weekdays,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if !has(self.months, date.month()) {
+            return false;
+        }
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        if self.days_restricted && self.weekdays_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+
+    /// First matching time of day at or after `from`.
+    fn first_time_from(&self, from: NaiveTime) -> Option<NaiveTime> {
+        (from.hour()..24)
+            .filter(|&hour| has(self.hours, hour))
+            .find_map(|hour| {
+                let first = if hour == from.hour() {
+                    from.minute()
+                } else {
+                    0
+                };
+                (first..60)
+                    .find(|&minute| has(self.minutes, minute))
+                    .and_then(|minute| NaiveTime::from_hms_opt(hour, minute, 0))
+            })
+    }
+
+    /// The first due minute strictly after `after`, or `None` if the schedule never
+    /// matches (such as on February 30th).
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + TimeDelta::minutes(1);
+        let mut date = start.date();
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.matches_day(date) {
+                let from = if date == start.date() {
+                    start.time()
+                } else {
+                    NaiveTime::MIN
+                };
+                if let Some(time) = self.first_time_from(from) {
+                    return Some(date.and_time(time));
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+}
+
+fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Parse one schedule field into a bit mask of the values it matches.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let invalid = || format!("invalid field `{field}`");
+    let value = |v: &str| v.parse::<u32>().map_err(|_| invalid());
+
+    let mut mask = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, value(step)?),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else {
+            // `a/n` runs from `a` to the end of the field
+            let start = value(range)?;
+            (start, if part.contains('/') { max } else { start })
+        };
+        if step == 0 || start < min || end > max || start > end {
+            return Err(invalid());
+        }
+        for v in (start..=end).step_by(step as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+/// How many stored backups to keep: the newest backup of each of the last `daily` days,
+/// `weekly` ISO weeks and `monthly` months that have backups. The newest backup is always
+/// kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Retention {
+    pub daily: usize,
+    pub weekly: usize,
+    pub monthly: usize,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            daily: 7,
+            weekly: 4,
+            monthly: 6,
+        }
+    }
+}
+
+impl Retention {
+    /// Indices of the backups to delete, given the local times they were taken, newest
+    /// first.
+    fn expired(self, taken: &[NaiveDateTime]) -> Vec<usize> {
+        fn keep_newest<K: Eq + Hash>(
+            taken: &[NaiveDateTime],
+            count: usize,
+            key: impl Fn(&NaiveDateTime) -> K,
+            keep: &mut HashSet<usize>,
+        ) {
+            let mut buckets = HashSet::new();
+            for (index, time) in taken.iter().enumerate() {
+                if buckets.len() == count {
+                    break;
+                }
+                if buckets.insert(key(time)) {
+                    keep.insert(index);
+                }
+            }
+        }
+
+        let mut keep = HashSet::from([0]);
+        keep_newest(taken, self.daily, NaiveDateTime::date, &mut keep);
+        keep_newest(taken, self.weekly, Datelike::iso_week, &mut keep);
+        keep_newest(
+            taken,
+            self.monthly,
+            |time| (time.year(), time.month()),
+            &mut keep,
+        );
+        (0..taken.len()).filter(|i| !keep.contains(i)).collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BackupInfo {
+    pub name: String,
+    /// When the backup was taken (RFC 3339, UTC).
+    pub created_at: String,
+    pub size_bytes: u64,
+    #[serde(skip)]
+    taken_at: DateTime<Utc>,
+}
+
+impl BackupInfo {
+    fn new(name: String, taken_at: DateTime<Utc>, size_bytes: u64) -> Self {
+        Self {
+            name,
+            created_at: taken_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            size_bytes,
+            taken_at,
+        }
+    }
+}
+
+/// When the backup file `name` was taken, or `None` if it is not a backup file name.
+fn parse_name(name: &str) -> Option<DateTime<Utc>> {
+    let stamp = name.strip_prefix(NAME_PREFIX)?.strip_suffix(NAME_SUFFIX)?;
+    let taken_at = NaiveDateTime::parse_from_str(stamp, NAME_TIME_FORMAT).ok()?;
+    // Only accept the canonical spelling, so a valid name never contains anything else
+    (taken_at.format(NAME_TIME_FORMAT).to_string() == stamp).then(|| taken_at.and_utc())
+}
+
+#[derive(Debug)]
+pub enum BackupError {
+    Database(sqlx::Error),
+    Io(io::Error),
+}
+
+impl std::fmt::Display for BackupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Database(e) => write!(f, "Database error: {e}"),
+            Self::Io(e) => write!(f, "File I/O error: {e}"),
+        }
+    }
+}
+
+impl From<sqlx::Error> for BackupError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+impl From<io::Error> for BackupError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Backups stored as export archives in a local directory.
+#[derive(Clone)]
+pub struct BackupStore {
+    dir: PathBuf,
+    retention: Retention,
+    schedule: Option<Schedule>,
+    /// Held while a backup is written and old ones are pruned.
+    lock: Arc<Mutex<()>>,
+}
+
+impl BackupStore {
+    pub fn new(dir: PathBuf, retention: Retention) -> Self {
+        Self {
+            dir,
+            retention,
+            schedule: None,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    #[must_use]
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn retention(&self) -> Retention {
+        self.retention
+    }
+
+    pub fn schedule(&self) -> Option<&Schedule> {
+        self.schedule.as_ref()
+    }
+
+    /// Path of the stored backup `name`, or `None` if `name` is not a backup file name.
+    pub fn path(&self, name: &str) -> Option<PathBuf> {
+        parse_name(name).map(|_| self.dir.join(name))
+    }
+
+    /// Stored backups, newest first.
+    ///
+    /// # Errors
+    /// Returns the underlying I/O error if the backup directory cannot be read.
+    pub async fn list(&self) -> io::Result<Vec<BackupInfo>> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut backups = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Some(taken_at) = parse_name(&name) else {
+                continue;
+            };
+            let metadata = entry.metadata().await?;
+            if metadata.is_file() {
+                backups.push(BackupInfo::new(name, taken_at, metadata.len()));
+            }
+        }
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.taken_at));
+        Ok(backups)
+    }
+
+    /// Write a backup of all data and photos, then delete the backups the retention policy
+    /// no longer keeps, bucketed by day in the configured time zone.
+    ///
+    /// # Errors
+    /// Returns the underlying error if the data cannot be read or the archive written.
+    pub async fn create(
+        &self,
+        pool: &SqlitePool,
+        image_store: &ImageStore,
+    ) -> Result<BackupInfo, BackupError> {
+        let _guard = self.lock.lock().await;
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        let mut tx = pool.begin().await?;
+        let data = collect_export_data(&mut tx).await?;
+        tx.commit().await?;
+
+        let mut taken_at = Utc::now().with_nanosecond(0).unwrap_or_else(Utc::now);
+        // Names have second precision; never overwrite a backup taken in the same second
+        while tokio::fs::try_exists(self.dir.join(backup_name(taken_at))).await? {
+            taken_at += TimeDelta::seconds(1);
+        }
+        let name = backup_name(taken_at);
+        let path = self.dir.join(&name);
+        let partial = self.dir.join(format!(".{name}.partial"));
+        let upload_dir = image_store.upload_dir().to_path_buf();
+
+        let size_bytes = tokio::task::spawn_blocking(move || {
+            let written = write_file(&partial, &path, |out| {
+                write_archive(out, &data, &upload_dir).map_err(io::Error::from)
+            });
+            if written.is_err() {
+                let _ = std::fs::remove_file(&partial);
+            }
+            written
+        })
+        .await
+        .map_err(io::Error::other)??;
+
+        let zone = configured_time_zone(pool).await.unwrap_or(Tz::UTC);
+        if let Err(e) = self.prune(zone).await {
+            warn!("Failed to prune old backups: {e}");
+        }
+        Ok(BackupInfo::new(name, taken_at, size_bytes))
+    }
+
+    /// Delete the backups the retention policy no longer keeps, returning how many.
+    async fn prune(&self, zone: Tz) -> io::Result<usize> {
+        let backups = self.list().await?;
+        let taken: Vec<NaiveDateTime> = backups
+            .iter()
+            .map(|backup| backup.taken_at.with_timezone(&zone).naive_local())
+            .collect();
+        let expired = self.retention.expired(&taken);
+        for &index in &expired {
+            tokio::fs::remove_file(self.dir.join(&backups[index].name)).await?;
+        }
+        if !expired.is_empty() {
+            info!(deleted = expired.len(), "Pruned old backups");
+        }
+        Ok(expired.len())
+    }
+}
+
+fn backup_name(taken_at: DateTime<Utc>) -> String {
+    format!(
+        "{NAME_PREFIX}{}{NAME_SUFFIX}",
+        taken_at.format(NAME_TIME_FORMAT)
+    )
+}
+
+/// Write to `partial` and move it to `path` once it is complete and on disk, so a stored
+/// backup is never half-written. Returns the size of the file.
+fn write_file(
+    partial: &Path,
+    path: &Path,
+    write: impl FnOnce(BufWriter<File>) -> io::Result<BufWriter<File>>,
+) -> io::Result<u64> {
+    let mut out = write(BufWriter::new(File::create(partial)?))?;
+    out.flush()?;
+    let file = out.into_inner().map_err(io::IntoInnerError::into_error)?;
+    file.sync_all()?;
+    let size = file.metadata()?.len();
+    std::fs::rename(partial, path)?;
+    Ok(size)
+}
+
+/// Next time the schedule is due, reading it in the time zone configured in the settings.
+pub async fn next_run(pool: &SqlitePool, schedule: &Schedule) -> Option<DateTime<Utc>> {
+    let zone = configured_time_zone(pool).await.unwrap_or(Tz::UTC);
+    let local = Utc::now().with_timezone(&zone).naive_local();
+    schedule.next_after(local).map(|next| to_utc(zone, next))
+}
+
+/// Spawn a background task that writes a backup whenever the schedule is due.
+/// Returns `None` when no schedule is configured, which leaves only manual backups.
+pub fn spawn_backup_task(state: &AppState) -> Option<JoinHandle<()>> {
+    let Some(schedule) = state.backups.schedule().cloned() else {
+        info!("No FLOWL_BACKUP_SCHEDULE set, scheduled backups disabled");
+        return None;
+    };
+    let pool = state.pool.clone();
+    let image_store = state.image_store.clone();
+    let store = state.backups.clone();
+
+    info!(
+        schedule = schedule.expression(),
+        dir = %store.dir().display(),
+        "Starting backup scheduler"
+    );
+
+    Some(tokio::spawn(async move {
+        loop {
+            let Some(next) = next_run(&pool, &schedule).await else {
+                warn!(
+                    schedule = schedule.expression(),
+                    "Backup schedule never matches, no backups will be taken"
+                );
+                return;
+            };
+            let wait = (next - Utc::now()).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            match store.create(&pool, &image_store).await {
+                Ok(backup) => info!(
+                    name = %backup.name,
+                    size_bytes = backup.size_bytes,
+                    "Scheduled backup written"
+                ),
+                Err(e) => warn!("Scheduled backup failed: {e}"),
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn next(expression: &str, after: &str) -> Option<NaiveDateTime> {
+        Schedule::parse(expression).unwrap().next_after(at(after))
+    }
+
+    #[test]
+    fn schedules_are_parsed() {
+        assert!(Schedule::parse("30 3 * * *").is_ok());
+        assert!(Schedule::parse("*/15 8-18 1,15 1-12/2 1").is_ok());
+        assert!(Schedule::parse("@daily").is_ok());
+        assert!(Schedule::parse("0 0 * * 7").is_ok());
+
+        assert!(Schedule::parse("").is_err());
+        assert!(Schedule::parse("0 3 * *").is_err());
+        assert!(Schedule::parse("60 3 * * *").is_err());
+        assert!(Schedule::parse("0 3 0 * *").is_err());
+        assert!(Schedule::parse("0 3 * * 8").is_err());
+        assert!(Schedule::parse("*/0 * * * *").is_err());
+        assert!(Schedule::parse("5-1 * * * *").is_err());
+        assert!(Schedule::parse("x * * * *").is_err());
+        assert!(Schedule::parse("@yearly").is_err());
+    }
+
+    #[test]
+    fn next_run_is_strictly_after() {
+        assert_eq!(
+            next("30 3 * * *", "2026-10-19 01:00"),
+            Some(at("2026-10-19 03:30"))
+        );
+        assert_eq!(
+            next("30 3 * * *", "2026-10-19 03:30"),
+            Some(at("2026-10-20 03:30"))
+        );
+        assert_eq!(
+            next("*/20 * * * *", "2026-10-19 23:55"),
+            Some(at("2026-10-20 00:00"))
+        );
+        assert_eq!(
+            next("@hourly", "2026-12-31 23:10"),
+            Some(at("2027-01-01 00:00"))
+        );
+    }
+
+    #[test]
+    fn day_fields_follow_cron_semantics() {
+        // 2026-10-19 is a Monday; Sunday may be written as 0 or 7
+        assert_eq!(
+            next("0 2 * * 7", "2026-10-19 12:00"),
+            Some(at("2026-10-25 02:00"))
+        );
+        // Both day fields restricted: either matches
+        assert_eq!(
+            next("0 2 1 * 5", "2026-10-19 12:00"),
+            Some(at("2026-10-23 02:00"))
+        );
+        // Only day of month restricted
+        assert_eq!(
+            next("@monthly", "2026-10-19 12:00"),
+            Some(at("2026-11-01 00:00"))
+        );
+        assert_eq!(
+            next("0 0 31 * *", "2026-10-31 12:00"),
+            Some(at("2026-12-31 00:00"))
+        );
+        assert_eq!(next("0 0 30 2 *", "2026-10-19 12:00"), None);
+    }
+
+    fn times(values: &[&str]) -> Vec<NaiveDateTime> {
+        values.iter().map(|value| at(value)).collect()
+    }
+
+    #[test]
+    fn retention_keeps_newest_per_bucket() {
+        let retention = Retention {
+            daily: 2,
+            weekly: 2,
+            monthly: 2,
+        };
+        let taken = times(&[
+            "2026-10-19 12:00", // day 1, week 43, October
+            "2026-10-19 03:00", // same day
+            "2026-10-18 03:00", // day 2, week 42
+            "2026-10-17 03:00", // week 42 already kept
+            "2026-10-05 03:00", // week 41, too many weeks
+            "2026-09-30 03:00", // September
+            "2026-09-01 03:00", // September already kept
+            "2026-08-31 03:00", // too many months
+        ]);
+        assert_eq!(retention.expired(&taken), vec![1, 3, 4, 6, 7]);
+    }
+
+    #[test]
+    fn retention_always_keeps_the_newest_backup() {
+        let retention = Retention {
+            daily: 0,
+            weekly: 0,
+            monthly: 0,
+        };
+        let taken = times(&["2026-10-19 03:00", "2026-10-18 03:00"]);
+        assert_eq!(retention.expired(&taken), vec![1]);
+        assert!(retention.expired(&[]).is_empty());
+    }
+
+    #[test]
+    fn only_canonical_backup_names_are_accepted() {
+        let taken_at = parse_name("flowl-backup-20261019T033000Z.zip").unwrap();
+        assert_eq!(backup_name(taken_at), "flowl-backup-20261019T033000Z.zip");
+
+        assert!(parse_name("flowl-backup-20261019T033000Z.zip.partial").is_none());
+        assert!(parse_name("flowl-backup-2026109T033000Z.zip").is_none());
+        assert!(parse_name("../flowl-backup-20261019T033000Z.zip").is_none());
+        assert!(parse_name("other.zip").is_none());
+    }
+}
@@ -25,6 +25,12 @@ pub struct Config {
     pub ai_base_url: String,
     pub ai_model: String,
     pub ai_rate_limit: u32,
+    pub trash_retention_days: u32,
+    pub backup_schedule: Option<String>,
+    pub backup_dir: Option<String>,
+    pub backup_keep_daily: usize,
+    pub backup_keep_weekly: usize,
+    pub backup_keep_monthly: usize,
 }
 
 impl Config {
@@ -57,6 +63,14 @@ impl Config {
                 .get("FLOWL_AI_MODEL")
                 .unwrap_or_else(|| "gpt-4.1-mini".to_string()),
             ai_rate_limit: parse_or(source, "FLOWL_AI_RATE_LIMIT", 10),
+            trash_retention_days: parse_or(source, "FLOWL_TRASH_RETENTION_DAYS", 30),
+            backup_schedule: source
+                .get("FLOWL_BACKUP_SCHEDULE")
+                .filter(|v| !v.trim().is_empty()),
+            backup_dir: source.get("FLOWL_BACKUP_DIR"),
+            backup_keep_daily: parse_or(source, "FLOWL_BACKUP_KEEP_DAILY", 7),
+            backup_keep_weekly: parse_or(source, "FLOWL_BACKUP_KEEP_WEEKLY", 4),
+            backup_keep_monthly: parse_or(source, "FLOWL_BACKUP_KEEP_MONTHLY", 6),
         }
     }
 }
@@ -106,6 +120,12 @@ mod tests {
         assert_eq!(config.ai_base_url, "https://api.openai.com/v1");
         assert_eq!(config.ai_model, "gpt-4.1-mini");
         assert_eq!(config.ai_rate_limit, 10);
+        assert_eq!(config.trash_retention_days, 30);
+        assert!(config.backup_schedule.is_none());
+        assert!(config.backup_dir.is_none());
+        assert_eq!(config.backup_keep_daily, 7);
+        assert_eq!(config.backup_keep_weekly, 4);
+        assert_eq!(config.backup_keep_monthly, 6);
     }
 
     #[test]
@@ -122,7 +142,13 @@ mod tests {
                 .with("FLOWL_AI_API_KEY", "sk-test-key")
                 .with("FLOWL_AI_BASE_URL", "http://localhost:11434/v1")
                 .with("FLOWL_AI_MODEL", "llama3")
-                .with("FLOWL_AI_RATE_LIMIT", "20"),
+                .with("FLOWL_AI_RATE_LIMIT", "20")
+                .with("FLOWL_TRASH_RETENTION_DAYS", "7")
+                .with("FLOWL_BACKUP_SCHEDULE", "30 3 * * *")
+                .with("FLOWL_BACKUP_DIR", "/backups")
+                .with("FLOWL_BACKUP_KEEP_DAILY", "3")
+                .with("FLOWL_BACKUP_KEEP_WEEKLY", "2")
+                .with("FLOWL_BACKUP_KEEP_MONTHLY", "12"),
         );
         assert_eq!(config.port, 3000);
         assert_eq!(config.db_path, "/tmp/test.db");
@@ -135,6 +161,12 @@ mod tests {
         assert_eq!(config.ai_base_url, "http://localhost:11434/v1");
         assert_eq!(config.ai_model, "llama3");
         assert_eq!(config.ai_rate_limit, 20);
+        assert_eq!(config.trash_retention_days, 7);
+        assert_eq!(config.backup_schedule.as_deref(), Some("30 3 * * *"));
+        assert_eq!(config.backup_dir.as_deref(), Some("/backups"));
+        assert_eq!(config.backup_keep_daily, 3);
+        assert_eq!(config.backup_keep_weekly, 2);
+        assert_eq!(config.backup_keep_monthly, 12);
     }
 
     #[test]
@@ -150,6 +182,12 @@ mod tests {
         assert_eq!(config.ai_rate_limit, 0);
     }
 
+    #[test]
+    fn blank_backup_schedule_disables() {
+        let config = Config::load_from(&MockConfig::new().with("FLOWL_BACKUP_SCHEDULE", "  "));
+        assert!(config.backup_schedule.is_none());
+    }
+
     #[test]
     fn invalid_port_falls_back_to_default() {
         let config = Config::load_from(&MockConfig::new().with("FLOWL_PORT", "not_a_number"));
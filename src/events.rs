@@ -0,0 +1,166 @@
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Buffered events per subscriber before slow clients start lagging.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// A change to the collection, broadcast to all clients subscribed to `GET /api/events`.
+///
+/// Payloads only carry identifiers; clients refetch the resources they display.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChangeEvent {
+    PlantCreated {
+        plant_id: i64,
+    },
+    PlantUpdated {
+        plant_id: i64,
+    },
+    PlantDeleted {
+        plant_id: i64,
+    },
+    CareEventCreated {
+        plant_id: i64,
+        event_id: i64,
+    },
+    CareEventDeleted {
+        plant_id: i64,
+        event_id: i64,
+    },
+    PhotoChanged {
+        plant_id: i64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        event_id: Option<i64>,
+    },
+    WateringStatusChanged {
+        plant_id: i64,
+        status: String,
+    },
+    MeasurementChanged {
+        plant_id: i64,
+    },
+    /// Everything may have changed (after an import or restore); clients reload all data.
+    Resync,
+}
+
+impl ChangeEvent {
+    /// Name used for the SSE `event:` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PlantCreated { .. } => "plant_created",
+            Self::PlantUpdated { .. } => "plant_updated",
+            Self::PlantDeleted { .. } => "plant_deleted",
+            Self::CareEventCreated { .. } => "care_event_created",
+            Self::CareEventDeleted { .. } => "care_event_deleted",
+            Self::PhotoChanged { .. } => "photo_changed",
+            Self::WateringStatusChanged { .. } => "watering_status_changed",
+            Self::MeasurementChanged { .. } => "measurement_changed",
+            Self::Resync => "resync",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<ChangeEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    /// Broadcast an event to all current subscribers. Events are dropped when nobody listens.
+    pub fn publish(&self, event: ChangeEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    /// Publish a watering status transition if the status actually changed.
+    pub fn publish_status_change(&self, plant_id: i64, previous: Option<&str>, current: &str) {
+        if previous.is_some_and(|p| p != current) {
+            self.publish(ChangeEvent::WateringStatusChanged {
+                plant_id,
+                status: current.to_string(),
+            });
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ChangeEvent> {
+        self.sender.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_with_type_tag() {
+        let json = serde_json::to_value(ChangeEvent::CareEventCreated {
+            plant_id: 1,
+            event_id: 2,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "care_event_created", "plant_id": 1, "event_id": 2})
+        );
+
+        let json = serde_json::to_value(ChangeEvent::PhotoChanged {
+            plant_id: 3,
+            event_id: None,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "photo_changed", "plant_id": 3})
+        );
+
+        let json = serde_json::to_value(ChangeEvent::Resync).unwrap();
+        assert_eq!(json, serde_json::json!({"type": "resync"}));
+    }
+
+    #[tokio::test]
+    async fn subscribers_receive_published_events() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+
+        bus.publish(ChangeEvent::PlantDeleted { plant_id: 5 });
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ChangeEvent::PlantDeleted { plant_id: 5 }
+        );
+    }
+
+    #[test]
+    fn publish_without_subscribers_does_not_panic() {
+        EventBus::new().publish(ChangeEvent::PlantCreated { plant_id: 1 });
+    }
+
+    #[tokio::test]
+    async fn status_change_only_published_on_transition() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+
+        bus.publish_status_change(1, Some("ok"), "ok");
+        bus.publish_status_change(1, None, "due");
+        bus.publish_status_change(1, Some("due"), "ok");
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ChangeEvent::WateringStatusChanged {
+                plant_id: 1,
+                status: "ok".to_string()
+            }
+        );
+        assert!(rx.try_recv().is_err());
+    }
+}
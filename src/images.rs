@@ -21,6 +21,15 @@ fn detect_image_type(data: &[u8]) -> Option<&'static str> {
 const THUMBNAIL_SIZES: [u32; 3] = [200, 600, 1000];
 const JPEG_QUALITY: u8 = 80;
 
+/// Subdirectory of the uploads directory holding rendered time-lapse sheets.
+const TIMELAPSE_DIR: &str = "timelapse";
+/// Edge length of one square frame on a time-lapse sheet.
+const TIMELAPSE_CELL: u32 = 240;
+const TIMELAPSE_GAP: u32 = 8;
+const TIMELAPSE_MAX_COLUMNS: usize = 6;
+/// Longer histories are sampled evenly down to this many frames.
+const TIMELAPSE_MAX_FRAMES: usize = 36;
+
 #[derive(Debug)]
 pub enum ImageError {
     InvalidContentType,
@@ -40,42 +49,42 @@ impl std::fmt::Display for ImageError {
     }
 }
 
-/// Generate thumbnail variants for an image file on disk.
-/// Writes `{stem}_{size}.jpg` for each size in `THUMBNAIL_SIZES`.
-/// Applies EXIF orientation so thumbnails match the visual orientation of the original.
-fn generate_thumbnails(original_path: &Path) {
-    let reader = match image::ImageReader::open(original_path)
-        .and_then(image::ImageReader::with_guessed_format)
-    {
-        Ok(r) => r,
-        Err(e) => {
-            warn!(
-                path = %original_path.display(),
-                error = %e,
-                "Failed to open image for thumbnail generation"
-            );
-            return;
-        }
-    };
+/// Decode an image file and apply its EXIF orientation.
+/// Logs a warning and returns `None` if the file cannot be opened or decoded.
+fn open_oriented(path: &Path) -> Option<image::DynamicImage> {
+    let reader =
+        match image::ImageReader::open(path).and_then(image::ImageReader::with_guessed_format) {
+            Ok(r) => r,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to open image");
+                return None;
+            }
+        };
 
     let orientation = reader
         .into_decoder()
         .and_then(|mut d| d.orientation())
         .unwrap_or(image::metadata::Orientation::NoTransforms);
 
-    let mut img = match image::open(original_path) {
+    let mut img = match image::open(path) {
         Ok(img) => img,
         Err(e) => {
-            warn!(
-                path = %original_path.display(),
-                error = %e,
-                "Failed to decode image for thumbnail generation"
-            );
-            return;
+            warn!(path = %path.display(), error = %e, "Failed to decode image");
+            return None;
         }
     };
 
     img.apply_orientation(orientation);
+    Some(img)
+}
+
+/// Generate thumbnail variants for an image file on disk.
+/// Writes `{stem}_{size}.jpg` for each size in `THUMBNAIL_SIZES`.
+/// Applies EXIF orientation so thumbnails match the visual orientation of the original.
+fn generate_thumbnails(original_path: &Path) {
+    let Some(img) = open_oriented(original_path) else {
+        return;
+    };
 
     let stem = original_path
         .file_stem()
@@ -111,6 +120,64 @@ fn generate_thumbnails(original_path: &Path) {
     }
 }
 
+/// Pick at most `TIMELAPSE_MAX_FRAMES` evenly spaced items, always keeping the first and last.
+fn sample_frames<T>(items: &[T]) -> Vec<&T> {
+    let n = items.len();
+    if n <= TIMELAPSE_MAX_FRAMES {
+        return items.iter().collect();
+    }
+    (0..TIMELAPSE_MAX_FRAMES)
+        .map(|i| &items[i * (n - 1) / (TIMELAPSE_MAX_FRAMES - 1)])
+        .collect()
+}
+
+/// Render photos into a contact sheet: square, orientation-corrected frames laid out
+/// left to right, top to bottom. Files that cannot be decoded are skipped.
+/// Returns `None` if no frame could be rendered.
+#[allow(clippy::cast_possible_truncation)] // frame counts are capped by TIMELAPSE_MAX_FRAMES
+fn render_contact_sheet(paths: &[PathBuf]) -> Option<image::RgbImage> {
+    let frames: Vec<image::RgbImage> = sample_frames(paths)
+        .into_iter()
+        .filter_map(|path| open_oriented(path))
+        .map(|img| {
+            img.resize_to_fill(
+                TIMELAPSE_CELL,
+                TIMELAPSE_CELL,
+                image::imageops::FilterType::Triangle,
+            )
+            .to_rgb8()
+        })
+        .collect();
+    if frames.is_empty() {
+        return None;
+    }
+
+    let columns = frames.len().min(TIMELAPSE_MAX_COLUMNS);
+    let rows = frames.len().div_ceil(columns);
+    let step = TIMELAPSE_CELL + TIMELAPSE_GAP;
+    let mut sheet = image::RgbImage::from_pixel(
+        columns as u32 * step + TIMELAPSE_GAP,
+        rows as u32 * step + TIMELAPSE_GAP,
+        image::Rgb([255, 255, 255]),
+    );
+    for (i, frame) in frames.iter().enumerate() {
+        let x = (i % columns) as u32 * step + TIMELAPSE_GAP;
+        let y = (i / columns) as u32 * step + TIMELAPSE_GAP;
+        image::imageops::replace(&mut sheet, frame, i64::from(x), i64::from(y));
+    }
+    Some(sheet)
+}
+
+/// Cache key of a time-lapse sheet: changes whenever photos are added, removed or reordered.
+fn timelapse_fingerprint(photos: &[String]) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::hash::DefaultHasher::new();
+    (TIMELAPSE_CELL, TIMELAPSE_MAX_FRAMES, TIMELAPSE_MAX_COLUMNS).hash(&mut hasher);
+    photos.hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Return the thumbnail paths for a given original filename.
 fn thumbnail_paths(filename: &str) -> Vec<String> {
     let path = Path::new(filename);
@@ -210,6 +277,70 @@ impl ImageStore {
         }
     }
 
+    /// Return the time-lapse contact sheet of a plant's `photos` (filenames in chronological
+    /// order), rendering it on first use. Sheets are cached under `timelapse/` keyed by the
+    /// photo list, so adding, removing or reordering photos renders a fresh sheet and
+    /// replaces the stale one. Returns `None` if none of the photos can be decoded.
+    ///
+    /// # Errors
+    /// Returns `ImageError::Io` if the sheet cannot be written.
+    pub async fn timelapse(
+        &self,
+        plant_id: i64,
+        photos: &[String],
+    ) -> Result<Option<PathBuf>, ImageError> {
+        let dir = self.upload_dir.join(TIMELAPSE_DIR);
+        let path = dir.join(format!(
+            "{plant_id}-{:016x}.jpg",
+            timelapse_fingerprint(photos)
+        ));
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(Some(path));
+        }
+
+        let paths: Vec<PathBuf> = photos.iter().map(|f| self.upload_dir.join(f)).collect();
+        let Some(sheet) = tokio::task::spawn_blocking(move || render_contact_sheet(&paths))
+            .await
+            .map_err(|e| ImageError::Io(std::io::Error::other(e)))?
+        else {
+            return Ok(None);
+        };
+
+        let mut data = std::io::Cursor::new(Vec::new());
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY)
+            .encode_image(&sheet)
+            .map_err(|e| ImageError::Io(std::io::Error::other(e)))?;
+
+        self.clear_timelapse(plant_id).await;
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(ImageError::Io)?;
+        // Write to a temporary file first so concurrent requests never serve a partial sheet
+        let tmp = dir.join(format!("{}.tmp", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, data.into_inner())
+            .await
+            .map_err(ImageError::Io)?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(ImageError::Io)?;
+        Ok(Some(path))
+    }
+
+    /// Remove all cached time-lapse sheets of a plant.
+    pub async fn clear_timelapse(&self, plant_id: i64) {
+        let Ok(mut entries) = tokio::fs::read_dir(self.upload_dir.join(TIMELAPSE_DIR)).await else {
+            return;
+        };
+        let prefix = format!("{plant_id}-");
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if entry.file_name().to_string_lossy().starts_with(&prefix)
+                && let Err(e) = tokio::fs::remove_file(entry.path()).await
+            {
+                warn!(path = %entry.path().display(), error = %e, "Failed to remove time-lapse sheet");
+            }
+        }
+    }
+
     /// Remove only thumbnail files from the uploads directory, preserving originals.
     pub async fn clear_thumbnails(&self) {
         let Ok(mut entries) = tokio::fs::read_dir(&self.upload_dir).await else {
@@ -231,6 +362,28 @@ impl ImageStore {
         }
     }
 
+    /// Total size in bytes of all files under the uploads directory, including subdirectories.
+    pub async fn disk_usage(&self) -> u64 {
+        let mut total = 0u64;
+        let mut dirs = vec![self.upload_dir.clone()];
+        while let Some(dir) = dirs.pop() {
+            let Ok(mut entries) = tokio::fs::read_dir(&dir).await else {
+                continue;
+            };
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let Ok(metadata) = entry.metadata().await else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    dirs.push(entry.path());
+                } else {
+                    total += metadata.len();
+                }
+            }
+        }
+        total
+    }
+
     pub async fn cleanup_orphans(&self, pool: &SqlitePool) {
         let referenced = Self::referenced_photos(pool).await;
 
@@ -329,6 +482,7 @@ impl ImageStore {
         }
     }
 
+    /// Photos referenced by any row, including trashed ones so they survive until purged.
     async fn referenced_photos(pool: &SqlitePool) -> HashSet<String> {
         let mut referenced: HashSet<String> = HashSet::new();
 
@@ -341,6 +495,13 @@ impl ImageStore {
             referenced.extend(rows);
         }
 
+        if let Ok(rows) = sqlx::query_scalar::<_, String>("SELECT filename FROM plant_photos")
+            .fetch_all(pool)
+            .await
+        {
+            referenced.extend(rows);
+        }
+
         if let Ok(rows) = sqlx::query_scalar::<_, String>(
             "SELECT photo_path FROM care_events WHERE photo_path IS NOT NULL",
         )
@@ -350,6 +511,13 @@ impl ImageStore {
             referenced.extend(rows);
         }
 
+        if let Ok(rows) = sqlx::query_scalar::<_, String>("SELECT filename FROM care_event_photos")
+            .fetch_all(pool)
+            .await
+        {
+            referenced.extend(rows);
+        }
+
         referenced
     }
 }
@@ -616,6 +784,16 @@ mod tests {
         // Should complete without error
     }
 
+    #[tokio::test]
+    async fn disk_usage_sums_files_in_subdirectories() {
+        let (store, dir) = temp_store();
+        std::fs::write(dir.path().join("a.jpg"), [0u8; 10]).unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested").join("b.jpg"), [0u8; 5]).unwrap();
+
+        assert_eq!(store.disk_usage().await, 15);
+    }
+
     #[tokio::test]
     async fn cleanup_orphans_removes_unreferenced_files() {
         let (store, _dir) = temp_store();
@@ -778,6 +956,63 @@ mod tests {
         store.generate_missing_thumbnails(&pool).await;
     }
 
+    #[test]
+    fn sample_frames_keeps_first_and_last() {
+        let items: Vec<usize> = (0..100).collect();
+        let sampled = sample_frames(&items);
+        assert_eq!(sampled.len(), TIMELAPSE_MAX_FRAMES);
+        assert_eq!(*sampled[0], 0);
+        assert_eq!(*sampled[TIMELAPSE_MAX_FRAMES - 1], 99);
+        assert_eq!(sample_frames(&items[..3]).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn timelapse_renders_grid_and_skips_undecodable_files() {
+        let (store, _dir) = temp_store();
+        let mut photos = Vec::new();
+        for _ in 0..7 {
+            photos.push(store.save(&tiny_jpeg(), "image/jpeg").await.unwrap());
+        }
+        photos.push(store.save(b"\xFF\xD8\xFFfake", "image/jpeg").await.unwrap());
+
+        let path = store.timelapse(1, &photos).await.unwrap().unwrap();
+        let sheet = image::open(&path).unwrap();
+        let step = TIMELAPSE_CELL + TIMELAPSE_GAP;
+        // 7 decodable frames: a full row of 6 and a second row
+        assert_eq!(sheet.width(), 6 * step + TIMELAPSE_GAP);
+        assert_eq!(sheet.height(), 2 * step + TIMELAPSE_GAP);
+
+        let fake = photos.pop().unwrap();
+        assert!(store.timelapse(2, &[fake]).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn timelapse_is_cached_until_photos_change() {
+        let (store, _dir) = temp_store();
+        let first = store.save(&tiny_jpeg(), "image/jpeg").await.unwrap();
+        let second = store.save(&tiny_png(), "image/png").await.unwrap();
+
+        let one = store
+            .timelapse(1, std::slice::from_ref(&first))
+            .await
+            .unwrap()
+            .unwrap();
+        let again = store
+            .timelapse(1, std::slice::from_ref(&first))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(one, again);
+
+        let two = store.timelapse(1, &[first, second]).await.unwrap().unwrap();
+        assert_ne!(one, two);
+        assert!(!one.exists());
+        assert!(two.exists());
+
+        store.clear_timelapse(1).await;
+        assert!(!two.exists());
+    }
+
     #[test]
     fn is_thumbnail_filename_detects_200() {
         assert!(is_thumbnail_filename("abc_200.jpg"));
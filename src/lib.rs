@@ -1,9 +1,13 @@
 pub mod ai;
 pub mod api;
+pub mod backups;
 pub mod config;
 pub mod db;
 pub mod embedded;
+pub mod events;
 pub mod images;
+pub mod metrics;
 pub mod mqtt;
 pub mod server;
 pub mod state;
+pub mod trash;
@@ -1,12 +1,16 @@
 mod ai;
 mod api;
+mod backups;
 mod config;
 mod db;
 mod embedded;
+mod events;
 mod images;
+mod metrics;
 mod mqtt;
 mod server;
 mod state;
+mod trash;
 
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -18,6 +22,34 @@ use state::AppState;
 use tracing::{error, info};
 use tracing_subscriber::EnvFilter;
 
+/// Stored backups in `FLOWL_BACKUP_DIR`, by default next to the database.
+fn backup_store(config: &config::Config) -> backups::BackupStore {
+    let backup_dir = config.backup_dir.as_ref().map_or_else(
+        || {
+            PathBuf::from(&config.db_path)
+                .parent()
+                .map_or_else(|| PathBuf::from("backups"), |p| p.join("backups"))
+        },
+        PathBuf::from,
+    );
+    info!("Backup directory at {}", backup_dir.display());
+    let mut store = backups::BackupStore::new(
+        backup_dir,
+        backups::Retention {
+            daily: config.backup_keep_daily,
+            weekly: config.backup_keep_weekly,
+            monthly: config.backup_keep_monthly,
+        },
+    );
+    if let Some(expression) = &config.backup_schedule {
+        match backups::Schedule::parse(expression) {
+            Ok(schedule) => store = store.with_schedule(schedule),
+            Err(e) => error!("Invalid FLOWL_BACKUP_SCHEDULE, scheduled backups disabled: {e}"),
+        }
+    }
+    store
+}
+
 #[tokio::main]
 async fn main() {
     let config = config::Config::load();
@@ -66,6 +98,8 @@ async fn main() {
         .expect("Failed to create upload directory");
     info!("Upload directory at {}", upload_dir.display());
 
+    let backup_store = backup_store(&config);
+
     let image_store = images::ImageStore::new(upload_dir);
     image_store.cleanup_orphans(&pool).await;
     image_store.generate_missing_thumbnails(&pool).await;
@@ -88,8 +122,8 @@ async fn main() {
     let state = AppState {
         pool: pool.clone(),
         image_store,
-        mqtt_client: mqtt_client.clone(),
-        mqtt_prefix: mqtt_prefix.clone(),
+        mqtt_client,
+        mqtt_prefix,
         mqtt_connected: mqtt_connected.clone(),
         mqtt_host: config.mqtt_host.clone(),
         mqtt_port: config.mqtt_port,
@@ -102,18 +136,26 @@ async fn main() {
         } else {
             None
         },
+        metrics: Arc::new(metrics::Metrics::new()),
+        events: events::EventBus::new(),
+        backups: backup_store,
     };
+    let purge_handle = trash::spawn_purge_task(
+        pool.clone(),
+        state.image_store.clone(),
+        config.trash_retention_days,
+    );
+    let backup_handle = backups::spawn_backup_task(&state);
+    let checker_handle = mqtt::spawn_state_checker(&state, mqtt_needs_republish);
     let router = server::router(state);
 
-    let checker_handle =
-        mqtt::spawn_state_checker(pool, mqtt_client.clone(), mqtt_prefix, mqtt_needs_republish);
-
     if let Err(e) = server::serve(router, config.port).await {
         error!("Server error: {e}");
     }
 
     info!("Shutting down");
-    if let Some(handle) = checker_handle {
+    let tasks = [checker_handle, purge_handle, backup_handle];
+    for handle in tasks.into_iter().flatten() {
         handle.abort();
     }
     if let Some(handle) = mqtt_handle {
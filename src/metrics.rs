@@ -0,0 +1,372 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::api::plants::compute_watering_status;
+use crate::state::AppState;
+
+const HTTP_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+const AI_BUCKETS: &[f64] = &[0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0];
+
+struct Histogram {
+    bounds: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (i, bound) in self.bounds.iter().enumerate() {
+            if value <= *bound {
+                self.counts[i] += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+#[derive(Default)]
+struct AiStats {
+    calls: u64,
+    failures: u64,
+    latency: Option<Histogram>,
+}
+
+/// In-process counters and histograms exposed on `/metrics` in the Prometheus text format.
+///
+/// Values derived from the database (plant and care event counts) are computed at scrape time
+/// and are not stored here.
+#[derive(Default)]
+pub struct Metrics {
+    http_requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    http_latency: Mutex<BTreeMap<(String, String), Histogram>>,
+    ai: Mutex<BTreeMap<&'static str, AiStats>>,
+    mqtt_publish_failures: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a finished HTTP request. `route` should be the matched route template so label
+    /// cardinality stays bounded.
+    pub fn record_http(&self, method: &str, route: &str, status: u16, latency: Duration) {
+        if let Ok(mut requests) = self.http_requests.lock() {
+            *requests
+                .entry((method.to_string(), route.to_string(), status))
+                .or_default() += 1;
+        }
+        if let Ok(mut histograms) = self.http_latency.lock() {
+            histograms
+                .entry((method.to_string(), route.to_string()))
+                .or_insert_with(|| Histogram::new(HTTP_BUCKETS))
+                .observe(latency.as_secs_f64());
+        }
+    }
+
+    /// Record a call to the AI provider for the given task (`identify`, `chat`, `summarize`).
+    pub fn record_ai(&self, task: &'static str, latency: Duration, success: bool) {
+        if let Ok(mut ai) = self.ai.lock() {
+            let stats = ai.entry(task).or_default();
+            stats.calls += 1;
+            if !success {
+                stats.failures += 1;
+            }
+            stats
+                .latency
+                .get_or_insert_with(|| Histogram::new(AI_BUCKETS))
+                .observe(latency.as_secs_f64());
+        }
+    }
+
+    /// Record an MQTT publish that still failed after all retries.
+    pub fn record_mqtt_publish_failure(&self) {
+        self.mqtt_publish_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render_http(&self, out: &mut String) {
+        out.push_str("# HELP flowl_http_requests_total Total HTTP requests handled.\n");
+        out.push_str("# TYPE flowl_http_requests_total counter\n");
+        if let Ok(requests) = self.http_requests.lock() {
+            for ((method, route, status), count) in requests.iter() {
+                let _ = writeln!(
+                    out,
+                    "flowl_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{status}\"}} {count}",
+                    escape_label(method),
+                    escape_label(route),
+                );
+            }
+        }
+
+        out.push_str("# HELP flowl_http_request_duration_seconds HTTP request latency.\n");
+        out.push_str("# TYPE flowl_http_request_duration_seconds histogram\n");
+        if let Ok(histograms) = self.http_latency.lock() {
+            for ((method, route), histogram) in histograms.iter() {
+                let labels = format!(
+                    "method=\"{}\",route=\"{}\"",
+                    escape_label(method),
+                    escape_label(route)
+                );
+                histogram.render(out, "flowl_http_request_duration_seconds", &labels);
+            }
+        }
+    }
+
+    fn render_ai(&self, out: &mut String) {
+        let Ok(ai) = self.ai.lock() else { return };
+
+        out.push_str("# HELP flowl_ai_requests_total Total AI provider calls per task.\n");
+        out.push_str("# TYPE flowl_ai_requests_total counter\n");
+        for (task, stats) in ai.iter() {
+            let _ = writeln!(
+                out,
+                "flowl_ai_requests_total{{task=\"{task}\"}} {}",
+                stats.calls
+            );
+        }
+
+        out.push_str("# HELP flowl_ai_failures_total Failed AI provider calls per task.\n");
+        out.push_str("# TYPE flowl_ai_failures_total counter\n");
+        for (task, stats) in ai.iter() {
+            let _ = writeln!(
+                out,
+                "flowl_ai_failures_total{{task=\"{task}\"}} {}",
+                stats.failures
+            );
+        }
+
+        out.push_str("# HELP flowl_ai_request_duration_seconds AI provider call latency.\n");
+        out.push_str("# TYPE flowl_ai_request_duration_seconds histogram\n");
+        for (task, stats) in ai.iter() {
+            if let Some(histogram) = &stats.latency {
+                histogram.render(
+                    out,
+                    "flowl_ai_request_duration_seconds",
+                    &format!("task=\"{task}\""),
+                );
+            }
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PlantStatusRow {
+    location_name: Option<String>,
+    watering_interval_days: i64,
+    last_watered: Option<String>,
+}
+
+/// Render all metrics in the Prometheus text exposition format.
+pub async fn render(state: &AppState) -> String {
+    let mut out = String::new();
+
+    render_plants(state, &mut out).await;
+    render_care_events(state, &mut out).await;
+    state.metrics.render_http(&mut out);
+    state.metrics.render_ai(&mut out);
+    render_mqtt(state, &mut out);
+
+    let upload_bytes = state.image_store.disk_usage().await;
+    out.push_str("# HELP flowl_upload_dir_bytes Total size of the upload directory.\n");
+    out.push_str("# TYPE flowl_upload_dir_bytes gauge\n");
+    let _ = writeln!(out, "flowl_upload_dir_bytes {upload_bytes}");
+
+    out
+}
+
+async fn render_plants(state: &AppState, out: &mut String) {
+    let rows = match sqlx::query_as::<_, PlantStatusRow>(
+        "SELECT l.name AS location_name, p.watering_interval_days, lw.last_watered \
+         FROM plants p LEFT JOIN locations l ON p.location_id = l.id \
+         LEFT JOIN plant_last_watered lw ON lw.plant_id = p.id \
+         WHERE p.deleted_at IS NULL AND p.lifecycle_status = 'active'",
+    )
+    .fetch_all(&state.pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::warn!("Metrics plant query failed: {e}");
+            return;
+        }
+    };
+
+    let mut counts: BTreeMap<(String, String), u64> = BTreeMap::new();
+    for row in rows {
+        let (status, _) =
+            compute_watering_status(row.last_watered.as_deref(), row.watering_interval_days);
+        let location = row.location_name.unwrap_or_default();
+        *counts.entry((status, location)).or_default() += 1;
+    }
+
+    out.push_str("# HELP flowl_plants Number of plants per watering status and location.\n");
+    out.push_str("# TYPE flowl_plants gauge\n");
+    for ((status, location), count) in &counts {
+        let _ = writeln!(
+            out,
+            "flowl_plants{{watering_status=\"{status}\",location=\"{}\"}} {count}",
+            escape_label(location)
+        );
+    }
+}
+
+async fn render_care_events(state: &AppState, out: &mut String) {
+    let rows = match sqlx::query_as::<_, (String, i64)>(
+        "SELECT ce.event_type, COUNT(*) FROM care_events ce JOIN plants p ON ce.plant_id = p.id \
+         WHERE ce.deleted_at IS NULL AND p.deleted_at IS NULL \
+         GROUP BY ce.event_type ORDER BY ce.event_type",
+    )
+    .fetch_all(&state.pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::warn!("Metrics care event query failed: {e}");
+            return;
+        }
+    };
+
+    out.push_str("# HELP flowl_care_events Number of care events per type.\n");
+    out.push_str("# TYPE flowl_care_events gauge\n");
+    for (event_type, count) in rows {
+        let _ = writeln!(
+            out,
+            "flowl_care_events{{event_type=\"{}\"}} {count}",
+            escape_label(&event_type)
+        );
+    }
+}
+
+fn render_mqtt(state: &AppState, out: &mut String) {
+    let connected = state
+        .mqtt_connected
+        .as_ref()
+        .is_some_and(|b| b.load(Ordering::Relaxed));
+
+    out.push_str("# HELP flowl_mqtt_enabled Whether the MQTT integration is enabled.\n");
+    out.push_str("# TYPE flowl_mqtt_enabled gauge\n");
+    let _ = writeln!(out, "flowl_mqtt_enabled {}", u8::from(!state.mqtt_disabled));
+
+    out.push_str("# HELP flowl_mqtt_connected Whether the MQTT client is connected.\n");
+    out.push_str("# TYPE flowl_mqtt_connected gauge\n");
+    let _ = writeln!(out, "flowl_mqtt_connected {}", u8::from(connected));
+
+    out.push_str(
+        "# HELP flowl_mqtt_publish_failures_total MQTT publishes that failed after all retries.\n",
+    );
+    out.push_str("# TYPE flowl_mqtt_publish_failures_total counter\n");
+    let _ = writeln!(
+        out,
+        "flowl_mqtt_publish_failures_total {}",
+        state.metrics.mqtt_publish_failures.load(Ordering::Relaxed)
+    );
+}
+
+fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let mut histogram = Histogram::new(&[0.1, 1.0]);
+        histogram.observe(0.05);
+        histogram.observe(0.5);
+        histogram.observe(5.0);
+
+        let mut out = String::new();
+        histogram.render(&mut out, "test_seconds", "task=\"x\"");
+
+        assert!(out.contains("test_seconds_bucket{task=\"x\",le=\"0.1\"} 1\n"));
+        assert!(out.contains("test_seconds_bucket{task=\"x\",le=\"1\"} 2\n"));
+        assert!(out.contains("test_seconds_bucket{task=\"x\",le=\"+Inf\"} 3\n"));
+        assert!(out.contains("test_seconds_count{task=\"x\"} 3\n"));
+    }
+
+    #[test]
+    fn record_http_counts_per_route_and_status() {
+        let metrics = Metrics::new();
+        metrics.record_http("GET", "/api/plants", 200, Duration::from_millis(3));
+        metrics.record_http("GET", "/api/plants", 200, Duration::from_millis(7));
+        metrics.record_http("GET", "/api/plants/{id}", 404, Duration::from_millis(1));
+
+        let mut out = String::new();
+        metrics.render_http(&mut out);
+
+        assert!(out.contains(
+            "flowl_http_requests_total{method=\"GET\",route=\"/api/plants\",status=\"200\"} 2\n"
+        ));
+        assert!(out.contains(
+            "flowl_http_requests_total{method=\"GET\",route=\"/api/plants/{id}\",status=\"404\"} 1\n"
+        ));
+        assert!(out.contains(
+            "flowl_http_request_duration_seconds_count{method=\"GET\",route=\"/api/plants\"} 2\n"
+        ));
+    }
+
+    #[test]
+    fn record_ai_tracks_failures() {
+        let metrics = Metrics::new();
+        metrics.record_ai("chat", Duration::from_secs(1), true);
+        metrics.record_ai("chat", Duration::from_secs(2), false);
+
+        let mut out = String::new();
+        metrics.render_ai(&mut out);
+
+        assert!(out.contains("flowl_ai_requests_total{task=\"chat\"} 2\n"));
+        assert!(out.contains("flowl_ai_failures_total{task=\"chat\"} 1\n"));
+        assert!(out.contains("flowl_ai_request_duration_seconds_count{task=\"chat\"} 2\n"));
+    }
+
+    #[test]
+    fn record_mqtt_publish_failure_counts_failures() {
+        let metrics = Metrics::new();
+        metrics.record_mqtt_publish_failure();
+        metrics.record_mqtt_publish_failure();
+
+        assert_eq!(metrics.mqtt_publish_failures.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn escape_label_handles_quotes_and_newlines() {
+        assert_eq!(escape_label("Kid's \"Room\"\n"), "Kid's \\\"Room\\\"\\n");
+        assert_eq!(escape_label("a\\b"), "a\\\\b");
+    }
+}
@@ -1,8 +1,10 @@
+use std::sync::Arc;
 use std::time::Instant;
 
 use axum::Router;
 use axum::body::Body;
-use axum::http::{Request, StatusCode};
+use axum::extract::{MatchedPath, State};
+use axum::http::{Request, StatusCode, header};
 use axum::middleware::{self, Next};
 use axum::response::{IntoResponse, Json, Response};
 use axum::routing::get;
@@ -15,31 +17,52 @@ use tracing::{debug, info};
 
 use crate::api;
 use crate::embedded::static_handler;
+use crate::metrics::{self, Metrics};
 use crate::state::AppState;
 
 pub fn router(state: AppState) -> Router {
     let uploads = ServeDir::new(state.image_store.upload_dir());
     let pool = state.pool.clone();
+    let metrics_state = state.clone();
+    let metrics = state.metrics.clone();
     Router::new()
         .route("/health", get(move || health(pool)))
+        .route("/metrics", get(move || metrics_handler(metrics_state)))
         .route("/api/info", get(info))
         .nest("/api", api::router(state))
         .nest_service("/uploads", uploads)
         .fallback(static_handler)
-        .layer(middleware::from_fn(access_log))
+        .layer(middleware::from_fn_with_state(metrics, access_log))
 }
 
-async fn access_log(req: Request<Body>, next: Next) -> Response {
+async fn access_log(
+    State(metrics): State<Arc<Metrics>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
     let method = req.method().clone();
     let path = req.uri().path().to_string();
+    // Label metrics by route template rather than raw path to keep cardinality bounded
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |p| p.as_str().to_string());
     let start = Instant::now();
     let response = next.run(req).await;
     let status = response.status().as_u16();
     let latency = start.elapsed();
     debug!(method = %method, path, status, latency_ms = latency.as_millis(), "access");
+    metrics.record_http(method.as_str(), &route, status, latency);
     response
 }
 
+async fn metrics_handler(state: AppState) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics::render(&state).await,
+    )
+}
+
 async fn health(pool: SqlitePool) -> impl IntoResponse {
     match sqlx::query_scalar::<_, i32>("SELECT 1")
         .fetch_one(&pool)
@@ -7,7 +7,10 @@ use rumqttc::AsyncClient;
 use sqlx::SqlitePool;
 
 use crate::ai::provider::AiProvider;
+use crate::backups::BackupStore;
+use crate::events::EventBus;
 use crate::images::ImageStore;
+use crate::metrics::Metrics;
 
 pub struct AiRateLimiter {
     limit: u32,
@@ -62,6 +65,9 @@ pub struct AppState {
     pub ai_base_url: String,
     pub ai_model: String,
     pub ai_rate_limiter: Option<Arc<AiRateLimiter>>,
+    pub metrics: Arc<Metrics>,
+    pub events: EventBus,
+    pub backups: BackupStore,
 }
 
 impl FromRef<AppState> for SqlitePool {
@@ -0,0 +1,164 @@
+use sqlx::SqlitePool;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::images::ImageStore;
+
+/// Permanently delete a trashed plant together with its care events and all their photos.
+/// Returns `false` if no trashed plant with this id exists.
+///
+/// # Errors
+/// Returns the underlying database error if the plant cannot be deleted.
+pub async fn purge_plant(
+    pool: &SqlitePool,
+    image_store: &ImageStore,
+    id: i64,
+) -> Result<bool, sqlx::Error> {
+    let photos = sqlx::query_scalar::<_, String>(
+        "SELECT photo_path FROM plants WHERE id = ?1 AND photo_path IS NOT NULL \
+         UNION SELECT filename FROM plant_photos WHERE plant_id = ?1 \
+         UNION SELECT photo_path FROM care_events WHERE plant_id = ?1 AND photo_path IS NOT NULL \
+         UNION SELECT cep.filename FROM care_event_photos cep \
+         JOIN care_events ce ON ce.id = cep.care_event_id WHERE ce.plant_id = ?1",
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await?;
+
+    // Care events and gallery photos go with the plant via ON DELETE CASCADE
+    let result = sqlx::query("DELETE FROM plants WHERE id = ? AND deleted_at IS NOT NULL")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    for filename in &photos {
+        image_store.delete(filename).await;
+    }
+    image_store.clear_timelapse(id).await;
+    Ok(true)
+}
+
+/// Permanently delete a trashed care event and its photos.
+/// Returns `false` if no trashed care event with this id exists.
+///
+/// # Errors
+/// Returns the underlying database error if the care event cannot be deleted.
+pub async fn purge_care_event(
+    pool: &SqlitePool,
+    image_store: &ImageStore,
+    id: i64,
+) -> Result<bool, sqlx::Error> {
+    let photos = sqlx::query_scalar::<_, String>(
+        "SELECT filename FROM care_event_photos WHERE care_event_id = ?",
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await?;
+
+    // Photo rows go with the event via ON DELETE CASCADE
+    let photo = sqlx::query_scalar::<_, Option<String>>(
+        "DELETE FROM care_events WHERE id = ? AND deleted_at IS NOT NULL RETURNING photo_path",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(photo) = photo else {
+        return Ok(false);
+    };
+    let mut filenames: std::collections::BTreeSet<String> = photos.into_iter().collect();
+    filenames.extend(photo);
+    for filename in &filenames {
+        image_store.delete(filename).await;
+    }
+    Ok(true)
+}
+
+/// Permanently delete everything trashed before `cutoff` (RFC 3339, or `None` for all).
+/// Returns the number of purged plants and care events.
+///
+/// # Errors
+/// Returns the underlying database error if the trash cannot be queried or purged.
+pub async fn purge_trashed_before(
+    pool: &SqlitePool,
+    image_store: &ImageStore,
+    cutoff: Option<&str>,
+) -> Result<usize, sqlx::Error> {
+    let plant_ids = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM plants WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)",
+    )
+    .bind(cutoff)
+    .fetch_all(pool)
+    .await?;
+    let event_ids = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM care_events \
+         WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)",
+    )
+    .bind(cutoff)
+    .fetch_all(pool)
+    .await?;
+
+    let mut purged = 0;
+    for id in plant_ids {
+        if purge_plant(pool, image_store, id).await? {
+            purged += 1;
+        }
+    }
+    for id in event_ids {
+        // Events of a plant purged above are already gone
+        if purge_care_event(pool, image_store, id).await? {
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
+
+/// Spawn a background task that purges trash older than `retention_days` every hour.
+/// Returns `None` when the retention is 0, which keeps trashed items until emptied manually.
+pub fn spawn_purge_task(
+    pool: SqlitePool,
+    image_store: ImageStore,
+    retention_days: u32,
+) -> Option<JoinHandle<()>> {
+    if retention_days == 0 {
+        info!("Trash retention disabled, trashed items are kept until emptied");
+        return None;
+    }
+
+    info!(retention_days, "Starting trash purge task");
+
+    Some(tokio::spawn(async move {
+        loop {
+            let cutoff = (chrono::Utc::now() - chrono::Duration::days(i64::from(retention_days)))
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+            match purge_trashed_before(&pool, &image_store, Some(&cutoff)).await {
+                Ok(0) => {}
+                Ok(purged) => info!(purged, "Purged expired trash"),
+                Err(e) => warn!("Trash purge failed: {e}"),
+            }
+
+            tokio::time::sleep(std::time::Duration::from_hours(1)).await;
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn spawn_purge_task_skips_when_retention_is_zero() {
+        let pool = SqlitePoolOptions::new()
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create in-memory pool");
+        let store = ImageStore::new(std::path::PathBuf::from("uploads"));
+
+        let handle = spawn_purge_task(pool, store, 0);
+        assert!(handle.is_none());
+    }
+}
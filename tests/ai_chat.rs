@@ -79,6 +79,43 @@ impl AiProvider for FailingChatProvider {
     }
 }
 
+struct InterruptedChatProvider;
+
+#[async_trait]
+impl AiProvider for InterruptedChatProvider {
+    async fn identify(
+        &self,
+        _images: &[&[u8]],
+        _locale: &str,
+    ) -> Result<IdentifyResponse, Box<dyn std::error::Error + Send + Sync>> {
+        unimplemented!()
+    }
+
+    async fn chat(
+        &self,
+        _system_prompt: &str,
+        _messages: &[ChatMessage],
+        _image: Option<&[u8]>,
+        _locale: &str,
+    ) -> Result<ChatResponseStream, Box<dyn std::error::Error + Send + Sync>> {
+        let (tx, rx) = tokio::sync::mpsc::channel(32);
+        tokio::spawn(async move {
+            let _ = tx.send(Ok("Hello ".to_string())).await;
+            let _ = tx.send(Err("connection reset".to_string())).await;
+        });
+        Ok(tokio_stream::wrappers::ReceiverStream::new(rx))
+    }
+
+    async fn summarize(
+        &self,
+        _system_prompt: &str,
+        _messages: &[ChatMessage],
+        _locale: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        unimplemented!()
+    }
+}
+
 async fn test_app_with_provider(
     provider: Arc<dyn AiProvider>,
 ) -> (Router, sqlx::SqlitePool, tempfile::TempDir) {
@@ -98,6 +135,12 @@ async fn test_app_with_provider(
         ai_base_url: "https://api.openai.com/v1".to_string(),
         ai_model: "gpt-4.1-mini".to_string(),
         ai_rate_limiter: None,
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     (flowl::server::router(state), pool, tmp)
 }
@@ -254,6 +297,31 @@ async fn chat_returns_500_when_provider_fails() {
     assert_eq!(body["code"], "AI_PROVIDER_FAILED");
 }
 
+#[tokio::test]
+async fn chat_stream_errors_count_as_failed_calls() {
+    let (app, pool, _dir) = test_app_with_provider(Arc::new(InterruptedChatProvider)).await;
+    let plant_id = insert_test_plant(&pool).await;
+
+    let request = common::json_request(
+        "POST",
+        "/api/ai/chat",
+        Some(&format!(r#"{{"plant_id":{plant_id},"message":"hello"}}"#)),
+    );
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = String::from_utf8(body_bytes(response).await).unwrap();
+    assert!(body.contains(r#""code":"AI_STREAM_ERROR""#));
+
+    let response = app
+        .oneshot(common::json_request("GET", "/metrics", None))
+        .await
+        .unwrap();
+    let metrics = String::from_utf8(body_bytes(response).await).unwrap();
+    assert!(metrics.contains("flowl_ai_requests_total{task=\"chat\"} 1\n"));
+    assert!(metrics.contains("flowl_ai_failures_total{task=\"chat\"} 1\n"));
+}
+
 #[tokio::test]
 async fn chat_returns_429_when_rate_limited() {
     let pool = common::test_pool().await;
@@ -272,6 +340,12 @@ async fn chat_returns_429_when_rate_limited() {
         ai_base_url: "https://api.openai.com/v1".to_string(),
         ai_model: "gpt-4.1-mini".to_string(),
         ai_rate_limiter: Some(Arc::new(flowl::state::AiRateLimiter::new(1))),
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     let app = flowl::server::router(state);
     let plant_id = insert_test_plant(&pool).await;
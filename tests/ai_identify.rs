@@ -111,6 +111,12 @@ async fn test_app_with_provider(provider: Arc<dyn AiProvider>) -> (Router, tempf
         ai_base_url: "https://api.openai.com/v1".to_string(),
         ai_model: "gpt-4.1-mini".to_string(),
         ai_rate_limiter: None,
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     (flowl::server::router(state), tmp)
 }
@@ -136,6 +142,12 @@ async fn test_app_rate_limited() -> (Router, tempfile::TempDir) {
         ai_base_url: "https://api.openai.com/v1".to_string(),
         ai_model: "gpt-4.1-mini".to_string(),
         ai_rate_limiter: Some(Arc::new(AiRateLimiter::new(1))),
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     (flowl::server::router(state), tmp)
 }
@@ -32,6 +32,12 @@ async fn test_app_ai_enabled() -> (Router, tempfile::TempDir) {
         ai_base_url: "https://api.openai.com/v1".to_string(),
         ai_model: "gpt-4.1-mini".to_string(),
         ai_rate_limiter: None,
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     (flowl::server::router(state), tmp)
 }
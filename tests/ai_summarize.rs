@@ -96,6 +96,12 @@ async fn test_app_with_provider(
         ai_base_url: "https://api.openai.com/v1".to_string(),
         ai_model: "gpt-4.1-mini".to_string(),
         ai_rate_limiter: None,
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     (flowl::server::router(state), pool, tmp)
 }
@@ -218,6 +224,12 @@ async fn summarize_returns_429_when_rate_limited() {
         ai_base_url: "https://api.openai.com/v1".to_string(),
         ai_model: "gpt-4.1-mini".to_string(),
         ai_rate_limiter: Some(Arc::new(AiRateLimiter::new(1))),
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     let app = flowl::server::router(state);
     let plant_id = insert_test_plant(&pool).await;
@@ -93,6 +93,8 @@ async fn export_empty_database() {
 
     assert_eq!(response.status(), StatusCode::OK);
     assert_eq!(response.headers()["content-type"], "application/zip");
+    // Streamed while it is written, so the length is not known up front
+    assert!(response.headers().get("content-length").is_none());
     assert_eq!(
         response.headers()["content-disposition"],
         format!(
@@ -113,6 +115,10 @@ async fn export_empty_database() {
     let data: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(data["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        data["format_version"],
+        flowl::api::backup_format::FORMAT_VERSION
+    );
     assert!(data["exported_at"].is_string());
     assert_eq!(data["locations"].as_array().unwrap().len(), 0);
     assert_eq!(data["plants"].as_array().unwrap().len(), 0);
@@ -146,6 +152,12 @@ async fn export_populated_database_with_photo() {
         ai_base_url: String::new(),
         ai_model: String::new(),
         ai_rate_limiter: None,
+        metrics: std::sync::Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            dir.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     let app = flowl::server::router(state.clone());
 
@@ -207,6 +219,105 @@ async fn import_valid_archive() {
     assert_eq!(body["photos"], 0);
 }
 
+#[tokio::test]
+async fn import_preserves_lineage() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+
+    // The child is listed before its parent
+    let json = format!(
+        r#"{{
+            "version": "{}",
+            "exported_at": "2026-02-21T12:00:00Z",
+            "locations": [],
+            "plants": [
+                {{"id": 1, "name": "Cutting", "species": null, "icon": "🪴",
+                  "photo_path": null, "location_id": null, "watering_interval_days": 7,
+                  "light_needs": "indirect", "difficulty": null, "pet_safety": null,
+                  "growth_speed": null, "soil_type": null, "soil_moisture": null, "notes": null,
+                  "parent_id": 2,
+                  "created_at": "2026-02-01T08:00:00Z", "updated_at": "2026-02-01T08:00:00Z"}},
+                {{"id": 2, "name": "Mother", "species": null, "icon": "🪴",
+                  "photo_path": null, "location_id": null, "watering_interval_days": 7,
+                  "light_needs": "indirect", "difficulty": null, "pet_safety": null,
+                  "growth_speed": null, "soil_type": null, "soil_moisture": null, "notes": null,
+                  "created_at": "2026-01-01T08:00:00Z", "updated_at": "2026-01-01T08:00:00Z"}}
+            ],
+            "care_events": [{{
+                "id": 1, "plant_id": 2, "event_type": "propagated", "notes": null,
+                "child_plant_id": 1, "occurred_at": "2026-02-01T08:00:00Z",
+                "created_at": "2026-02-01T08:00:00Z"
+            }}]
+        }}"#,
+        env!("CARGO_PKG_VERSION")
+    );
+    let response = app
+        .clone()
+        .oneshot(multipart_import_request(&build_export_zip(&json)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/plants/2/lineage", None))
+        .await
+        .unwrap();
+    let lineage = common::body_json(response).await;
+    assert_eq!(lineage["descendants"][0]["id"], 1);
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/2/care", None))
+        .await
+        .unwrap();
+    let events = common::body_json(response).await;
+    assert_eq!(events[0]["child_plant_id"], 1);
+}
+
+#[tokio::test]
+async fn import_preserves_tags() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+
+    let json = format!(
+        r#"{{
+            "version": "{}",
+            "exported_at": "2026-02-21T12:00:00Z",
+            "locations": [],
+            "plants": [{{
+                "id": 1, "name": "Aloe", "species": null, "icon": "🪴",
+                "photo_path": null, "location_id": null, "watering_interval_days": 14,
+                "light_needs": "direct", "difficulty": null, "pet_safety": null,
+                "growth_speed": null, "soil_type": null, "soil_moisture": null, "notes": null,
+                "created_at": "2026-01-01T08:00:00Z", "updated_at": "2026-01-01T08:00:00Z"
+            }}],
+            "care_events": [],
+            "tags": [{{"id": 3, "name": "succulents"}}, {{"id": 4, "name": "office"}}],
+            "plant_tags": [{{"plant_id": 1, "tag_id": 3}}]
+        }}"#,
+        env!("CARGO_PKG_VERSION")
+    );
+    let response = app
+        .clone()
+        .oneshot(multipart_import_request(&build_export_zip(&json)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/plants/1", None))
+        .await
+        .unwrap();
+    let plant = common::body_json(response).await;
+    assert_eq!(plant["tags"], serde_json::json!(["succulents"]));
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/tags", None))
+        .await
+        .unwrap();
+    let tags = common::body_json(response).await;
+    assert_eq!(tags.as_array().unwrap().len(), 2);
+}
+
 #[tokio::test]
 async fn import_with_photo() {
     let (app, dir) = common::test_app_with_uploads().await;
@@ -234,6 +345,7 @@ async fn import_with_photo() {
     let zip_bytes = build_export_zip_with_photo(&json, "test-photo.jpg", photo_data);
 
     let response = app
+        .clone()
         .oneshot(multipart_import_request(&zip_bytes))
         .await
         .unwrap();
@@ -247,6 +359,177 @@ async fn import_with_photo() {
     assert!(photo_path.exists());
     let contents = std::fs::read(&photo_path).unwrap();
     assert_eq!(contents, photo_data);
+
+    // Backups without a gallery get the cover as the only gallery photo
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/1/photos", None))
+        .await
+        .unwrap();
+    let photos = common::body_json(response).await;
+    assert_eq!(photos.as_array().unwrap().len(), 1);
+    assert_eq!(photos[0]["url"], "/uploads/test-photo.jpg");
+    assert_eq!(photos[0]["is_cover"], true);
+}
+
+#[tokio::test]
+async fn import_preserves_photo_gallery() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+
+    let json = format!(
+        r#"{{
+            "version": "{}",
+            "exported_at": "2026-02-21T12:00:00Z",
+            "locations": [],
+            "plants": [{{
+                "id": 1, "name": "Fern", "species": null, "icon": "🪴",
+                "photo_path": "new.jpg", "location_id": null, "watering_interval_days": 3,
+                "light_needs": "indirect", "difficulty": null, "pet_safety": null,
+                "growth_speed": null, "soil_type": null, "soil_moisture": null, "notes": null,
+                "created_at": "2026-01-01T08:00:00Z", "updated_at": "2026-01-01T08:00:00Z"
+            }}],
+            "care_events": [],
+            "plant_photos": [
+                {{"id": 7, "plant_id": 1, "filename": "new.jpg", "caption": null,
+                  "taken_at": "2026-02-01", "position": 1, "created_at": "2026-02-01T08:00:00Z"}},
+                {{"id": 5, "plant_id": 1, "filename": "old.jpg", "caption": "Day one",
+                  "taken_at": "2026-01-01", "position": 0, "created_at": "2026-01-01T08:00:00Z"}}
+            ]
+        }}"#,
+        env!("CARGO_PKG_VERSION")
+    );
+    let response = app
+        .clone()
+        .oneshot(multipart_import_request(&build_export_zip(&json)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/1/photos", None))
+        .await
+        .unwrap();
+    let photos = common::body_json(response).await;
+    assert_eq!(photos.as_array().unwrap().len(), 2);
+    assert_eq!(photos[0]["id"], 5);
+    assert_eq!(photos[0]["caption"], "Day one");
+    assert_eq!(photos[0]["is_cover"], false);
+    assert_eq!(photos[1]["id"], 7);
+    assert_eq!(photos[1]["is_cover"], true);
+}
+
+#[tokio::test]
+async fn import_care_event_photos() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+
+    let json = valid_export_json().replace(
+        r#""notes": null, "occurred_at""#,
+        r#""notes": null, "photo_path": "legacy.jpg", "occurred_at""#,
+    );
+    let response = app
+        .clone()
+        .oneshot(multipart_import_request(&build_export_zip(&json)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Backups from before multiple photos get their single photo as the only one
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/plants/1/care", None))
+        .await
+        .unwrap();
+    let events = common::body_json(response).await;
+    assert_eq!(events[0]["photos"].as_array().unwrap().len(), 1);
+    assert_eq!(events[0]["photos"][0]["url"], "/uploads/legacy.jpg");
+
+    let json = valid_export_json().replace(
+        r#""care_events": ["#,
+        r#""care_event_photos": [
+            {"id": 4, "care_event_id": 1, "filename": "b.jpg", "position": 1,
+             "created_at": "2026-02-15T10:00:00Z"},
+            {"id": 3, "care_event_id": 1, "filename": "a.jpg", "position": 0,
+             "created_at": "2026-02-15T10:00:00Z"}
+        ],
+        "care_events": ["#,
+    );
+    let response = app
+        .clone()
+        .oneshot(multipart_import_request(&build_export_zip(&json)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/1/care", None))
+        .await
+        .unwrap();
+    let events = common::body_json(response).await;
+    assert_eq!(events[0]["photos"][0]["id"], 3);
+    assert_eq!(events[0]["photos"][1]["id"], 4);
+    assert_eq!(events[0]["photo_url"], "/uploads/a.jpg");
+}
+
+#[tokio::test]
+async fn import_preserves_measurements() {
+    let (app, _dir) = common::test_app().await;
+
+    let mut data: serde_json::Value = serde_json::from_str(&valid_export_json()).unwrap();
+    data["measurements"] = serde_json::json!([{
+        "id": 3, "plant_id": 1, "care_event_id": 1, "metric": "height", "value": 42.5,
+        "notes": "Before repotting", "measured_at": "2026-02-15T10:00:00Z",
+        "created_at": "2026-02-15T10:00:00Z"
+    }]);
+    let response = app
+        .clone()
+        .oneshot(multipart_import_request(&build_export_zip(
+            &data.to_string(),
+        )))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "GET",
+            "/api/plants/1/measurements",
+            None,
+        ))
+        .await
+        .unwrap();
+    let measurements = common::body_json(response).await;
+    assert_eq!(measurements[0]["id"], 3);
+    assert_eq!(measurements[0]["care_event_id"], 1);
+    assert_eq!(measurements[0]["value"], 42.5);
+    assert_eq!(measurements[0]["unit"], "cm");
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/data/export", None))
+        .await
+        .unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(&bytes[..])).unwrap();
+    let mut json = String::new();
+    archive
+        .by_name("data.json")
+        .unwrap()
+        .read_to_string(&mut json)
+        .unwrap();
+    let export: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(export["measurements"][0]["metric"], "height");
+    assert_eq!(export["measurements"][0]["notes"], "Before repotting");
+
+    data["measurements"][0]["metric"] = "weight".into();
+    let (app, _dir) = common::test_app().await;
+    let response = app
+        .oneshot(multipart_import_request(&build_export_zip(
+            &data.to_string(),
+        )))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
 }
 
 #[tokio::test]
@@ -284,9 +567,10 @@ async fn import_missing_data_json() {
 }
 
 #[tokio::test]
-async fn import_version_mismatch() {
+async fn import_rejects_newer_format() {
     let json = r#"{
         "version": "99.0.0",
+        "format_version": 99,
         "exported_at": "2026-02-21T12:00:00Z",
         "locations": [],
         "plants": [],
@@ -303,34 +587,43 @@ async fn import_version_mismatch() {
 
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     let body = common::body_json(response).await;
-    assert_eq!(body["code"], "IMPORT_VERSION_MISMATCH");
+    assert_eq!(body["code"], "IMPORT_FORMAT_TOO_NEW");
 }
 
 #[tokio::test]
-async fn import_patch_version_difference_allowed() {
-    let parts: Vec<&str> = env!("CARGO_PKG_VERSION").split('.').collect();
-    let patch: u32 = parts[2].parse::<u32>().unwrap() + 1;
-    let compatible_version = format!("{}.{}.{}", parts[0], parts[1], patch);
-
-    let json = format!(
-        r#"{{
-            "version": "{compatible_version}",
-            "exported_at": "2026-02-21T12:00:00Z",
-            "locations": [],
-            "plants": [],
-            "care_events": []
-        }}"#
-    );
-    let zip_bytes = build_export_zip(&json);
+async fn import_upgrades_archive_from_older_version() {
+    // Written by 1.4 before the format version: no lifecycle fields or newer collections
+    let json = r#"{
+        "version": "1.4.0",
+        "exported_at": "2025-11-02T12:00:00Z",
+        "locations": [],
+        "plants": [{
+            "id": 1, "name": "Fern", "species": null, "icon": "🌿",
+            "photo_path": null, "location_id": null, "watering_interval_days": 7,
+            "light_needs": "indirect", "difficulty": null, "pet_safety": null,
+            "growth_speed": null, "soil_type": null, "soil_moisture": null, "notes": null,
+            "created_at": "2025-10-01T08:00:00", "updated_at": "2025-10-01T08:00:00"
+        }],
+        "care_events": []
+    }"#;
+    let zip_bytes = build_export_zip(json);
 
     let (app, _dir) = common::test_app().await;
 
     let response = app
+        .clone()
         .oneshot(multipart_import_request(&zip_bytes))
         .await
         .unwrap();
-
     assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/1", None))
+        .await
+        .unwrap();
+    let plant = common::body_json(response).await;
+    assert_eq!(plant["name"], "Fern");
+    assert_eq!(plant["lifecycle_status"], "active");
 }
 
 #[tokio::test]
@@ -497,6 +790,12 @@ async fn failed_import_preserves_existing_photos() {
         ai_base_url: String::new(),
         ai_model: String::new(),
         ai_rate_limiter: None,
+        metrics: std::sync::Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
 
     // Seed a plant with a photo via valid import
@@ -590,6 +889,12 @@ async fn round_trip_export_import_export() {
         ai_base_url: String::new(),
         ai_model: String::new(),
         ai_rate_limiter: None,
+        metrics: std::sync::Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
 
     // Seed data via import
@@ -639,6 +944,12 @@ async fn round_trip_export_import_export() {
         ai_base_url: String::new(),
         ai_model: String::new(),
         ai_rate_limiter: None,
+        metrics: std::sync::Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp2.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
 
     let app = flowl::server::router(state2.clone());
@@ -676,3 +987,324 @@ async fn round_trip_export_import_export() {
     drop(tmp);
     drop(tmp2);
 }
+
+// --- Merge tests ---
+
+fn multipart_merge_request(zip_bytes: &[u8]) -> Request<Body> {
+    let mut request = multipart_import_request(zip_bytes);
+    *request.uri_mut() = "/api/data/import?mode=merge".parse().unwrap();
+    request
+}
+
+async fn merge(app: &axum::Router, zip_bytes: &[u8]) -> serde_json::Value {
+    let response = app
+        .clone()
+        .oneshot(multipart_merge_request(zip_bytes))
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = common::body_json(response).await;
+    assert_eq!(status, StatusCode::OK, "Merge failed: {body}");
+    body
+}
+
+fn merged_plant(report: &serde_json::Value) -> &serde_json::Value {
+    report["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["entity"] == "plant")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn merge_keeps_existing_data_and_remaps_ids() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+    for body in [r#"{"name":"Kitchen"}"#, r#"{"name":"Living Room"}"#] {
+        let response = app
+            .clone()
+            .oneshot(common::json_request("POST", "/api/locations", Some(body)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Fern","location_id":1}"#),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let report = merge(&app, &build_export_zip(&valid_export_json())).await;
+    assert_eq!(report["locations"]["skipped"], 1);
+    assert_eq!(report["locations"]["added"], 0);
+    assert_eq!(report["plants"]["added"], 1);
+    assert_eq!(report["care_events"]["added"], 1);
+    let plant = merged_plant(&report);
+    assert_eq!(plant["archive_id"], 1);
+    assert_eq!(plant["id"], 2);
+    assert_eq!(plant["outcome"], "added");
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/plants", None))
+        .await
+        .unwrap();
+    let plants = common::body_json(response).await;
+    assert_eq!(plants.as_array().unwrap().len(), 2);
+
+    // The archive location is matched by name, not by id
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/plants/2", None))
+        .await
+        .unwrap();
+    let monstera = common::body_json(response).await;
+    assert_eq!(monstera["name"], "Monstera");
+    assert_eq!(monstera["location_id"], 2);
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/2/care", None))
+        .await
+        .unwrap();
+    let events = common::body_json(response).await;
+    assert_eq!(events.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn merge_skips_duplicates_and_reports_conflicts() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+    let zip_bytes = build_export_zip(&valid_export_json());
+    merge(&app, &zip_bytes).await;
+
+    let report = merge(&app, &zip_bytes).await;
+    assert_eq!(report["plants"]["skipped"], 1);
+    assert_eq!(report["care_events"]["skipped"], 1);
+    assert_eq!(report["locations"]["skipped"], 1);
+    assert_eq!(merged_plant(&report)["outcome"], "skipped");
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "PUT",
+            "/api/plants/1",
+            Some(r#"{"watering_interval_days":10}"#),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let report = merge(&app, &zip_bytes).await;
+    assert_eq!(report["plants"]["conflicted"], 1);
+    let plant = merged_plant(&report);
+    assert_eq!(plant["outcome"], "conflicted");
+    assert_eq!(
+        plant["fields"],
+        serde_json::json!(["watering_interval_days"])
+    );
+
+    // The existing plant is kept as it is
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants", None))
+        .await
+        .unwrap();
+    let plants = common::body_json(response).await;
+    assert_eq!(plants.as_array().unwrap().len(), 1);
+    assert_eq!(plants[0]["watering_interval_days"], 10);
+}
+
+#[tokio::test]
+async fn merge_reports_renamed_plants_as_conflicts() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+    let zip_bytes = build_export_zip(&valid_export_json());
+    merge(&app, &zip_bytes).await;
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "PUT",
+            "/api/plants/1",
+            Some(r#"{"name":"Swiss Cheese Plant"}"#),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let report = merge(&app, &zip_bytes).await;
+    assert_eq!(report["plants"]["added"], 0);
+    assert_eq!(report["plants"]["conflicted"], 1);
+    assert_eq!(report["care_events"]["skipped"], 1);
+    let plant = merged_plant(&report);
+    assert_eq!(plant["id"], 1);
+    assert_eq!(plant["fields"], serde_json::json!(["name"]));
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants", None))
+        .await
+        .unwrap();
+    let plants = common::body_json(response).await;
+    assert_eq!(plants.as_array().unwrap().len(), 1);
+    assert_eq!(plants[0]["name"], "Swiss Cheese Plant");
+}
+
+#[tokio::test]
+async fn merge_copies_only_new_photos() {
+    let (app, dir) = common::test_app_with_uploads().await;
+    let json = valid_export_json().replace(
+        r#""photo_path": null, "location_id": 1"#,
+        r#""photo_path": "merged.jpg", "location_id": 1"#,
+    );
+    let zip_bytes = build_export_zip_with_photo(&json, "merged.jpg", b"photo bytes");
+
+    let report = merge(&app, &zip_bytes).await;
+    assert_eq!(report["photos"]["added"], 1);
+    assert_eq!(
+        std::fs::read(dir.path().join("merged.jpg")).unwrap(),
+        b"photo bytes"
+    );
+
+    let report = merge(&app, &zip_bytes).await;
+    assert_eq!(report["photos"]["added"], 0);
+    assert_eq!(report["photos"]["skipped"], 1);
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/1/photos", None))
+        .await
+        .unwrap();
+    let photos = common::body_json(response).await;
+    assert_eq!(photos.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn merged_entries_do_not_share_photo_files() {
+    let (app, dir) = common::test_app_with_uploads().await;
+    let json = valid_export_json().replace(
+        r#""photo_path": null, "location_id": 1"#,
+        r#""photo_path": "shared.jpg", "location_id": 1"#,
+    );
+    merge(
+        &app,
+        &build_export_zip_with_photo(&json, "shared.jpg", b"photo bytes"),
+    )
+    .await;
+
+    // Another plant with the same photo, such as a copy made before the backup
+    let json = json.replace("Monstera", "Pothos").replace(
+        r#""created_at": "2026-02-01T08:00:00""#,
+        r#""created_at": "2026-02-02T08:00:00""#,
+    );
+    let report = merge(
+        &app,
+        &build_export_zip_with_photo(&json, "shared.jpg", b"photo bytes"),
+    )
+    .await;
+    assert_eq!(report["plants"]["added"], 1);
+    assert_eq!(report["photos"]["added"], 1);
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/plants/2", None))
+        .await
+        .unwrap();
+    let pothos = common::body_json(response).await;
+    assert_ne!(pothos["photo_url"], "/uploads/shared.jpg");
+
+    for (method, uri) in [
+        ("DELETE", "/api/plants/2"),
+        ("DELETE", "/api/trash/plants/2"),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(common::json_request(method, uri, None))
+            .await
+            .unwrap();
+        assert!(response.status().is_success(), "{method} {uri}");
+    }
+
+    assert_eq!(
+        std::fs::read(dir.path().join("shared.jpg")).unwrap(),
+        b"photo bytes"
+    );
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/1", None))
+        .await
+        .unwrap();
+    let monstera = common::body_json(response).await;
+    assert_eq!(monstera["photo_url"], "/uploads/shared.jpg");
+}
+
+#[tokio::test]
+async fn merging_an_archive_twice_adds_nothing_the_second_time() {
+    let (app, dir) = common::test_app_with_uploads().await;
+    // A local file of the same name makes the gallery photo be copied under a new name
+    std::fs::write(dir.path().join("gallery.jpg"), b"local photo").unwrap();
+    let json = valid_export_json().replace(
+        r#""care_events": ["#,
+        r#""tags": [{"id": 1, "name": "green"}],
+            "plant_tags": [{"plant_id": 1, "tag_id": 1}],
+            "plant_photos": [{"id": 1, "plant_id": 1, "filename": "gallery.jpg",
+                "caption": null, "taken_at": null, "position": 0,
+                "created_at": "2026-02-01T08:00:00"}],
+            "care_events": ["#,
+    );
+    let zip_bytes = build_export_zip_with_photo(&json, "gallery.jpg", b"archive photo");
+    let jpgs = || {
+        std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|entry| entry.as_ref().unwrap().path().extension() == Some("jpg".as_ref()))
+            .count()
+    };
+
+    let report = merge(&app, &zip_bytes).await;
+    assert_eq!(report["plants"]["added"], 1);
+    assert_eq!(report["photos"]["conflicted"], 1);
+    assert_eq!(jpgs(), 2);
+
+    // Tags removed locally since stay removed: matched plants keep their own
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "PUT",
+            "/api/plants/1",
+            Some(r#"{"tags":[]}"#),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let report = merge(&app, &zip_bytes).await;
+    assert_eq!(merged_plant(&report)["outcome"], "skipped");
+    assert_eq!(report["photos"]["added"], 0);
+    assert_eq!(report["photos"]["conflicted"], 0);
+    assert_eq!(jpgs(), 2);
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/plants/1/photos", None))
+        .await
+        .unwrap();
+    let photos = common::body_json(response).await;
+    assert_eq!(photos.as_array().unwrap().len(), 1);
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/1", None))
+        .await
+        .unwrap();
+    let plant = common::body_json(response).await;
+    assert_eq!(plant["tags"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn import_rejects_unknown_mode() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+    let mut request = multipart_import_request(&build_export_zip(&valid_export_json()));
+    *request.uri_mut() = "/api/data/import?mode=append".parse().unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = common::body_json(response).await;
+    assert_eq!(body["code"], "IMPORT_INVALID_MODE");
+}
@@ -0,0 +1,218 @@
+mod common;
+
+use std::io::Read;
+
+use axum::Router;
+use axum::http::StatusCode;
+use tower::ServiceExt;
+
+async fn create_plant(app: &Router, name: &str) {
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants",
+            Some(&format!(r#"{{"name": "{name}"}}"#)),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+async fn create_backup(app: &Router) -> serde_json::Value {
+    let response = app
+        .clone()
+        .oneshot(common::json_request("POST", "/api/backups", None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    common::body_json(response).await
+}
+
+async fn plant_names(app: &Router) -> Vec<String> {
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/plants", None))
+        .await
+        .unwrap();
+    let plants = common::body_json(response).await;
+    plants
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn list_is_empty_without_backups() {
+    let (app, _dir) = common::test_app().await;
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/backups", None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let list = common::body_json(response).await;
+    assert!(list["schedule"].is_null());
+    assert!(list["next_run"].is_null());
+    assert_eq!(list["retention"]["daily"], 7);
+    assert_eq!(list["retention"]["weekly"], 4);
+    assert_eq!(list["retention"]["monthly"], 6);
+    assert_eq!(list["backups"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn created_backup_is_listed_and_stored() {
+    let (app, dir) = common::test_app().await;
+    create_plant(&app, "Fern").await;
+
+    let backup = create_backup(&app).await;
+    let name = backup["name"].as_str().unwrap();
+    assert!(name.starts_with("flowl-backup-"));
+    assert!(backup["created_at"].as_str().unwrap().ends_with('Z'));
+    assert!(backup["size_bytes"].as_u64().unwrap() > 0);
+    assert!(dir.path().join("backups").join(name).is_file());
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/backups", None))
+        .await
+        .unwrap();
+    let list = common::body_json(response).await;
+    let backups = list["backups"].as_array().unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0], backup);
+}
+
+#[tokio::test]
+async fn stored_backup_can_be_downloaded() {
+    let (app, _dir) = common::test_app().await;
+    create_plant(&app, "Fern").await;
+    let backup = create_backup(&app).await;
+    let name = backup["name"].as_str().unwrap();
+
+    let response = app
+        .oneshot(common::json_request(
+            "GET",
+            &format!("/api/backups/{name}"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/zip");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        format!("attachment; filename=\"{name}\"")
+    );
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body.len() as u64, backup["size_bytes"].as_u64().unwrap());
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(&body[..])).unwrap();
+    let mut json = String::new();
+    archive
+        .by_name("data.json")
+        .unwrap()
+        .read_to_string(&mut json)
+        .unwrap();
+    let data: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(data["plants"][0]["name"], "Fern");
+    assert_eq!(
+        data["format_version"],
+        flowl::api::backup_format::FORMAT_VERSION
+    );
+}
+
+#[tokio::test]
+async fn stored_backup_can_be_restored() {
+    let (app, _dir) = common::test_app().await;
+    create_plant(&app, "Fern").await;
+    let backup = create_backup(&app).await;
+    let name = backup["name"].as_str().unwrap();
+    create_plant(&app, "Cactus").await;
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "POST",
+            &format!("/api/backups/{name}/restore"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let result = common::body_json(response).await;
+    assert_eq!(result["plants"], 1);
+    assert_eq!(plant_names(&app).await, vec!["Fern"]);
+}
+
+#[tokio::test]
+async fn stored_backup_can_be_merged() {
+    let (app, _dir) = common::test_app().await;
+    create_plant(&app, "Fern").await;
+    let backup = create_backup(&app).await;
+    let name = backup["name"].as_str().unwrap();
+    create_plant(&app, "Cactus").await;
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "POST",
+            &format!("/api/backups/{name}/restore?mode=merge"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let report = common::body_json(response).await;
+    assert_eq!(report["plants"]["skipped"], 1);
+    let mut names = plant_names(&app).await;
+    names.sort();
+    assert_eq!(names, vec!["Cactus", "Fern"]);
+}
+
+#[tokio::test]
+async fn restore_rejects_unknown_mode() {
+    let (app, _dir) = common::test_app().await;
+    let backup = create_backup(&app).await;
+    let name = backup["name"].as_str().unwrap();
+
+    let response = app
+        .oneshot(common::json_request(
+            "POST",
+            &format!("/api/backups/{name}/restore?mode=append"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = common::body_json(response).await;
+    assert_eq!(body["code"], "IMPORT_INVALID_MODE");
+}
+
+#[tokio::test]
+async fn unknown_backups_are_not_found() {
+    let (app, dir) = common::test_app().await;
+    std::fs::write(dir.path().join("secret.zip"), b"not a backup").unwrap();
+
+    for (method, uri) in [
+        ("GET", "/api/backups/flowl-backup-20260101T000000Z.zip"),
+        ("GET", "/api/backups/secret.zip"),
+        ("GET", "/api/backups/..%2Fsecret.zip"),
+        (
+            "POST",
+            "/api/backups/flowl-backup-20260101T000000Z.zip/restore",
+        ),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(common::json_request(method, uri, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{method} {uri}");
+        let body = common::body_json(response).await;
+        assert_eq!(body["code"], "BACKUP_NOT_FOUND");
+    }
+}
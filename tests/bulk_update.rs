@@ -0,0 +1,143 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let resp = app
+        .clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+async fn seeded_app() -> (axum::Router, tempfile::TempDir) {
+    let (app, dir) = common::test_app().await;
+    send(
+        &app,
+        "POST",
+        "/api/locations",
+        Some(r#"{"name":"New Flat"}"#),
+    )
+    .await;
+    for name in ["Aloe", "Basil", "Cactus"] {
+        send(
+            &app,
+            "POST",
+            "/api/plants",
+            Some(&format!(r#"{{"name":"{name}","notes":"{name} notes"}}"#)),
+        )
+        .await;
+    }
+    (app, dir)
+}
+
+#[tokio::test]
+async fn bulk_update_applies_changes_to_all_plants() {
+    let (app, _dir) = seeded_app().await;
+
+    let (status, plants) = send(
+        &app,
+        "PATCH",
+        "/api/plants",
+        Some(r#"{"ids":[3,1,3],"changes":{"location_id":1,"watering_interval_days":10,"pet_safety":"toxic","tags":["moved"]}}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let plants = plants.as_array().unwrap();
+    assert_eq!(plants.len(), 2);
+    for plant in plants {
+        assert_eq!(plant["location_name"], "New Flat");
+        assert_eq!(plant["watering_interval_days"], 10);
+        assert_eq!(plant["pet_safety"], "toxic");
+        assert_eq!(plant["tags"], serde_json::json!(["moved"]));
+    }
+    // Fields not in the changes are kept per plant
+    assert_eq!(plants[0]["notes"], "Aloe notes");
+    assert_eq!(plants[1]["notes"], "Cactus notes");
+
+    let (_, basil) = send(&app, "GET", "/api/plants/2", None).await;
+    assert_eq!(basil["location_id"], serde_json::Value::Null);
+
+    let (_, history) = send(&app, "GET", "/api/plants/1/history", None).await;
+    let interval_change = history
+        .as_array()
+        .unwrap()
+        .iter()
+        .find_map(|entry| entry["changes"].get("watering_interval_days"))
+        .unwrap();
+    assert_eq!(interval_change["new"], 10);
+}
+
+#[tokio::test]
+async fn bulk_update_is_all_or_nothing() {
+    let (app, _dir) = seeded_app().await;
+
+    let (status, body) = send(
+        &app,
+        "PATCH",
+        "/api/plants",
+        Some(r#"{"ids":[1,2,99],"changes":{"watering_interval_days":10}}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "PLANT_NOT_FOUND");
+
+    let (_, plants) = send(&app, "GET", "/api/plants", None).await;
+    for plant in plants.as_array().unwrap() {
+        assert_eq!(plant["watering_interval_days"], 7);
+    }
+}
+
+#[tokio::test]
+async fn bulk_update_rejects_plant_specific_fields() {
+    let (app, _dir) = seeded_app().await;
+
+    for field in [
+        r#""name":"Fern""#,
+        r#""icon":"🌵""#,
+        r#""notes":"moved""#,
+        r#""parent_id":2"#,
+        r#""lifecycle_status":"archived""#,
+    ] {
+        let body = format!(r#"{{"ids":[1,3],"changes":{{"watering_interval_days":10,{field}}}}}"#);
+        let (status, json) = send(&app, "PATCH", "/api/plants", Some(&body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+        assert_eq!(json["code"], "INVALID_REQUEST_BODY", "{body}");
+    }
+
+    let (_, plants) = send(&app, "GET", "/api/plants", None).await;
+    for plant in plants.as_array().unwrap() {
+        assert_eq!(plant["watering_interval_days"], 7);
+        assert_eq!(plant["parent_id"], serde_json::Value::Null);
+    }
+}
+
+#[tokio::test]
+async fn bulk_update_validates_request() {
+    let (app, _dir) = seeded_app().await;
+
+    for (body, code) in [
+        (r#"{"ids":[],"changes":{}}"#, "PLANT_BULK_IDS_REQUIRED"),
+        (
+            r#"{"ids":[1],"changes":{"light_needs":"dark"}}"#,
+            "PLANT_INVALID_LIGHT_NEEDS",
+        ),
+        (
+            r#"{"ids":[1],"changes":{"tags":[" "]}}"#,
+            "TAG_NAME_REQUIRED",
+        ),
+    ] {
+        let (status, json) = send(&app, "PATCH", "/api/plants", Some(body)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+        assert_eq!(json["code"], code, "{body}");
+    }
+}
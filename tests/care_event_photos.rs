@@ -0,0 +1,163 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+fn photo_request(uri: &str) -> Request<Body> {
+    let mut body_bytes = Vec::new();
+    body_bytes.extend_from_slice(
+        b"------testboundary\r\n\
+          Content-Disposition: form-data; name=\"file\"; filename=\"test.jpg\"\r\n\
+          Content-Type: image/jpeg\r\n\r\n",
+    );
+    body_bytes.extend_from_slice(&[0xFF, 0xD8, 0xFF, 0xE0]);
+    body_bytes.extend_from_slice(b"\r\n------testboundary--\r\n");
+
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(
+            "content-type",
+            "multipart/form-data; boundary=----testboundary",
+        )
+        .body(Body::from(body_bytes))
+        .unwrap()
+}
+
+async fn send(app: &axum::Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+    let resp = app.clone().oneshot(request).await.unwrap();
+    let status = resp.status();
+    if status == StatusCode::NO_CONTENT {
+        return (status, serde_json::Value::Null);
+    }
+    (status, body_json(resp).await)
+}
+
+/// A plant with one care event, and the event after uploading `photos` photos.
+async fn setup(photos: usize) -> (axum::Router, tempfile::TempDir, serde_json::Value) {
+    let (app, dir) = common::test_app_with_uploads().await;
+    send(
+        &app,
+        json_request("POST", "/api/plants", Some(r#"{"name":"Fern"}"#)),
+    )
+    .await;
+    let (_, mut event) = send(
+        &app,
+        json_request(
+            "POST",
+            "/api/plants/1/care",
+            Some(r#"{"event_type":"custom","notes":"Spider mites"}"#),
+        ),
+    )
+    .await;
+    for _ in 0..photos {
+        let (status, updated) = send(&app, photo_request("/api/plants/1/care/1/photo")).await;
+        assert_eq!(status, StatusCode::OK);
+        event = updated;
+    }
+    (app, dir, event)
+}
+
+fn on_disk(dir: &tempfile::TempDir, url: &serde_json::Value) -> bool {
+    let filename = url.as_str().unwrap().strip_prefix("/uploads/").unwrap();
+    dir.path().join(filename).exists()
+}
+
+#[tokio::test]
+async fn uploads_append_photos_in_order() {
+    let (app, dir, event) = setup(3).await;
+
+    let photos = event["photos"].as_array().unwrap();
+    assert_eq!(photos.len(), 3);
+    assert_eq!(event["photo_url"], photos[0]["url"]);
+    for photo in photos {
+        assert!(on_disk(&dir, &photo["url"]));
+    }
+
+    let (_, events) = send(&app, json_request("GET", "/api/plants/1/care", None)).await;
+    assert_eq!(events[0]["photos"], event["photos"]);
+
+    let (_, page) = send(&app, json_request("GET", "/api/care?has_photo=true", None)).await;
+    assert_eq!(page["events"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn events_without_photos_have_an_empty_list() {
+    let (_app, _dir, event) = setup(0).await;
+    assert!(event["photo_url"].is_null());
+    assert_eq!(event["photos"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn photo_limit_per_event() {
+    let (app, _dir, _) = setup(10).await;
+    let (status, body) = send(&app, photo_request("/api/plants/1/care/1/photo")).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "CARE_EVENT_TOO_MANY_PHOTOS");
+}
+
+#[tokio::test]
+async fn delete_and_reorder_individual_photos() {
+    let (app, dir, event) = setup(3).await;
+    let photos = event["photos"].as_array().unwrap();
+    let ids: Vec<i64> = photos.iter().map(|p| p["id"].as_i64().unwrap()).collect();
+
+    let order = format!(r#"{{"ids":[{},{},{}]}}"#, ids[2], ids[0], ids[1]);
+    let (status, reordered) = send(
+        &app,
+        json_request("PUT", "/api/plants/1/care/1/photos", Some(&order)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(reordered["photos"][0]["id"], ids[2]);
+    assert_eq!(reordered["photo_url"], photos[2]["url"]);
+
+    let (status, body) = send(
+        &app,
+        json_request("PUT", "/api/plants/1/care/1/photos", Some(r#"{"ids":[1]}"#)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "PHOTO_ORDER_MISMATCH");
+
+    // Deleting the first photo moves photo_url to the next one
+    let uri = format!("/api/plants/1/care/1/photos/{}", ids[2]);
+    let (status, _) = send(&app, json_request("DELETE", &uri, None)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!on_disk(&dir, &photos[2]["url"]));
+    let (status, body) = send(&app, json_request("DELETE", &uri, None)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "PHOTO_NOT_FOUND");
+
+    let (_, events) = send(&app, json_request("GET", "/api/plants/1/care", None)).await;
+    assert_eq!(events[0]["photo_url"], photos[0]["url"]);
+    assert_eq!(events[0]["photos"].as_array().unwrap().len(), 2);
+
+    // Deleting through the single-photo endpoint removes all of them
+    let (status, _) = send(
+        &app,
+        json_request("DELETE", "/api/plants/1/care/1/photo", None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!on_disk(&dir, &photos[0]["url"]));
+    assert!(!on_disk(&dir, &photos[1]["url"]));
+    let (_, events) = send(&app, json_request("GET", "/api/plants/1/care", None)).await;
+    assert!(events[0]["photo_url"].is_null());
+    assert_eq!(events[0]["photos"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn purging_an_event_deletes_all_its_photos() {
+    let (app, dir, event) = setup(2).await;
+
+    send(&app, json_request("DELETE", "/api/plants/1/care/1", None)).await;
+    let (status, _) = send(&app, json_request("DELETE", "/api/trash/care/1", None)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    for photo in event["photos"].as_array().unwrap() {
+        assert!(!on_disk(&dir, &photo["url"]));
+    }
+}
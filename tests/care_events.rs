@@ -367,14 +367,14 @@ async fn global_cursor_pagination() {
     let events = json["events"].as_array().unwrap();
     assert_eq!(events.len(), 2);
     assert_eq!(json["has_more"], true);
-    let last_id = events[1]["id"].as_i64().unwrap();
+    let cursor = json["next_cursor"].as_str().unwrap().to_string();
 
     // Get second page
     let resp = app
         .clone()
         .oneshot(json_request(
             "GET",
-            &format!("/api/care?limit=2&before={last_id}"),
+            &format!("/api/care?limit=2&cursor={cursor}"),
             None,
         ))
         .await
@@ -383,6 +383,118 @@ async fn global_cursor_pagination() {
     let events = json["events"].as_array().unwrap();
     assert_eq!(events.len(), 2);
     assert_eq!(json["has_more"], false);
+    assert_eq!(json["next_cursor"], serde_json::Value::Null);
+}
+
+/// Log events whose ids do not follow `occurred_at`, as when entries are back-dated.
+async fn create_back_dated_events(app: &axum::Router, plant_id: i64) {
+    for day in [14, 10, 12, 11, 13, 12] {
+        app.clone()
+            .oneshot(json_request(
+                "POST",
+                &format!("/api/plants/{plant_id}/care"),
+                Some(&format!(
+                    r#"{{"event_type":"watered","occurred_at":"2026-02-{day}T08:00:00Z"}}"#
+                )),
+            ))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn global_cursor_pagination_with_back_dated_events() {
+    let (app, _dir) = app().await;
+    let id = create_plant(&app).await;
+    create_back_dated_events(&app, id).await;
+
+    let mut ids = Vec::new();
+    let mut uri = "/api/care?limit=2".to_string();
+    loop {
+        let resp = app
+            .clone()
+            .oneshot(json_request("GET", &uri, None))
+            .await
+            .unwrap();
+        let json = body_json(resp).await;
+        for event in json["events"].as_array().unwrap() {
+            ids.push(event["id"].as_i64().unwrap());
+        }
+        let Some(cursor) = json["next_cursor"].as_str() else {
+            break;
+        };
+        uri = format!("/api/care?limit=2&cursor={cursor}");
+    }
+    assert_eq!(ids, [1, 5, 6, 3, 4, 2]);
+}
+
+#[tokio::test]
+async fn plant_care_cursor_pagination() {
+    let (app, _dir) = app().await;
+    let id = create_plant(&app).await;
+    create_back_dated_events(&app, id).await;
+
+    let resp = app
+        .clone()
+        .oneshot(json_request(
+            "GET",
+            &format!("/api/plants/{id}/care?limit=4"),
+            None,
+        ))
+        .await
+        .unwrap();
+    let cursor = resp.headers()["x-next-cursor"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let json = body_json(resp).await;
+    let ids: Vec<i64> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [1, 5, 6, 3]);
+
+    let resp = app
+        .clone()
+        .oneshot(json_request(
+            "GET",
+            &format!("/api/plants/{id}/care?limit=4&cursor={cursor}"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert!(!resp.headers().contains_key("x-next-cursor"));
+    let json = body_json(resp).await;
+    assert_eq!(json.as_array().unwrap().len(), 2);
+
+    // Without a limit every event is listed
+    let resp = app
+        .oneshot(json_request("GET", &format!("/api/plants/{id}/care"), None))
+        .await
+        .unwrap();
+    assert_eq!(body_json(resp).await.as_array().unwrap().len(), 6);
+}
+
+#[tokio::test]
+async fn invalid_cursor_is_rejected() {
+    let (app, _dir) = app().await;
+    let id = create_plant(&app).await;
+
+    for uri in [
+        "/api/care?cursor=not-a-cursor".to_string(),
+        format!("/api/plants/{id}/care?cursor=%%%"),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(json_request("GET", &uri, None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY, "{uri}");
+        let json = body_json(resp).await;
+        assert_eq!(json["code"], "CARE_EVENT_INVALID_CURSOR", "{uri}");
+    }
 }
 
 #[tokio::test]
@@ -505,6 +617,104 @@ async fn global_no_type_filter_returns_all() {
 
 // --- Water auto-logs care event ---
 
+async fn global_event_ids(app: &axum::Router, query: &str) -> Vec<i64> {
+    let resp = app
+        .clone()
+        .oneshot(json_request("GET", &format!("/api/care?{query}"), None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "{query}");
+    let json = body_json(resp).await;
+    json["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["id"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn global_journal_filters() {
+    let (app, _dir) = app().await;
+    app.clone()
+        .oneshot(json_request(
+            "POST",
+            "/api/locations",
+            Some(r#"{"name":"Living Room"}"#),
+        ))
+        .await
+        .unwrap();
+    app.clone()
+        .oneshot(json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Monstera","location_id":1}"#),
+        ))
+        .await
+        .unwrap();
+    create_plant(&app).await;
+
+    // Event ids 1-3 on the living room plant, 4 on the other one
+    for (plant_id, event_type, occurred_at, notes) in [
+        (
+            1,
+            "fertilized",
+            "2026-03-05T09:00:00Z",
+            "Half-strength feed",
+        ),
+        (1, "fertilized", "2026-04-02T09:00:00Z", "Full feed"),
+        (1, "watered", "2026-03-31T23:30:00Z", "Soaked the roots"),
+        (2, "fertilized", "2026-03-10T09:00:00Z", "Slow-release feed"),
+    ] {
+        app.clone()
+            .oneshot(json_request(
+                "POST",
+                &format!("/api/plants/{plant_id}/care"),
+                Some(&format!(
+                    r#"{{"event_type":"{event_type}","occurred_at":"{occurred_at}","notes":"{notes}"}}"#
+                )),
+            ))
+            .await
+            .unwrap();
+    }
+
+    for (query, expected) in [
+        (
+            "type=fertilized&location_id=1&from=2026-03-01&to=2026-03-31",
+            vec![1],
+        ),
+        ("from=2026-03-31&to=2026-03-31", vec![3]),
+        ("plant_id=2", vec![4]),
+        ("plant_id=1&plant_id=2&type=watered", vec![3]),
+        ("q=feed&to=2026-03-31", vec![4, 1]),
+        ("q=ROOT", vec![3]),
+        ("has_photo=true", vec![]),
+        ("has_photo=false&plant_id=2", vec![4]),
+    ] {
+        assert_eq!(global_event_ids(&app, query).await, expected, "{query}");
+    }
+}
+
+#[tokio::test]
+async fn global_invalid_date_range() {
+    let (app, _dir) = app().await;
+
+    for query in [
+        "from=March",
+        "to=2026-02-30",
+        "from=2026-04-01&to=2026-03-01",
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(json_request("GET", &format!("/api/care?{query}"), None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY, "{query}");
+        let json = body_json(resp).await;
+        assert_eq!(json["code"], "CARE_EVENT_INVALID_DATE_RANGE", "{query}");
+    }
+}
+
 #[tokio::test]
 async fn water_auto_logs_care_event() {
     let (app, _dir) = app().await;
@@ -27,7 +27,7 @@ pub async fn test_pool() -> SqlitePool {
     pool
 }
 
-fn make_state(pool: SqlitePool, upload_dir: &std::path::Path) -> AppState {
+pub fn make_state(pool: SqlitePool, upload_dir: &std::path::Path) -> AppState {
     AppState {
         pool,
         image_store: flowl::images::ImageStore::new(upload_dir.to_path_buf()),
@@ -41,6 +41,12 @@ fn make_state(pool: SqlitePool, upload_dir: &std::path::Path) -> AppState {
         ai_base_url: String::new(),
         ai_model: String::new(),
         ai_rate_limiter: None,
+        metrics: std::sync::Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            upload_dir.join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     }
 }
 
@@ -0,0 +1,177 @@
+mod common;
+
+use axum::http::{StatusCode, header};
+use tower::ServiceExt;
+
+async fn send(app: &axum::Router, method: &str, uri: &str, body: Option<&str>) -> StatusCode {
+    app.clone()
+        .oneshot(common::json_request(method, uri, body))
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn get_csv(app: &axum::Router, uri: &str) -> (StatusCode, String) {
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", uri, None))
+        .await
+        .unwrap();
+    let status = response.status();
+    if status == StatusCode::OK {
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        assert!(
+            response.headers()[header::CONTENT_DISPOSITION]
+                .to_str()
+                .unwrap()
+                .starts_with("attachment; filename=\"flowl-")
+        );
+    }
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn setup() -> (axum::Router, tempfile::TempDir) {
+    let (app, dir) = common::test_app().await;
+    assert_eq!(
+        send(
+            &app,
+            "POST",
+            "/api/locations",
+            Some(r#"{"name":"Kitchen"}"#)
+        )
+        .await,
+        StatusCode::CREATED
+    );
+    for body in [
+        r#"{"name":"Fern","location_id":1,"notes":"Likes \"rain\", mist daily"}"#,
+        r#"{"name":"=Cactus","watering_interval_days":14}"#,
+    ] {
+        assert_eq!(
+            send(&app, "POST", "/api/plants", Some(body)).await,
+            StatusCode::CREATED
+        );
+    }
+    for (plant, event_type, occurred_at) in [
+        (1, "watered", "2026-03-01T09:00:00Z"),
+        (2, "fertilized", "2026-03-05T09:00:00Z"),
+        (1, "repotted", "2026-04-02T09:00:00Z"),
+    ] {
+        let body = format!(r#"{{"event_type":"{event_type}","occurred_at":"{occurred_at}"}}"#);
+        assert_eq!(
+            send(
+                &app,
+                "POST",
+                &format!("/api/plants/{plant}/care"),
+                Some(&body)
+            )
+            .await,
+            StatusCode::CREATED
+        );
+    }
+    (app, dir)
+}
+
+#[tokio::test]
+async fn plants_csv_lists_plants_with_location_and_status() {
+    let (app, _dir) = setup().await;
+
+    let (status, csv) = get_csv(&app, "/api/data/export/plants.csv").await;
+    assert_eq!(status, StatusCode::OK);
+    let lines: Vec<&str> = csv.split("\r\n").collect();
+    assert!(lines[0].starts_with("\u{feff}ID,Name,Species,Location,Watering status,"));
+    // Sorted by name; formulas are defused and quotes escaped
+    assert!(lines[1].starts_with("2,\"'=Cactus\",,,"));
+    assert!(lines[2].starts_with("1,Fern,,Kitchen,overdue,2026-03-01"));
+    assert!(lines[2].contains(",\"Likes \"\"rain\"\", mist daily\","));
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[3], "");
+
+    let (_, csv) = get_csv(&app, "/api/data/export/plants.csv?plant_id=2").await;
+    assert_eq!(csv.lines().count(), 2);
+}
+
+#[tokio::test]
+async fn care_csv_filters_by_date_plant_and_type() {
+    let (app, _dir) = setup().await;
+
+    let (status, csv) = get_csv(&app, "/api/data/export/care.csv").await;
+    assert_eq!(status, StatusCode::OK);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "\u{feff}ID,Date,Plant ID,Plant,Type,Notes");
+    assert_eq!(lines[1], "1,2026-03-01T09:00:00Z,1,Fern,watered,");
+    assert_eq!(lines.len(), 4);
+
+    let (_, csv) = get_csv(
+        &app,
+        "/api/data/export/care.csv?from=2026-03-02&to=2026-04-30&plant_id=1",
+    )
+    .await;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].ends_with(",Fern,repotted,"));
+
+    let (_, csv) = get_csv(&app, "/api/data/export/care.csv?type=fertilized").await;
+    assert!(
+        csv.lines()
+            .nth(1)
+            .unwrap()
+            .contains("\"'=Cactus\",fertilized")
+    );
+}
+
+#[tokio::test]
+async fn csv_headers_follow_the_locale() {
+    let (app, _dir) = setup().await;
+    assert_eq!(
+        send(&app, "PUT", "/api/settings", Some(r#"{"locale":"de"}"#)).await,
+        StatusCode::OK
+    );
+
+    let (_, csv) = get_csv(&app, "/api/data/export/care.csv").await;
+    assert!(csv.starts_with("\u{feff}ID,Datum,Pflanzen-ID,Pflanze,Typ,Notizen\r\n"));
+    let (_, csv) = get_csv(&app, "/api/data/export/plants.csv").await;
+    assert!(csv.starts_with("\u{feff}ID,Name,Art,Standort,Gießstatus,"));
+}
+
+#[tokio::test]
+async fn csv_skips_trashed_plants_and_events() {
+    let (app, _dir) = setup().await;
+    assert_eq!(
+        send(&app, "DELETE", "/api/plants/2", None).await,
+        StatusCode::NO_CONTENT
+    );
+    assert_eq!(
+        send(&app, "DELETE", "/api/plants/1/care/1", None).await,
+        StatusCode::NO_CONTENT
+    );
+
+    let (_, csv) = get_csv(&app, "/api/data/export/plants.csv").await;
+    assert_eq!(csv.lines().count(), 2);
+    let (_, csv) = get_csv(&app, "/api/data/export/care.csv").await;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].starts_with("3,"));
+}
+
+#[tokio::test]
+async fn care_csv_validates_filters() {
+    let (app, _dir) = setup().await;
+
+    let (status, body) = get_csv(
+        &app,
+        "/api/data/export/care.csv?from=2026-05-01&to=2026-04-01",
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body.contains("CARE_EVENT_INVALID_DATE_RANGE"));
+
+    let (status, body) = get_csv(&app, "/api/data/export/care.csv?type=dusting").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body.contains("CARE_EVENT_INVALID_TYPE"));
+}
@@ -0,0 +1,247 @@
+mod common;
+
+use std::fmt::Write;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::ServiceExt;
+
+const CSV: &str = "Name,Species,Location,Watering interval (days),Light needs,Last watered,Notes\r\n\
+Fern,Nephrolepis,Kitchen,5,low,2026-01-10,\"Mist, daily\"\r\n\
+Ivy,,Hallway,,,,\r\n\
+\r\n\
+Cactus,,Kitchen,30,direct,,\r\n";
+
+async fn import(
+    app: &axum::Router,
+    query: &str,
+    csv: &str,
+    mapping: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let boundary = "----TestBoundary";
+    let mut body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"plants.csv\"\r\n\
+         Content-Type: text/csv\r\n\r\n{csv}\r\n"
+    );
+    if let Some(mapping) = mapping {
+        write!(
+            body,
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"mapping\"\r\n\r\n{mapping}\r\n"
+        )
+        .unwrap();
+    }
+    write!(body, "--{boundary}--\r\n").unwrap();
+
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/data/import/plants.csv{query}"))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={boundary}"),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    (status, common::body_json(response).await)
+}
+
+async fn get_json(app: &axum::Router, uri: &str) -> serde_json::Value {
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", uri, None))
+        .await
+        .unwrap();
+    common::body_json(response).await
+}
+
+async fn setup() -> (axum::Router, tempfile::TempDir) {
+    let (app, dir) = common::test_app().await;
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/locations",
+            Some(r#"{"name":"Hallway"}"#),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    (app, dir)
+}
+
+#[tokio::test]
+async fn dry_run_reports_without_creating() {
+    let (app, _dir) = setup().await;
+
+    let (status, report) = import(&app, "?dry_run=true", CSV, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["rows"], 3);
+    assert_eq!(report["valid_rows"], 3);
+    assert_eq!(
+        report["columns"]["watering_interval_days"],
+        "Watering interval (days)"
+    );
+    assert_eq!(report["new_locations"], serde_json::json!(["Kitchen"]));
+    assert_eq!(report["created_plant_ids"], serde_json::json!([]));
+    assert_eq!(report["errors"], serde_json::json!([]));
+
+    let plants = get_json(&app, "/api/plants").await;
+    assert_eq!(plants.as_array().unwrap().len(), 0);
+    let locations = get_json(&app, "/api/locations").await;
+    assert_eq!(locations.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn commit_creates_plants_locations_and_waterings() {
+    let (app, _dir) = setup().await;
+
+    let (status, report) = import(&app, "", CSV, None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(report["created_plant_ids"], serde_json::json!([1, 2, 3]));
+
+    let fern = get_json(&app, "/api/plants/1").await;
+    assert_eq!(fern["name"], "Fern");
+    assert_eq!(fern["species"], "Nephrolepis");
+    assert_eq!(fern["location_name"], "Kitchen");
+    assert_eq!(fern["watering_interval_days"], 5);
+    assert_eq!(fern["light_needs"], "low");
+    assert_eq!(fern["notes"], "Mist, daily");
+    assert_eq!(fern["last_watered"], "2026-01-10T12:00:00Z");
+    let ivy = get_json(&app, "/api/plants/2").await;
+    assert_eq!(ivy["location_id"], 1);
+    assert_eq!(ivy["watering_interval_days"], 7);
+    assert_eq!(ivy["light_needs"], "indirect");
+    assert!(ivy["last_watered"].is_null());
+    let cactus = get_json(&app, "/api/plants/3").await;
+    assert_eq!(cactus["location_id"], fern["location_id"]);
+
+    let journal = get_json(&app, "/api/plants/1/care").await;
+    assert_eq!(journal.as_array().unwrap().len(), 1);
+    assert_eq!(journal[0]["event_type"], "watered");
+    let locations = get_json(&app, "/api/locations").await;
+    assert_eq!(locations.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn invalid_rows_are_reported_per_field() {
+    let (app, _dir) = setup().await;
+    let csv = "name;light_needs;watering_interval_days\nFern;shade;7\n;low;0\nIvy;low;3\n";
+
+    let (status, report) = import(&app, "?dry_run=true", csv, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["valid_rows"], 1);
+    let errors: Vec<(i64, &str, &str)> = report["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["row"].as_i64().unwrap(),
+                e["field"].as_str().unwrap(),
+                e["code"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            (2, "light_needs", "PLANT_INVALID_LIGHT_NEEDS"),
+            (3, "name", "PLANT_NAME_REQUIRED"),
+            (
+                3,
+                "watering_interval_days",
+                "PLANT_INVALID_WATERING_INTERVAL"
+            ),
+        ]
+    );
+    assert!(report["errors"][0]["message"].as_str().is_some());
+
+    // Committing needs every row to be valid, unless invalid rows are skipped
+    let (status, body) = import(&app, "", csv, None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "IMPORT_CSV_INVALID_ROWS");
+    assert_eq!(
+        get_json(&app, "/api/plants")
+            .await
+            .as_array()
+            .unwrap()
+            .len(),
+        0
+    );
+
+    let (status, report) = import(&app, "?skip_invalid=true", csv, None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(report["created_plant_ids"], serde_json::json!([1]));
+    assert_eq!(report["errors"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn mapping_selects_columns() {
+    let (app, _dir) = setup().await;
+    let csv = "Plant,Name,Room\nFern,Nephrolepis,Hallway\n";
+
+    let (status, report) = import(
+        &app,
+        "",
+        csv,
+        Some(r#"{"name":"Plant","species":"Name","location":"Room"}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(report["new_locations"], serde_json::json!([]));
+    let fern = get_json(&app, "/api/plants/1").await;
+    assert_eq!(fern["name"], "Fern");
+    assert_eq!(fern["species"], "Nephrolepis");
+    assert_eq!(fern["location_id"], 1);
+
+    let (status, body) = import(&app, "", csv, Some(r#"{"name":"Title"}"#)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "IMPORT_CSV_INVALID_MAPPING");
+}
+
+#[tokio::test]
+async fn unusable_files_are_rejected() {
+    let (app, _dir) = setup().await;
+
+    let (status, body) = import(&app, "", "species\nNephrolepis\n", None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "IMPORT_CSV_NAME_COLUMN_REQUIRED");
+
+    let (status, body) = import(&app, "", "name\n\"Fern\n", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "IMPORT_CSV_INVALID");
+
+    let many = format!("name\n{}", "Fern\n".repeat(1001));
+    let (status, body) = import(&app, "", &many, None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "IMPORT_CSV_TOO_MANY_ROWS");
+}
+
+#[tokio::test]
+async fn exported_csv_imports_again() {
+    let (app, _dir) = setup().await;
+    let (status, _) = import(&app, "", CSV, None).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "GET",
+            "/api/data/export/plants.csv",
+            None,
+        ))
+        .await
+        .unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let exported = String::from_utf8(bytes.to_vec()).unwrap();
+
+    let (status, report) = import(&app, "?dry_run=true", &exported, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["valid_rows"], 3);
+    assert_eq!(report["columns"]["location"], "Location");
+    assert_eq!(report["columns"]["last_watered"], "Last watered");
+}
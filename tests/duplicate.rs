@@ -0,0 +1,188 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let resp = app
+        .clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+fn photo_request(uri: &str, data: &[u8]) -> Request<Body> {
+    let mut body_bytes = Vec::new();
+    body_bytes.extend_from_slice(
+        b"------testboundary\r\n\
+          Content-Disposition: form-data; name=\"file\"; filename=\"test.jpg\"\r\n\
+          Content-Type: image/jpeg\r\n\r\n",
+    );
+    body_bytes.extend_from_slice(data);
+    body_bytes.extend_from_slice(b"\r\n------testboundary--\r\n");
+
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(
+            "content-type",
+            "multipart/form-data; boundary=----testboundary",
+        )
+        .body(Body::from(body_bytes))
+        .unwrap()
+}
+
+async fn create_source(app: &axum::Router) {
+    let (status, _) = send(app, "POST", "/api/locations", Some(r#"{"name":"Kitchen"}"#)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(
+        app,
+        "POST",
+        "/api/plants",
+        Some(
+            r#"{"name":"Basil","species":"Ocimum basilicum","location_id":1,
+                "watering_interval_days":3,"light_needs":"direct","notes":"Pinch tops",
+                "tags":["herbs"]}"#,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn duplicate_copies_profile_without_journal() {
+    let (app, _dir) = common::test_app().await;
+    create_source(&app).await;
+    send(
+        &app,
+        "POST",
+        "/api/plants/1/care",
+        Some(r#"{"event_type":"watered"}"#),
+    )
+    .await;
+
+    let (status, copies) = send(&app, "POST", "/api/plants/1/duplicate", Some("{}")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let copies = copies.as_array().unwrap();
+    assert_eq!(copies.len(), 1);
+    let copy = &copies[0];
+    assert_eq!(copy["id"], 2);
+    assert_eq!(copy["name"], "Basil");
+    assert_eq!(copy["species"], "Ocimum basilicum");
+    assert_eq!(copy["location_id"], 1);
+    assert_eq!(copy["watering_interval_days"], 3);
+    assert_eq!(copy["light_needs"], "direct");
+    assert_eq!(copy["notes"], "Pinch tops");
+    assert_eq!(copy["tags"], serde_json::json!(["herbs"]));
+    assert!(copy["parent_id"].is_null());
+    assert!(copy["last_watered"].is_null());
+
+    let (_, events) = send(&app, "GET", "/api/plants/2/care", None).await;
+    assert!(events.as_array().unwrap().is_empty());
+
+    let (_, history) = send(&app, "GET", "/api/plants/2/history", None).await;
+    assert_eq!(history[0]["action"], "created");
+}
+
+#[tokio::test]
+async fn duplicate_numbers_multiple_copies() {
+    let (app, _dir) = common::test_app().await;
+    create_source(&app).await;
+
+    let (status, copies) = send(
+        &app,
+        "POST",
+        "/api/plants/1/duplicate",
+        Some(r#"{"count":3,"name":"Windowsill basil","numbered":true}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let names: Vec<&str> = copies
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "Windowsill basil 1",
+            "Windowsill basil 2",
+            "Windowsill basil 3"
+        ]
+    );
+
+    let (_, plants) = send(&app, "GET", "/api/plants", None).await;
+    assert_eq!(plants.as_array().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn duplicate_rejects_invalid_requests() {
+    let (app, _dir) = common::test_app().await;
+    create_source(&app).await;
+
+    for body in [r#"{"count":0}"#, r#"{"count":21}"#] {
+        let (status, json) = send(&app, "POST", "/api/plants/1/duplicate", Some(body)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(json["code"], "PLANT_INVALID_COPY_COUNT");
+    }
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/plants/1/duplicate",
+        Some(r#"{"name":"  "}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(json["code"], "PLANT_NAME_REQUIRED");
+
+    let (status, json) = send(&app, "POST", "/api/plants/99/duplicate", Some("{}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(json["code"], "PLANT_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn duplicate_copies_photo_on_request() {
+    let (app, dir) = common::test_app_with_uploads().await;
+    create_source(&app).await;
+    let resp = app
+        .clone()
+        .oneshot(photo_request(
+            "/api/plants/1/photo",
+            &[0xFF, 0xD8, 0xFF, 0xE0],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let source_url = body_json(resp).await["photo_url"].clone();
+
+    let (_, copies) = send(&app, "POST", "/api/plants/1/duplicate", Some("{}")).await;
+    assert!(copies[0]["photo_url"].is_null());
+
+    let (_, copies) = send(
+        &app,
+        "POST",
+        "/api/plants/1/duplicate",
+        Some(r#"{"copy_photo":true}"#),
+    )
+    .await;
+    let copy_url = copies[0]["photo_url"].as_str().unwrap();
+    assert_ne!(copy_url, source_url);
+    let filename = copy_url.strip_prefix("/uploads/").unwrap();
+    assert!(dir.path().join(filename).exists());
+
+    let uri = format!("/api/plants/{}/photos", copies[0]["id"]);
+    let (_, photos) = send(&app, "GET", &uri, None).await;
+    assert_eq!(photos.as_array().unwrap().len(), 1);
+    assert_eq!(photos[0]["url"], copy_url);
+}
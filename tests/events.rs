@@ -0,0 +1,221 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use tokio_stream::StreamExt;
+use tower::ServiceExt;
+
+type DataStream = axum::body::BodyDataStream;
+
+async fn subscribe(app: &axum::Router) -> DataStream {
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/api/events", None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"].to_str().unwrap(),
+        "text/event-stream"
+    );
+    response.into_body().into_data_stream()
+}
+
+/// Read the next SSE frame and return its `(event, data)` fields.
+async fn next_event(stream: &mut DataStream) -> (String, serde_json::Value) {
+    let chunk = tokio::time::timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("timed out waiting for event")
+        .unwrap()
+        .unwrap();
+    let text = String::from_utf8(chunk.to_vec()).unwrap();
+
+    let mut event = String::new();
+    let mut data = serde_json::Value::Null;
+    for line in text.lines() {
+        if let Some(name) = line.strip_prefix("event: ") {
+            event = name.to_string();
+        } else if let Some(json) = line.strip_prefix("data: ") {
+            data = serde_json::from_str(json).unwrap();
+        }
+    }
+    (event, data)
+}
+
+#[tokio::test]
+async fn plant_lifecycle_is_broadcast() {
+    let (app, _dir) = common::test_app().await;
+    let mut stream = subscribe(&app).await;
+
+    app.clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Fern"}"#),
+        ))
+        .await
+        .unwrap();
+    let (event, data) = next_event(&mut stream).await;
+    assert_eq!(event, "plant_created");
+    assert_eq!(
+        data,
+        serde_json::json!({"type": "plant_created", "plant_id": 1})
+    );
+
+    app.clone()
+        .oneshot(common::json_request(
+            "PUT",
+            "/api/plants/1",
+            Some(r#"{"notes":"Bright spot"}"#),
+        ))
+        .await
+        .unwrap();
+    let (event, _) = next_event(&mut stream).await;
+    assert_eq!(event, "plant_updated");
+
+    app.clone()
+        .oneshot(common::json_request("DELETE", "/api/plants/1", None))
+        .await
+        .unwrap();
+    let (event, data) = next_event(&mut stream).await;
+    assert_eq!(event, "plant_deleted");
+    assert_eq!(data["plant_id"], 1);
+}
+
+#[tokio::test]
+async fn watering_broadcasts_care_event_and_status_transition() {
+    let (app, _dir) = common::test_app().await;
+    app.clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Fern"}"#),
+        ))
+        .await
+        .unwrap();
+
+    let mut stream = subscribe(&app).await;
+
+    app.clone()
+        .oneshot(common::json_request("POST", "/api/plants/1/water", None))
+        .await
+        .unwrap();
+
+    let (event, data) = next_event(&mut stream).await;
+    assert_eq!(event, "care_event_created");
+    assert_eq!(data["plant_id"], 1);
+    assert_eq!(data["event_id"], 1);
+
+    let (event, data) = next_event(&mut stream).await;
+    assert_eq!(event, "watering_status_changed");
+    assert_eq!(data["status"], "ok");
+
+    app.clone()
+        .oneshot(common::json_request("DELETE", "/api/plants/1/care/1", None))
+        .await
+        .unwrap();
+
+    let (event, _) = next_event(&mut stream).await;
+    assert_eq!(event, "care_event_deleted");
+    let (event, data) = next_event(&mut stream).await;
+    assert_eq!(event, "watering_status_changed");
+    assert_eq!(data["status"], "due");
+}
+
+#[tokio::test]
+async fn non_watering_care_event_has_no_status_transition() {
+    let (app, _dir) = common::test_app().await;
+    app.clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Fern"}"#),
+        ))
+        .await
+        .unwrap();
+
+    let mut stream = subscribe(&app).await;
+
+    app.clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants/1/care",
+            Some(r#"{"event_type":"fertilized"}"#),
+        ))
+        .await
+        .unwrap();
+    app.clone()
+        .oneshot(common::json_request("DELETE", "/api/plants/1", None))
+        .await
+        .unwrap();
+
+    let (event, _) = next_event(&mut stream).await;
+    assert_eq!(event, "care_event_created");
+    let (event, _) = next_event(&mut stream).await;
+    assert_eq!(event, "plant_deleted");
+}
+
+#[tokio::test]
+async fn restore_requests_resync() {
+    let (app, _dir) = common::test_app().await;
+    let response = app
+        .clone()
+        .oneshot(common::json_request("POST", "/api/backups", None))
+        .await
+        .unwrap();
+    let backup = common::body_json(response).await;
+    let name = backup["name"].as_str().unwrap();
+
+    let mut stream = subscribe(&app).await;
+
+    for mode in ["replace", "merge"] {
+        let response = app
+            .clone()
+            .oneshot(common::json_request(
+                "POST",
+                &format!("/api/backups/{name}/restore?mode={mode}"),
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let (event, data) = next_event(&mut stream).await;
+        assert_eq!(event, "resync");
+        assert_eq!(data, serde_json::json!({"type": "resync"}));
+    }
+}
+
+#[tokio::test]
+async fn restored_plant_is_broadcast_as_updated() {
+    let (app, _dir) = common::test_app().await;
+    app.clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Fern"}"#),
+        ))
+        .await
+        .unwrap();
+    app.clone()
+        .oneshot(common::json_request("DELETE", "/api/plants/1", None))
+        .await
+        .unwrap();
+
+    let mut stream = subscribe(&app).await;
+
+    let response = app
+        .clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/trash/plants/1/restore",
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let (event, data) = next_event(&mut stream).await;
+    assert_eq!(event, "plant_updated");
+    assert_eq!(data["plant_id"], 1);
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+use tower::ServiceExt;
+
+async fn get_json(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", uri, None))
+        .await
+        .unwrap();
+    let status = response.status();
+    (status, common::body_json(response).await)
+}
+
+async fn set_time_zone(app: &axum::Router, zone: &str) {
+    let body = format!(r#"{{"time_zone":"{zone}"}}"#);
+    let response = app
+        .clone()
+        .oneshot(common::json_request("PUT", "/api/settings", Some(&body)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+async fn setup() -> (axum::Router, tempfile::TempDir) {
+    let (app, dir) = common::test_app().await;
+    for name in ["Fern", "Cactus"] {
+        let body = format!(r#"{{"name":"{name}"}}"#);
+        app.clone()
+            .oneshot(common::json_request("POST", "/api/plants", Some(&body)))
+            .await
+            .unwrap();
+    }
+    for (plant, event_type, occurred_at) in [
+        (1, "watered", "2026-03-01T09:00:00Z"),
+        (1, "fertilized", "2026-03-01T10:00:00Z"),
+        (2, "watered", "2026-03-01T23:30:00Z"),
+        (2, "watered", "2026-03-03T12:00:00Z"),
+        (1, "watered", "2026-03-05T12:00:00Z"),
+    ] {
+        let body = format!(r#"{{"event_type":"{event_type}","occurred_at":"{occurred_at}"}}"#);
+        let response = app
+            .clone()
+            .oneshot(common::json_request(
+                "POST",
+                &format!("/api/plants/{plant}/care"),
+                Some(&body),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    (app, dir)
+}
+
+#[tokio::test]
+async fn heatmap_counts_events_per_day_and_type() {
+    let (app, _dir) = setup().await;
+
+    let (status, body) = get_json(&app, "/api/care/heatmap?from=2026-03-01&to=2026-03-04").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["from"], "2026-03-01");
+    assert_eq!(body["to"], "2026-03-04");
+    assert_eq!(body["time_zone"], "UTC");
+    assert_eq!(body["total"], 4);
+    assert_eq!(body["max"], 3);
+    let days = body["days"].as_array().unwrap();
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[0]["date"], "2026-03-01");
+    assert_eq!(days[0]["count"], 3);
+    assert_eq!(days[0]["types"]["watered"], 2);
+    assert_eq!(days[0]["types"]["fertilized"], 1);
+    assert_eq!(days[1]["date"], "2026-03-03");
+}
+
+#[tokio::test]
+async fn heatmap_filters_by_type_and_plant() {
+    let (app, _dir) = setup().await;
+
+    let (_, body) = get_json(
+        &app,
+        "/api/care/heatmap?from=2026-03-01&to=2026-03-31&type=watered",
+    )
+    .await;
+    assert_eq!(body["total"], 4);
+    assert!(body["days"][0]["types"]["fertilized"].is_null());
+
+    let (_, body) = get_json(
+        &app,
+        "/api/care/heatmap?from=2026-03-01&to=2026-03-31&plant_id=1&type=watered&type=fertilized",
+    )
+    .await;
+    assert_eq!(body["total"], 3);
+    let dates: Vec<&str> = body["days"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|day| day["date"].as_str().unwrap())
+        .collect();
+    assert_eq!(dates, ["2026-03-01", "2026-03-05"]);
+}
+
+#[tokio::test]
+async fn heatmap_buckets_days_in_the_configured_time_zone() {
+    let (app, _dir) = setup().await;
+    set_time_zone(&app, "Europe/Helsinki").await;
+
+    // 23:30 UTC on March 1st is already March 2nd in Helsinki
+    let (_, body) = get_json(&app, "/api/care/heatmap?from=2026-03-01&to=2026-03-02").await;
+    assert_eq!(body["time_zone"], "Europe/Helsinki");
+    assert_eq!(body["days"][0]["count"], 2);
+    assert_eq!(body["days"][1]["date"], "2026-03-02");
+    assert_eq!(body["days"][1]["count"], 1);
+    assert_eq!(body["total"], 3);
+}
+
+#[tokio::test]
+async fn heatmap_days_follow_daylight_saving_time() {
+    let (app, _dir) = setup().await;
+    set_time_zone(&app, "Europe/Berlin").await;
+    // 23:30 in winter, but 00:30 the next day in summer
+    for occurred_at in ["2026-01-15T22:30:00Z", "2026-07-01T22:30:00Z"] {
+        let body = format!(r#"{{"event_type":"watered","occurred_at":"{occurred_at}"}}"#);
+        let response = app
+            .clone()
+            .oneshot(common::json_request(
+                "POST",
+                "/api/plants/1/care",
+                Some(&body),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    let (_, body) = get_json(&app, "/api/care/heatmap?from=2026-01-01&to=2026-02-28").await;
+    assert_eq!(body["days"][0]["date"], "2026-01-15");
+    let (_, body) = get_json(&app, "/api/care/heatmap?from=2026-06-01&to=2026-07-31").await;
+    assert_eq!(body["days"][0]["date"], "2026-07-02");
+    assert_eq!(body["total"], 1);
+}
+
+#[tokio::test]
+async fn heatmap_skips_trashed_events_and_plants() {
+    let (app, _dir) = setup().await;
+    for uri in ["/api/plants/1/care/1", "/api/plants/2"] {
+        let response = app
+            .clone()
+            .oneshot(common::json_request("DELETE", uri, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    let (_, body) = get_json(&app, "/api/care/heatmap?from=2026-03-01&to=2026-03-31").await;
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["days"][0]["types"]["fertilized"], 1);
+    assert!(body["days"][0]["types"]["watered"].is_null());
+}
+
+#[tokio::test]
+async fn heatmap_validates_the_query() {
+    let (app, _dir) = setup().await;
+
+    // Without a range the heatmap covers the 365 days up to today
+    let (status, body) = get_json(&app, "/api/care/heatmap").await;
+    assert_eq!(status, StatusCode::OK);
+    let day = |field: &str| body[field].as_str().unwrap().parse::<NaiveDate>().unwrap();
+    assert_eq!((day("to") - day("from")).num_days(), 364);
+
+    let (status, body) = get_json(&app, "/api/care/heatmap?from=2026-03-05&to=2026-03-01").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "HEATMAP_INVALID_DATE_RANGE");
+
+    let (status, body) = get_json(&app, "/api/care/heatmap?type=dusting").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "CARE_EVENT_INVALID_TYPE");
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use axum::http::StatusCode;
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> serde_json::Value {
+    let response = app
+        .clone()
+        .oneshot(common::json_request(method, uri, body))
+        .await
+        .unwrap();
+    assert!(
+        response.status().is_success(),
+        "{method} {uri} returned {}",
+        response.status()
+    );
+    if response.status() == StatusCode::NO_CONTENT {
+        return serde_json::Value::Null;
+    }
+    common::body_json(response).await
+}
+
+#[tokio::test]
+async fn history_records_field_level_changes() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"watering_interval_days":3,"notes":"Thirsty"}"#),
+    )
+    .await;
+    // An update without changes is not recorded
+    send(&app, "PUT", "/api/plants/1", Some(r#"{"notes":"Thirsty"}"#)).await;
+    send(&app, "POST", "/api/plants/1/water", None).await;
+
+    let history = send(&app, "GET", "/api/plants/1/history", None).await;
+    let entries = history.as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+
+    // Newest first
+    assert_eq!(entries[0]["entity_type"], "care_event");
+    assert_eq!(entries[0]["action"], "created");
+    assert_eq!(entries[0]["changes"]["event_type"]["new"], "watered");
+
+    assert_eq!(entries[1]["entity_type"], "plant");
+    assert_eq!(entries[1]["action"], "updated");
+    assert_eq!(
+        entries[1]["changes"],
+        serde_json::json!({
+            "watering_interval_days": {"old": 7, "new": 3},
+            "notes": {"old": null, "new": "Thirsty"},
+        })
+    );
+
+    assert_eq!(entries[2]["action"], "created");
+    assert_eq!(entries[2]["changes"]["name"]["new"], "Fern");
+}
+
+#[tokio::test]
+async fn history_of_missing_plant_returns_404() {
+    let (app, _dir) = common::test_app().await;
+
+    let response = app
+        .oneshot(common::json_request("GET", "/api/plants/999/history", None))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = common::body_json(response).await;
+    assert_eq!(body["code"], "PLANT_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn revert_restores_plant_to_version() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    send(&app, "PUT", "/api/plants/1", Some(r#"{"notes":"First"}"#)).await;
+    send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"name":"Boston Fern","notes":"Second","watering_interval_days":2}"#),
+    )
+    .await;
+
+    let history = send(&app, "GET", "/api/plants/1/history", None).await;
+    let first_update = history[1]["id"].as_i64().unwrap();
+
+    let plant = send(
+        &app,
+        "POST",
+        &format!("/api/plants/1/history/{first_update}/revert"),
+        None,
+    )
+    .await;
+    assert_eq!(plant["name"], "Fern");
+    assert_eq!(plant["notes"], "First");
+    assert_eq!(plant["watering_interval_days"], 7);
+
+    let history = send(&app, "GET", "/api/plants/1/history", None).await;
+    assert_eq!(history[0]["action"], "reverted");
+    assert_eq!(history[0]["changes"]["name"]["old"], "Boston Fern");
+    assert_eq!(history[0]["changes"]["name"]["new"], "Fern");
+}
+
+#[tokio::test]
+async fn revert_clears_deleted_location() {
+    let (app, _dir) = common::test_app().await;
+    send(
+        &app,
+        "POST",
+        "/api/locations",
+        Some(r#"{"name":"Kitchen"}"#),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Fern","location_id":1}"#),
+    )
+    .await;
+    send(&app, "PUT", "/api/plants/1", Some(r#"{"notes":"Moved"}"#)).await;
+    send(&app, "DELETE", "/api/locations/1", None).await;
+
+    let history = send(&app, "GET", "/api/plants/1/history", None).await;
+    assert_eq!(
+        history[0]["changes"]["location_id"],
+        serde_json::json!({"old": 1, "new": null})
+    );
+    let created = history.as_array().unwrap().last().unwrap()["id"]
+        .as_i64()
+        .unwrap();
+
+    let plant = send(
+        &app,
+        "POST",
+        &format!("/api/plants/1/history/{created}/revert"),
+        None,
+    )
+    .await;
+    assert_eq!(plant["notes"], serde_json::Value::Null);
+    assert_eq!(plant["location_id"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn revert_unknown_entry_returns_404() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Ivy"}"#)).await;
+
+    // Entry 2 belongs to the other plant
+    let response = app
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants/1/history/2/revert",
+            None,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = common::body_json(response).await;
+    assert_eq!(body["code"], "HISTORY_ENTRY_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn care_event_deletion_is_recorded() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    send(
+        &app,
+        "POST",
+        "/api/plants/1/care",
+        Some(r#"{"event_type":"fertilized","notes":"Half dose"}"#),
+    )
+    .await;
+    send(&app, "DELETE", "/api/plants/1/care/1", None).await;
+
+    let history = send(&app, "GET", "/api/plants/1/history", None).await;
+    assert_eq!(history[0]["entity_type"], "care_event");
+    assert_eq!(history[0]["entity_id"], 1);
+    assert_eq!(history[0]["action"], "deleted");
+    assert_eq!(history[0]["changes"]["notes"]["old"], "Half dose");
+}
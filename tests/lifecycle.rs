@@ -0,0 +1,132 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let resp = app
+        .clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+fn names(list: &serde_json::Value) -> Vec<&str> {
+    list.as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn new_plant_is_active() {
+    let (app, _dir) = common::test_app().await;
+    let (status, plant) = send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(plant["lifecycle_status"], "active");
+    assert_eq!(plant["lifecycle_date"], serde_json::Value::Null);
+    assert_eq!(plant["lifecycle_reason"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn inactive_plants_are_hidden_from_default_list() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Monstera"}"#)).await;
+
+    let (status, plant) = send(
+        &app,
+        "PUT",
+        "/api/plants/2",
+        Some(r#"{"lifecycle_status":"gifted","lifecycle_date":"2026-03-01","lifecycle_reason":"To Anna"}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(plant["lifecycle_status"], "gifted");
+    assert_eq!(plant["lifecycle_date"], "2026-03-01");
+    assert_eq!(plant["lifecycle_reason"], "To Anna");
+
+    let (_, list) = send(&app, "GET", "/api/plants", None).await;
+    assert_eq!(names(&list), vec!["Fern"]);
+    let (_, list) = send(&app, "GET", "/api/plants?lifecycle=gifted", None).await;
+    assert_eq!(names(&list), vec!["Monstera"]);
+    let (_, list) = send(&app, "GET", "/api/plants?lifecycle=all", None).await;
+    assert_eq!(names(&list), vec!["Fern", "Monstera"]);
+
+    // The plant and its journal stay reachable directly
+    let (status, _) = send(&app, "GET", "/api/plants/2", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "GET", "/api/plants/2/care", None).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn lifecycle_date_defaults_to_today_and_reactivation_clears() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+
+    let (_, plant) = send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"lifecycle_status":"deceased","lifecycle_reason":"Root rot"}"#),
+    )
+    .await;
+    let today = chrono::Utc::now().date_naive().to_string();
+    assert_eq!(plant["lifecycle_date"], today.as_str());
+    assert_eq!(plant["lifecycle_reason"], "Root rot");
+
+    // Unrelated edits keep the lifecycle details
+    let (_, plant) = send(&app, "PUT", "/api/plants/1", Some(r#"{"notes":"RIP"}"#)).await;
+    assert_eq!(plant["lifecycle_reason"], "Root rot");
+
+    let (_, plant) = send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"lifecycle_status":"active"}"#),
+    )
+    .await;
+    assert_eq!(plant["lifecycle_status"], "active");
+    assert_eq!(plant["lifecycle_date"], serde_json::Value::Null);
+    assert_eq!(plant["lifecycle_reason"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn invalid_lifecycle_values_are_rejected() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+
+    let (status, body) = send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"lifecycle_status":"sold"}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "PLANT_INVALID_LIFECYCLE_STATUS");
+
+    let (status, body) = send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"lifecycle_status":"archived","lifecycle_date":"yesterday"}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "PLANT_INVALID_LIFECYCLE_DATE");
+
+    let (status, body) = send(&app, "GET", "/api/plants?lifecycle=sold", None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "PLANT_INVALID_LIFECYCLE_STATUS");
+}
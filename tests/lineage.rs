@@ -0,0 +1,158 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let resp = app
+        .clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+fn ids(list: &serde_json::Value) -> Vec<i64> {
+    list.as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn propagated_event_creates_child_from_parent_profile() {
+    let (app, _dir) = common::test_app().await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Pothos","species":"Epipremnum aureum","watering_interval_days":5,"light_needs":"low","pet_safety":"toxic","notes":"Mother plant"}"#),
+    )
+    .await;
+
+    let (status, event) = send(
+        &app,
+        "POST",
+        "/api/plants/1/care",
+        Some(r#"{"event_type":"propagated","child_name":"Pothos cutting"}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(event["event_type"], "propagated");
+    assert_eq!(event["child_plant_id"], 2);
+
+    let (status, child) = send(&app, "GET", "/api/plants/2", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(child["name"], "Pothos cutting");
+    assert_eq!(child["species"], "Epipremnum aureum");
+    assert_eq!(child["watering_interval_days"], 5);
+    assert_eq!(child["light_needs"], "low");
+    assert_eq!(child["pet_safety"], "toxic");
+    assert_eq!(child["notes"], serde_json::Value::Null);
+    assert_eq!(child["parent_id"], 1);
+
+    // Without a name the child is named after its parent
+    let (_, event) = send(
+        &app,
+        "POST",
+        "/api/plants/1/care",
+        Some(r#"{"event_type":"propagated"}"#),
+    )
+    .await;
+    let (_, child) = send(&app, "GET", "/api/plants/3", None).await;
+    assert_eq!(event["child_plant_id"], 3);
+    assert_eq!(child["name"], "Pothos");
+}
+
+#[tokio::test]
+async fn lineage_lists_ancestors_and_descendants() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Mother"}"#)).await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Daughter","parent_id":1}"#),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/api/plants/2/care",
+        Some(r#"{"event_type":"propagated","child_name":"Granddaughter"}"#),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Aunt","parent_id":1}"#),
+    )
+    .await;
+
+    let (status, lineage) = send(&app, "GET", "/api/plants/2/lineage", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&lineage["ancestors"]), vec![1]);
+    assert_eq!(lineage["ancestors"][0]["depth"], 1);
+    assert_eq!(ids(&lineage["descendants"]), vec![3]);
+
+    let (_, lineage) = send(&app, "GET", "/api/plants/1/lineage", None).await;
+    assert_eq!(lineage["ancestors"], serde_json::json!([]));
+    assert_eq!(ids(&lineage["descendants"]), vec![4, 2, 3]);
+    assert_eq!(lineage["descendants"][2]["depth"], 2);
+    assert_eq!(lineage["descendants"][2]["parent_id"], 2);
+
+    let (_, lineage) = send(&app, "GET", "/api/plants/3/lineage", None).await;
+    assert_eq!(ids(&lineage["ancestors"]), vec![2, 1]);
+
+    let (status, _) = send(&app, "GET", "/api/plants/99/lineage", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn invalid_parent_is_rejected() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Mother"}"#)).await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Daughter","parent_id":1}"#),
+    )
+    .await;
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Orphan","parent_id":99}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "PLANT_INVALID_PARENT");
+
+    // A plant cannot descend from itself or its own descendants
+    for parent in [1, 2] {
+        let (status, body) = send(
+            &app,
+            "PUT",
+            "/api/plants/1",
+            Some(&format!(r#"{{"parent_id":{parent}}}"#)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "PLANT_INVALID_PARENT");
+    }
+
+    let (status, plant) = send(&app, "PUT", "/api/plants/2", Some(r#"{"parent_id":null}"#)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(plant["parent_id"], serde_json::Value::Null);
+}
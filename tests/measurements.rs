@@ -0,0 +1,215 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let resp = app
+        .clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    if status == StatusCode::NO_CONTENT {
+        return (status, serde_json::Value::Null);
+    }
+    (status, body_json(resp).await)
+}
+
+async fn setup() -> axum::Router {
+    let (app, _dir) = common::test_app().await;
+    let (status, _) = send(&app, "POST", "/api/plants", Some(r#"{"name":"Monstera"}"#)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    app
+}
+
+#[tokio::test]
+async fn record_update_and_delete_measurements() {
+    let app = setup().await;
+
+    let (status, m) = send(
+        &app,
+        "POST",
+        "/api/plants/1/measurements",
+        Some(r#"{"metric":"height","value":42.5,"measured_at":"2026-03-01T12:00:00+02:00","notes":" Staked "}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(m["metric"], "height");
+    assert_eq!(m["value"], 42.5);
+    assert_eq!(m["unit"], "cm");
+    assert_eq!(m["notes"], "Staked");
+    assert_eq!(m["measured_at"], "2026-03-01T10:00:00Z");
+    assert!(m["care_event_id"].is_null());
+
+    let uri = format!("/api/plants/1/measurements/{}", m["id"]);
+    let (status, m) = send(&app, "PUT", &uri, Some(r#"{"value":44,"notes":null}"#)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(m["value"], 44.0);
+    assert!(m["notes"].is_null());
+    assert_eq!(m["measured_at"], "2026-03-01T10:00:00Z");
+
+    send(
+        &app,
+        "POST",
+        "/api/plants/1/measurements",
+        Some(r#"{"metric":"leaf_count","value":7,"measured_at":"2026-04-01T10:00:00Z"}"#),
+    )
+    .await;
+    let (_, list) = send(&app, "GET", "/api/plants/1/measurements", None).await;
+    assert_eq!(list.as_array().unwrap().len(), 2);
+    assert_eq!(list[0]["metric"], "leaf_count");
+    assert_eq!(list[0]["unit"], "leaves");
+    let (_, list) = send(
+        &app,
+        "GET",
+        "/api/plants/1/measurements?metric=height",
+        None,
+    )
+    .await;
+    assert_eq!(list.as_array().unwrap().len(), 1);
+
+    let (status, _) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, body) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "MEASUREMENT_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn measurement_attached_to_care_event() {
+    let app = setup().await;
+    send(
+        &app,
+        "POST",
+        "/api/plants/1/care",
+        Some(r#"{"event_type":"repotted","occurred_at":"2026-02-10T09:00:00Z"}"#),
+    )
+    .await;
+
+    // Without measured_at the reading takes the event's date
+    let (status, m) = send(
+        &app,
+        "POST",
+        "/api/plants/1/measurements",
+        Some(r#"{"metric":"pot_size","value":21,"care_event_id":1}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(m["care_event_id"], 1);
+    assert_eq!(m["measured_at"], "2026-02-10T09:00:00Z");
+
+    let uri = format!("/api/plants/1/measurements/{}", m["id"]);
+    let (_, m) = send(&app, "PUT", &uri, Some(r#"{"care_event_id":null}"#)).await;
+    assert!(m["care_event_id"].is_null());
+
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/plants/2/measurements",
+        Some(r#"{"metric":"height","value":10,"care_event_id":1}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "MEASUREMENT_INVALID_CARE_EVENT");
+}
+
+#[tokio::test]
+async fn measurement_validation() {
+    let app = setup().await;
+
+    for (body, code) in [
+        (r#"{"value":1}"#, "MEASUREMENT_METRIC_REQUIRED"),
+        (r#"{"metric":" ","value":1}"#, "MEASUREMENT_METRIC_REQUIRED"),
+        (r#"{"metric":"height"}"#, "MEASUREMENT_VALUE_REQUIRED"),
+        (
+            r#"{"metric":"weight","value":1}"#,
+            "MEASUREMENT_INVALID_METRIC",
+        ),
+        (
+            r#"{"metric":"height","value":-3}"#,
+            "MEASUREMENT_INVALID_VALUE",
+        ),
+        (
+            r#"{"metric":"leaf_count","value":2.5}"#,
+            "MEASUREMENT_INVALID_VALUE",
+        ),
+        (
+            r#"{"metric":"height","value":3,"measured_at":"yesterday"}"#,
+            "MEASUREMENT_INVALID_DATE",
+        ),
+    ] {
+        let (status, json) = send(&app, "POST", "/api/plants/1/measurements", Some(body)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+        assert_eq!(json["code"], code);
+    }
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/plants/99/measurements",
+        Some(r#"{"metric":"height","value":3}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(json["code"], "PLANT_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn series_groups_readings_per_metric() {
+    let app = setup().await;
+    for (metric, value, date) in [
+        ("height", 30, "2026-01-01"),
+        ("leaf_count", 5, "2026-01-01"),
+        ("height", 38, "2026-03-01"),
+        ("height", 34, "2026-02-01"),
+    ] {
+        let body =
+            format!(r#"{{"metric":"{metric}","value":{value},"measured_at":"{date}T12:00:00Z"}}"#);
+        send(&app, "POST", "/api/plants/1/measurements", Some(&body)).await;
+    }
+
+    let (status, series) = send(&app, "GET", "/api/plants/1/measurements/series", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(series.as_array().unwrap().len(), 2);
+    assert_eq!(series[0]["metric"], "height");
+    assert_eq!(series[0]["unit"], "cm");
+    let values: Vec<f64> = series[0]["points"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["value"].as_f64().unwrap())
+        .collect();
+    assert_eq!(values, [30.0, 34.0, 38.0]);
+    assert_eq!(series[1]["metric"], "leaf_count");
+
+    let (_, series) = send(
+        &app,
+        "GET",
+        "/api/plants/1/measurements/series?metric=height&from=2026-02-01&to=2026-02-28",
+        None,
+    )
+    .await;
+    assert_eq!(series[0]["points"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        series[0]["points"][0]["measured_at"],
+        "2026-02-01T12:00:00Z"
+    );
+
+    let (status, json) = send(
+        &app,
+        "GET",
+        "/api/plants/1/measurements/series?from=2026-03-01&to=2026-02-01",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(json["code"], "MEASUREMENT_INVALID_DATE_RANGE");
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use axum::http::StatusCode;
+use tower::ServiceExt;
+
+async fn scrape(app: &axum::Router) -> String {
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", "/metrics", None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain")
+    );
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn metrics_reports_plants_by_status_and_location() {
+    let (app, _dir) = common::test_app().await;
+
+    app.clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/locations",
+            Some(r#"{"name":"Living Room"}"#),
+        ))
+        .await
+        .unwrap();
+    app.clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Fern","location_id":1}"#),
+        ))
+        .await
+        .unwrap();
+    app.clone()
+        .oneshot(common::json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Cactus"}"#),
+        ))
+        .await
+        .unwrap();
+    app.clone()
+        .oneshot(common::json_request("POST", "/api/plants/1/water", None))
+        .await
+        .unwrap();
+
+    let body = scrape(&app).await;
+
+    assert!(body.contains("flowl_plants{watering_status=\"ok\",location=\"Living Room\"} 1\n"));
+    assert!(body.contains("flowl_plants{watering_status=\"due\",location=\"\"} 1\n"));
+    assert!(body.contains("flowl_care_events{event_type=\"watered\"} 1\n"));
+}
+
+#[tokio::test]
+async fn metrics_reports_http_requests_by_route_template() {
+    let (app, _dir) = common::test_app().await;
+
+    app.clone()
+        .oneshot(common::json_request("GET", "/api/plants/42", None))
+        .await
+        .unwrap();
+
+    let body = scrape(&app).await;
+
+    assert!(body.contains(
+        "flowl_http_requests_total{method=\"GET\",route=\"/api/plants/{id}\",status=\"404\"} 1\n"
+    ));
+    assert!(body.contains(
+        "flowl_http_request_duration_seconds_count{method=\"GET\",route=\"/api/plants/{id}\"} 1\n"
+    ));
+}
+
+#[tokio::test]
+async fn metrics_reports_mqtt_and_upload_dir() {
+    let (app, dir) = common::test_app().await;
+    std::fs::write(dir.path().join("photo.jpg"), [0u8; 128]).unwrap();
+
+    let body = scrape(&app).await;
+
+    assert!(body.contains("flowl_mqtt_enabled 0\n"));
+    assert!(body.contains("flowl_mqtt_connected 0\n"));
+    assert!(body.contains("# TYPE flowl_mqtt_publish_failures_total counter\n"));
+    assert!(body.contains("flowl_upload_dir_bytes 128\n"));
+}
@@ -26,6 +26,12 @@ async fn test_app_mqtt_enabled(connected: bool) -> (Router, tempfile::TempDir) {
         ai_base_url: String::new(),
         ai_model: String::new(),
         ai_rate_limiter: None,
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     (flowl::server::router(state), tmp)
 }
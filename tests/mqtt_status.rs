@@ -26,6 +26,12 @@ async fn test_app_mqtt_enabled(connected: bool) -> (Router, tempfile::TempDir) {
         ai_base_url: String::new(),
         ai_model: String::new(),
         ai_rate_limiter: None,
+        metrics: Arc::new(flowl::metrics::Metrics::new()),
+        events: flowl::events::EventBus::new(),
+        backups: flowl::backups::BackupStore::new(
+            tmp.path().join("backups"),
+            flowl::backups::Retention::default(),
+        ),
     };
     (flowl::server::router(state), tmp)
 }
@@ -80,3 +86,13 @@ async fn mqtt_status_connected() {
     assert_eq!(json["broker"], "broker.local:1883");
     assert_eq!(json["topic_prefix"], "flowl");
 }
+
+#[tokio::test]
+async fn state_checker_skips_when_mqtt_disabled() {
+    let pool = common::test_pool().await;
+    let tmp = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let state = common::make_state(pool, tmp.path());
+
+    let handle = flowl::mqtt::spawn_state_checker(&state, None);
+    assert!(handle.is_none());
+}
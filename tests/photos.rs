@@ -94,7 +94,7 @@ async fn upload_to_nonexistent_plant() {
 }
 
 #[tokio::test]
-async fn upload_replaces_existing_photo() {
+async fn upload_makes_new_photo_the_cover() {
     let (app, dir) = common::test_app_with_uploads().await;
     let id = create_plant(&app).await;
 
@@ -135,8 +135,22 @@ async fn upload_replaces_existing_photo() {
             .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
     );
 
-    // Old file should be deleted
-    assert!(!dir.path().join(&first_filename).exists());
+    // The earlier photo stays in the gallery
+    assert!(dir.path().join(&first_filename).exists());
+    let resp = app
+        .oneshot(json_request(
+            "GET",
+            &format!("/api/plants/{id}/photos"),
+            None,
+        ))
+        .await
+        .unwrap();
+    let photos = body_json(resp).await;
+    assert_eq!(photos.as_array().unwrap().len(), 2);
+    assert_eq!(photos[0]["url"], format!("/uploads/{first_filename}"));
+    assert_eq!(photos[0]["is_cover"], false);
+    assert_eq!(photos[1]["url"], json["photo_url"]);
+    assert_eq!(photos[1]["is_cover"], true);
 }
 
 #[tokio::test]
@@ -240,7 +254,7 @@ async fn delete_photo_when_none_exists() {
 }
 
 #[tokio::test]
-async fn delete_plant_deletes_photo_file() {
+async fn purging_trashed_plant_deletes_photo_file() {
     let (app, dir) = common::test_app_with_uploads().await;
     let id = create_plant(&app).await;
 
@@ -271,6 +285,20 @@ async fn delete_plant_deletes_photo_file() {
         .unwrap();
     assert_eq!(resp.status(), StatusCode::NO_CONTENT);
 
+    // Trashed plants keep their photo until purged
+    assert!(dir.path().join(&filename).exists());
+
+    let resp = app
+        .clone()
+        .oneshot(json_request(
+            "DELETE",
+            &format!("/api/trash/plants/{id}"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
     // Photo file should be cleaned up
     assert!(!dir.path().join(&filename).exists());
 }
@@ -0,0 +1,155 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let resp = app
+        .clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+fn names(list: &serde_json::Value) -> Vec<&str> {
+    list.as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect()
+}
+
+fn days_ago(days: i64) -> String {
+    (chrono::Utc::now() - chrono::Duration::days(days))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Aloe is watered and ok, Basil overdue, Cactus never watered and due.
+async fn seeded_app() -> (axum::Router, tempfile::TempDir) {
+    let (app, dir) = common::test_app().await;
+    send(&app, "POST", "/api/locations", Some(r#"{"name":"Office"}"#)).await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Aloe","location_id":1,"watering_interval_days":7,"light_needs":"direct","difficulty":"easy","pet_safety":"safe"}"#),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Basil","watering_interval_days":3,"pet_safety":"safe"}"#),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Cactus","watering_interval_days":30,"difficulty":"easy"}"#),
+    )
+    .await;
+    for (id, days) in [(1, 1), (2, 10)] {
+        let body = format!(
+            r#"{{"event_type":"watered","occurred_at":"{}"}}"#,
+            days_ago(days)
+        );
+        send(&app, "POST", &format!("/api/plants/{id}/care"), Some(&body)).await;
+    }
+    (app, dir)
+}
+
+#[tokio::test]
+async fn list_filters_plants() {
+    let (app, _dir) = seeded_app().await;
+
+    for (query, expected) in [
+        ("location_id=1", vec!["Aloe"]),
+        ("status=ok", vec!["Aloe"]),
+        ("status=overdue", vec!["Basil"]),
+        ("status=due", vec!["Cactus"]),
+        ("light_needs=direct", vec!["Aloe"]),
+        ("pet_safety=safe", vec!["Aloe", "Basil"]),
+        ("difficulty=easy&pet_safety=safe", vec!["Aloe"]),
+    ] {
+        let (status, plants) = send(&app, "GET", &format!("/api/plants?{query}"), None).await;
+        assert_eq!(status, StatusCode::OK, "{query}");
+        assert_eq!(names(&plants), expected, "{query}");
+    }
+
+    let (_, plants) = send(&app, "GET", "/api/plants?status=overdue", None).await;
+    assert_eq!(plants[0]["watering_status"], "overdue");
+}
+
+#[tokio::test]
+async fn list_sorts_plants() {
+    let (app, _dir) = seeded_app().await;
+
+    for (query, expected) in [
+        ("", vec!["Aloe", "Basil", "Cactus"]),
+        ("sort=name&order=desc", vec!["Cactus", "Basil", "Aloe"]),
+        ("sort=next_due", vec!["Cactus", "Basil", "Aloe"]),
+        (
+            "sort=last_watered&order=desc",
+            vec!["Aloe", "Basil", "Cactus"],
+        ),
+        ("sort=created_at", vec!["Aloe", "Basil", "Cactus"]),
+    ] {
+        let (_, plants) = send(&app, "GET", &format!("/api/plants?{query}"), None).await;
+        assert_eq!(names(&plants), expected, "{query}");
+    }
+}
+
+#[tokio::test]
+async fn list_paginates_with_total_count() {
+    let (app, _dir) = seeded_app().await;
+
+    let resp = app
+        .clone()
+        .oneshot(json_request("GET", "/api/plants?limit=2&offset=1", None))
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["x-total-count"], "3");
+    assert_eq!(names(&body_json(resp).await), ["Basil", "Cactus"]);
+
+    let resp = app
+        .clone()
+        .oneshot(json_request(
+            "GET",
+            "/api/plants?pet_safety=safe&limit=1",
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["x-total-count"], "2");
+    assert_eq!(names(&body_json(resp).await), ["Aloe"]);
+}
+
+#[tokio::test]
+async fn list_rejects_invalid_options() {
+    let (app, _dir) = seeded_app().await;
+
+    for (query, code) in [
+        ("status=thirsty", "PLANT_INVALID_WATERING_STATUS"),
+        ("light_needs=dark", "PLANT_INVALID_LIGHT_NEEDS"),
+        ("pet_safety=maybe", "PLANT_INVALID_PET_SAFETY"),
+        ("difficulty=hard", "PLANT_INVALID_DIFFICULTY"),
+        ("sort=species", "PLANT_INVALID_SORT"),
+        ("order=up", "PLANT_INVALID_SORT"),
+        ("limit=0", "PLANT_INVALID_PAGINATION"),
+        ("limit=501", "PLANT_INVALID_PAGINATION"),
+        ("offset=-1", "PLANT_INVALID_PAGINATION"),
+    ] {
+        let (status, body) = send(&app, "GET", &format!("/api/plants?{query}"), None).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{query}");
+        assert_eq!(body["code"], code, "{query}");
+    }
+}
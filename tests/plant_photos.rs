@@ -0,0 +1,246 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0];
+
+/// Multipart gallery upload with a JPEG `file` field and the given text fields.
+fn gallery_upload(uri: &str, fields: &[(&str, &str)]) -> Request<Body> {
+    let mut body_bytes = Vec::new();
+    for (name, value) in fields {
+        body_bytes.extend_from_slice(
+            format!(
+                "------testboundary\r\n\
+                 Content-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    }
+    body_bytes.extend_from_slice(
+        b"------testboundary\r\n\
+          Content-Disposition: form-data; name=\"file\"; filename=\"test.jpg\"\r\n\
+          Content-Type: image/jpeg\r\n\r\n",
+    );
+    body_bytes.extend_from_slice(JPEG);
+    body_bytes.extend_from_slice(b"\r\n------testboundary--\r\n");
+
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(
+            "content-type",
+            "multipart/form-data; boundary=----testboundary",
+        )
+        .body(Body::from(body_bytes))
+        .unwrap()
+}
+
+async fn send(app: &axum::Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+    let resp = app.clone().oneshot(request).await.unwrap();
+    let status = resp.status();
+    if status == StatusCode::NO_CONTENT {
+        return (status, serde_json::Value::Null);
+    }
+    (status, body_json(resp).await)
+}
+
+async fn setup() -> (axum::Router, tempfile::TempDir) {
+    let (app, dir) = common::test_app_with_uploads().await;
+    let (status, _) = send(
+        &app,
+        json_request("POST", "/api/plants", Some(r#"{"name":"Fern"}"#)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    (app, dir)
+}
+
+fn filename(url: &serde_json::Value) -> &str {
+    url.as_str().unwrap().strip_prefix("/uploads/").unwrap()
+}
+
+#[tokio::test]
+async fn gallery_upload_and_list() {
+    let (app, _dir) = setup().await;
+
+    let (status, first) = send(
+        &app,
+        gallery_upload(
+            "/api/plants/1/photos",
+            &[("caption", " Day one "), ("taken_at", "2026-03-01")],
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(first["caption"], "Day one");
+    assert_eq!(first["taken_at"], "2026-03-01");
+    // The first photo becomes the cover
+    assert_eq!(first["is_cover"], true);
+
+    let (_, second) = send(&app, gallery_upload("/api/plants/1/photos", &[])).await;
+    assert_eq!(second["is_cover"], false);
+    assert!(second["caption"].is_null());
+    assert!(second["taken_at"].is_string());
+
+    let (_, photos) = send(&app, json_request("GET", "/api/plants/1/photos", None)).await;
+    assert_eq!(photos.as_array().unwrap().len(), 2);
+    assert_eq!(photos[0]["id"], first["id"]);
+    assert_eq!(photos[1]["id"], second["id"]);
+
+    let (_, plant) = send(&app, json_request("GET", "/api/plants/1", None)).await;
+    assert_eq!(plant["photo_url"], first["url"]);
+
+    let (_, third) = send(
+        &app,
+        gallery_upload("/api/plants/1/photos", &[("cover", "true")]),
+    )
+    .await;
+    assert_eq!(third["is_cover"], true);
+    let (_, plant) = send(&app, json_request("GET", "/api/plants/1", None)).await;
+    assert_eq!(plant["photo_url"], third["url"]);
+
+    let (status, body) = send(
+        &app,
+        gallery_upload("/api/plants/1/photos", &[("taken_at", "March")]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "PHOTO_INVALID_TAKEN_AT");
+
+    let (status, _) = send(&app, gallery_upload("/api/plants/99/photos", &[])).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn gallery_update_cover_and_reorder() {
+    let (app, _dir) = setup().await;
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let (_, photo) = send(&app, gallery_upload("/api/plants/1/photos", &[])).await;
+        ids.push(photo["id"].as_i64().unwrap());
+    }
+
+    let (status, photo) = send(
+        &app,
+        json_request(
+            "PUT",
+            &format!("/api/plants/1/photos/{}", ids[1]),
+            Some(r#"{"caption":"New leaf","taken_at":"2026-04-02"}"#),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(photo["caption"], "New leaf");
+    assert_eq!(photo["taken_at"], "2026-04-02");
+
+    let (_, photo) = send(
+        &app,
+        json_request(
+            "PUT",
+            &format!("/api/plants/1/photos/{}", ids[1]),
+            Some(r#"{"caption":null}"#),
+        ),
+    )
+    .await;
+    assert!(photo["caption"].is_null());
+    assert_eq!(photo["taken_at"], "2026-04-02");
+
+    let (status, plant) = send(
+        &app,
+        json_request(
+            "POST",
+            &format!("/api/plants/1/photos/{}/cover", ids[2]),
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, photos) = send(&app, json_request("GET", "/api/plants/1/photos", None)).await;
+    assert_eq!(plant["photo_url"], photos[2]["url"]);
+    assert_eq!(photos[2]["is_cover"], true);
+
+    let order = format!(r#"{{"ids":[{},{},{}]}}"#, ids[2], ids[0], ids[1]);
+    let (status, photos) = send(
+        &app,
+        json_request("PUT", "/api/plants/1/photos", Some(&order)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let listed: Vec<i64> = photos
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(listed, [ids[2], ids[0], ids[1]]);
+
+    let partial = format!(r#"{{"ids":[{},{}]}}"#, ids[0], ids[1]);
+    let (status, body) = send(
+        &app,
+        json_request("PUT", "/api/plants/1/photos", Some(&partial)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "PHOTO_ORDER_MISMATCH");
+}
+
+#[tokio::test]
+async fn deleting_the_cover_promotes_the_next_photo() {
+    let (app, dir) = setup().await;
+    let (_, first) = send(&app, gallery_upload("/api/plants/1/photos", &[])).await;
+    let (_, second) = send(&app, gallery_upload("/api/plants/1/photos", &[])).await;
+    assert!(dir.path().join(filename(&first["url"])).exists());
+
+    let (status, _) = send(
+        &app,
+        json_request(
+            "DELETE",
+            &format!("/api/plants/1/photos/{}", first["id"]),
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!dir.path().join(filename(&first["url"])).exists());
+
+    let (_, plant) = send(&app, json_request("GET", "/api/plants/1", None)).await;
+    assert_eq!(plant["photo_url"], second["url"]);
+
+    let (status, body) = send(
+        &app,
+        json_request(
+            "DELETE",
+            &format!("/api/plants/1/photos/{}", first["id"]),
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "PHOTO_NOT_FOUND");
+
+    // Deleting through the single-photo endpoint removes the cover from the gallery
+    let (status, _) = send(&app, json_request("DELETE", "/api/plants/1/photo", None)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, photos) = send(&app, json_request("GET", "/api/plants/1/photos", None)).await;
+    assert!(photos.as_array().unwrap().is_empty());
+    let (_, plant) = send(&app, json_request("GET", "/api/plants/1", None)).await;
+    assert!(plant["photo_url"].is_null());
+}
+
+#[tokio::test]
+async fn purging_a_plant_deletes_its_gallery() {
+    let (app, dir) = setup().await;
+    let (_, first) = send(&app, gallery_upload("/api/plants/1/photos", &[])).await;
+    let (_, second) = send(&app, gallery_upload("/api/plants/1/photos", &[])).await;
+
+    send(&app, json_request("DELETE", "/api/plants/1", None)).await;
+    let (status, _) = send(&app, json_request("DELETE", "/api/trash/plants/1", None)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    for photo in [first, second] {
+        assert!(!dir.path().join(filename(&photo["url"])).exists());
+    }
+}
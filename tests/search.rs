@@ -0,0 +1,136 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let resp = app
+        .clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    if status == StatusCode::NO_CONTENT {
+        return (status, serde_json::Value::Null);
+    }
+    (status, body_json(resp).await)
+}
+
+#[tokio::test]
+async fn search_finds_plants_and_care_events() {
+    let (app, _dir) = common::test_app().await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Calathea","species":"Goeppertia orbifolia","notes":"Prone to spider mites"}"#),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Spider Plant"}"#),
+    )
+    .await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    send(
+        &app,
+        "POST",
+        "/api/plants/3/care",
+        Some(r#"{"event_type":"custom","notes":"Treated <spider> mites with neem oil"}"#),
+    )
+    .await;
+
+    let (status, results) = send(&app, "GET", "/api/search?q=spider%20mite", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let plants = results["plants"].as_array().unwrap();
+    assert_eq!(plants.len(), 1);
+    assert_eq!(plants[0]["name"], "Calathea");
+    assert_eq!(plants[0]["name_highlight"], "Calathea");
+    assert_eq!(
+        plants[0]["notes_snippet"],
+        "Prone to <mark>spider</mark> <mark>mites</mark>"
+    );
+
+    let events = results["care_events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["plant_id"], 3);
+    assert_eq!(events[0]["plant_name"], "Fern");
+    assert_eq!(
+        events[0]["notes_snippet"],
+        "Treated &lt;<mark>spider</mark>&gt; <mark>mites</mark> with neem oil"
+    );
+
+    // Name matches rank above notes matches
+    let (_, results) = send(&app, "GET", "/api/search?q=spider", None).await;
+    assert_eq!(results["plants"][0]["name"], "Spider Plant");
+    assert_eq!(
+        results["plants"][0]["name_highlight"],
+        "<mark>Spider</mark> Plant"
+    );
+    assert_eq!(
+        results["plants"][0]["notes_snippet"],
+        serde_json::Value::Null
+    );
+    assert_eq!(results["plants"][1]["name"], "Calathea");
+}
+
+#[tokio::test]
+async fn search_index_follows_updates_and_deletes() {
+    let (app, _dir) = common::test_app().await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Monstera"}"#)).await;
+    send(
+        &app,
+        "POST",
+        "/api/plants/1/care",
+        Some(r#"{"event_type":"custom","notes":"Wiped leaves"}"#),
+    )
+    .await;
+
+    send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"name":"Philodendron"}"#),
+    )
+    .await;
+    let (_, results) = send(&app, "GET", "/api/search?q=monstera", None).await;
+    assert!(results["plants"].as_array().unwrap().is_empty());
+    let (_, results) = send(&app, "GET", "/api/search?q=philo", None).await;
+    assert_eq!(results["plants"][0]["id"], 1);
+
+    // Trashed events are hidden, restoring brings them back
+    send(&app, "DELETE", "/api/plants/1/care/1", None).await;
+    let (_, results) = send(&app, "GET", "/api/search?q=leaves", None).await;
+    assert!(results["care_events"].as_array().unwrap().is_empty());
+    send(&app, "POST", "/api/trash/care/1/restore", None).await;
+    let (_, results) = send(&app, "GET", "/api/search?q=leaves", None).await;
+    assert_eq!(results["care_events"][0]["id"], 1);
+
+    // Purged plants leave the index with their events
+    send(&app, "DELETE", "/api/plants/1", None).await;
+    send(&app, "DELETE", "/api/trash/plants/1", None).await;
+    let (_, results) = send(&app, "GET", "/api/search?q=leaves", None).await;
+    assert!(results["care_events"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn search_requires_a_query() {
+    let (app, _dir) = common::test_app().await;
+
+    let (status, body) = send(&app, "GET", "/api/search", None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "SEARCH_QUERY_REQUIRED");
+
+    // Query syntax is taken literally
+    let (status, _) = send(&app, "GET", "/api/search?q=%22AND%20(", None).await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -18,7 +18,10 @@ async fn get_returns_defaults() {
         .unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
     let body = body_json(resp).await;
-    assert_eq!(body, json!({"theme": "system", "locale": "en"}));
+    assert_eq!(
+        body,
+        json!({"theme": "system", "locale": "en", "time_zone": "UTC"})
+    );
 }
 
 #[tokio::test]
@@ -34,7 +37,10 @@ async fn put_theme_only() {
         .unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
     let body = body_json(resp).await;
-    assert_eq!(body, json!({"theme": "dark", "locale": "en"}));
+    assert_eq!(
+        body,
+        json!({"theme": "dark", "locale": "en", "time_zone": "UTC"})
+    );
 }
 
 #[tokio::test]
@@ -50,7 +56,10 @@ async fn put_locale_only() {
         .unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
     let body = body_json(resp).await;
-    assert_eq!(body, json!({"theme": "system", "locale": "de"}));
+    assert_eq!(
+        body,
+        json!({"theme": "system", "locale": "de", "time_zone": "UTC"})
+    );
 }
 
 #[tokio::test]
@@ -66,7 +75,10 @@ async fn put_both_fields() {
         .unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
     let body = body_json(resp).await;
-    assert_eq!(body, json!({"theme": "light", "locale": "es"}));
+    assert_eq!(
+        body,
+        json!({"theme": "light", "locale": "es", "time_zone": "UTC"})
+    );
 }
 
 #[tokio::test]
@@ -78,7 +90,10 @@ async fn put_empty_body() {
         .unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
     let body = body_json(resp).await;
-    assert_eq!(body, json!({"theme": "system", "locale": "en"}));
+    assert_eq!(
+        body,
+        json!({"theme": "system", "locale": "en", "time_zone": "UTC"})
+    );
 }
 
 #[tokio::test]
@@ -108,3 +123,41 @@ async fn put_invalid_locale() {
         .unwrap();
     assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
 }
+
+#[tokio::test]
+async fn put_time_zone() {
+    let (app, _dir) = app().await;
+    let resp = app
+        .oneshot(json_request(
+            "PUT",
+            "/api/settings",
+            Some(r#"{"time_zone":"America/New_York"}"#),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_json(resp).await;
+    assert_eq!(
+        body,
+        json!({"theme": "system", "locale": "en", "time_zone": "America/New_York"})
+    );
+}
+
+#[tokio::test]
+async fn put_invalid_time_zone() {
+    let (app, _dir) = app().await;
+    for zone in ["Europe/Atlantis", "+02:00", ""] {
+        let resp = app
+            .clone()
+            .oneshot(json_request(
+                "PUT",
+                "/api/settings",
+                Some(&format!(r#"{{"time_zone":"{zone}"}}"#)),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY, "{zone}");
+        let body = body_json(resp).await;
+        assert_eq!(body["code"], "SETTINGS_INVALID_TIME_ZONE");
+    }
+}
@@ -154,3 +154,117 @@ async fn stats_empty_database() {
     assert_eq!(json["location_count"], 0);
     assert_eq!(json["photo_count"], 0);
 }
+
+async fn get_json(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(common::json_request("GET", uri, None))
+        .await
+        .unwrap();
+    let status = response.status();
+    (status, common::body_json(response).await)
+}
+
+#[tokio::test]
+async fn care_stats_report_adherence_and_activity() {
+    let (app, _dir) = common::test_app().await;
+    for body in [
+        r#"{"name":"Fern","watering_interval_days":7}"#,
+        r#"{"name":"Cactus","watering_interval_days":14}"#,
+    ] {
+        app.clone()
+            .oneshot(common::json_request("POST", "/api/plants", Some(body)))
+            .await
+            .unwrap();
+    }
+    // Fern: on time, on time, 3 days late; Cactus: one repotting only
+    for (plant, event_type, date) in [
+        (1, "watered", "2026-01-05"),
+        (1, "watered", "2026-01-12"),
+        (1, "watered", "2026-01-19"),
+        (1, "watered", "2026-01-29"),
+        (1, "fertilized", "2026-01-29"),
+        (2, "repotted", "2026-02-02"),
+    ] {
+        let body = format!(r#"{{"event_type":"{event_type}","occurred_at":"{date}T09:00:00Z"}}"#);
+        let response = app
+            .clone()
+            .oneshot(common::json_request(
+                "POST",
+                &format!("/api/plants/{plant}/care"),
+                Some(&body),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    let (status, plant) = get_json(&app, "/api/plants/1/stats?from=2026-01-01&to=2026-02-28").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(plant["watering"]["judged_waterings"], 3);
+    assert_eq!(plant["watering"]["on_time_percent"], 66.7);
+    assert_eq!(plant["watering"]["average_days_overdue"], 3.0);
+    assert_eq!(plant["watering"]["longest_on_time_streak"], 2);
+    // Overdue on 2026-01-27 and -28, then from 2026-02-06 to the 28th
+    assert_eq!(plant["watering"]["days_overdue_last_90"], 2 + 23);
+    let months = plant["events_per_month"].as_array().unwrap();
+    assert_eq!(months.len(), 2);
+    assert_eq!(months[0]["month"], "2026-01");
+    assert_eq!(months[0]["count"], 5);
+    assert_eq!(months[1]["count"], 0);
+    // 2026-01-05, -12 and -19 are Mondays, the 29th a Thursday
+    assert_eq!(plant["busiest_weekdays"][0]["weekday"], "monday");
+    assert_eq!(plant["busiest_weekdays"][0]["count"], 3);
+
+    let (status, collection) =
+        get_json(&app, "/api/stats/care?from=2026-01-01&to=2026-02-28").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(collection["from"], "2026-01-01");
+    assert_eq!(collection["events_per_month"][1]["count"], 1);
+    assert_eq!(collection["watering"]["judged_waterings"], 3);
+    let plants = collection["plants"].as_array().unwrap();
+    assert_eq!(plants.len(), 2);
+    // Least punctual first; the never watered cactus has no rating
+    assert_eq!(plants[0]["plant_name"], "Fern");
+    assert!(plants[1]["on_time_percent"].is_null());
+
+    let (status, body) = get_json(&app, "/api/stats/care?from=2026-03-01&to=2026-01-01").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "STATS_INVALID_DATE_RANGE");
+
+    let (status, _) = get_json(&app, "/api/plants/99/stats").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn care_stats_histograms_use_the_configured_time_zone() {
+    let (app, _dir) = common::test_app().await;
+    for (method, uri, body) in [
+        ("POST", "/api/plants", r#"{"name":"Fern"}"#),
+        (
+            "PUT",
+            "/api/settings",
+            r#"{"time_zone":"Pacific/Auckland"}"#,
+        ),
+        // Saturday noon in UTC is already Sunday, February 1st in Auckland
+        (
+            "POST",
+            "/api/plants/1/care",
+            r#"{"event_type":"watered","occurred_at":"2026-01-31T12:00:00Z"}"#,
+        ),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(common::json_request(method, uri, Some(body)))
+            .await
+            .unwrap();
+        assert!(response.status().is_success(), "{method} {uri}");
+    }
+
+    let (_, stats) = get_json(&app, "/api/stats/care?from=2026-01-01&to=2026-02-28").await;
+    assert_eq!(stats["events_per_month"][0]["count"], 0);
+    assert_eq!(stats["events_per_month"][1]["month"], "2026-02");
+    assert_eq!(stats["events_per_month"][1]["count"], 1);
+    assert_eq!(stats["busiest_weekdays"][0]["weekday"], "sunday");
+    assert_eq!(stats["busiest_weekdays"][0]["count"], 1);
+}
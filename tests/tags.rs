@@ -0,0 +1,178 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let resp = app
+        .clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap();
+    let status = resp.status();
+    if status == StatusCode::NO_CONTENT {
+        return (status, serde_json::Value::Null);
+    }
+    (status, body_json(resp).await)
+}
+
+fn names(list: &serde_json::Value) -> Vec<&str> {
+    list.as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn tag_crud() {
+    let (app, _dir) = common::test_app().await;
+
+    let (status, tag) = send(&app, "POST", "/api/tags", Some(r#"{"name":" office "}"#)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(tag["name"], "office");
+    assert_eq!(tag["plant_count"], 0);
+
+    let (status, body) = send(&app, "POST", "/api/tags", Some(r#"{"name":"office"}"#)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "TAG_ALREADY_EXISTS");
+
+    let (status, body) = send(&app, "POST", "/api/tags", Some(r#"{"name":"  "}"#)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "TAG_NAME_REQUIRED");
+
+    let (status, tag) = send(&app, "PUT", "/api/tags/1", Some(r#"{"name":"desk"}"#)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tag["name"], "desk");
+
+    let (status, body) = send(&app, "PUT", "/api/tags/99", Some(r#"{"name":"x"}"#)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "TAG_NOT_FOUND");
+
+    let (status, _) = send(&app, "DELETE", "/api/tags/1", None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "DELETE", "/api/tags/1", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, tags) = send(&app, "GET", "/api/tags", None).await;
+    assert!(tags.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn plants_are_tagged_on_create_and_update() {
+    let (app, _dir) = common::test_app().await;
+
+    let (status, plant) = send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Aloe","tags":["succulents"," office","succulents"]}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(plant["tags"], serde_json::json!(["office", "succulents"]));
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Fern","tags":[""]}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "TAG_NAME_REQUIRED");
+
+    // Omitting tags keeps them, an explicit list replaces them
+    let (_, plant) = send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"name":"Aloe vera"}"#),
+    )
+    .await;
+    assert_eq!(plant["tags"], serde_json::json!(["office", "succulents"]));
+    let (_, plant) = send(
+        &app,
+        "PUT",
+        "/api/plants/1",
+        Some(r#"{"tags":["needs repotting"]}"#),
+    )
+    .await;
+    assert_eq!(plant["tags"], serde_json::json!(["needs repotting"]));
+
+    let (_, tags) = send(&app, "GET", "/api/tags", None).await;
+    assert_eq!(names(&tags), ["needs repotting", "office", "succulents"]);
+    assert_eq!(tags[0]["plant_count"], 1);
+    assert_eq!(tags[1]["plant_count"], 0);
+
+    let (_, history) = send(&app, "GET", "/api/plants/1/history", None).await;
+    let tag_change = history
+        .as_array()
+        .unwrap()
+        .iter()
+        .find_map(|entry| entry["changes"].get("tags"))
+        .unwrap();
+    assert_eq!(
+        tag_change["old"],
+        serde_json::json!(["office", "succulents"])
+    );
+    assert_eq!(tag_change["new"], serde_json::json!(["needs repotting"]));
+}
+
+#[tokio::test]
+async fn plants_and_care_events_filter_by_tag() {
+    let (app, _dir) = common::test_app().await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Aloe","tags":["succulents"]}"#),
+    )
+    .await;
+    send(&app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    for id in [1, 2] {
+        send(
+            &app,
+            "POST",
+            &format!("/api/plants/{id}/care"),
+            Some(r#"{"event_type":"watered"}"#),
+        )
+        .await;
+    }
+
+    let (_, plants) = send(&app, "GET", "/api/plants?tag=succulents", None).await;
+    assert_eq!(names(&plants), ["Aloe"]);
+    let (_, plants) = send(&app, "GET", "/api/plants?tag=unknown", None).await;
+    assert!(plants.as_array().unwrap().is_empty());
+
+    let (_, page) = send(&app, "GET", "/api/care?tag=succulents", None).await;
+    let events = page["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["plant_id"], 1);
+}
+
+#[tokio::test]
+async fn deleting_a_tag_untags_plants() {
+    let (app, _dir) = common::test_app().await;
+    send(
+        &app,
+        "POST",
+        "/api/plants",
+        Some(r#"{"name":"Aloe","tags":["office","succulents"]}"#),
+    )
+    .await;
+
+    let (_, tags) = send(&app, "GET", "/api/tags", None).await;
+    let office = tags[0]["id"].as_i64().unwrap();
+    let (status, _) = send(&app, "DELETE", &format!("/api/tags/{office}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, plant) = send(&app, "GET", "/api/plants/1", None).await;
+    assert_eq!(plant["tags"], serde_json::json!(["succulents"]));
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+/// Multipart upload of a solid-colour JPEG with the given extra text fields.
+fn upload(uri: &str, rgb: [u8; 3], fields: &[(&str, &str)]) -> Request<Body> {
+    let img = image::RgbImage::from_pixel(64, 48, image::Rgb(rgb));
+    let mut jpeg = std::io::Cursor::new(Vec::new());
+    img.write_to(&mut jpeg, image::ImageFormat::Jpeg).unwrap();
+
+    let mut body_bytes = Vec::new();
+    for (name, value) in fields {
+        body_bytes.extend_from_slice(
+            format!(
+                "------testboundary\r\n\
+                 Content-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    }
+    body_bytes.extend_from_slice(
+        b"------testboundary\r\n\
+          Content-Disposition: form-data; name=\"file\"; filename=\"test.jpg\"\r\n\
+          Content-Type: image/jpeg\r\n\r\n",
+    );
+    body_bytes.extend_from_slice(&jpeg.into_inner());
+    body_bytes.extend_from_slice(b"\r\n------testboundary--\r\n");
+
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(
+            "content-type",
+            "multipart/form-data; boundary=----testboundary",
+        )
+        .body(Body::from(body_bytes))
+        .unwrap()
+}
+
+async fn fetch_sheet(app: &axum::Router) -> image::RgbImage {
+    let resp = app
+        .clone()
+        .oneshot(json_request("GET", "/api/plants/1/timelapse", None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "image/jpeg");
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    image::load_from_memory(&bytes).unwrap().to_rgb8()
+}
+
+/// Centre pixel of the `n`-th frame in the first row.
+fn frame_color(sheet: &image::RgbImage, n: u32) -> image::Rgb<u8> {
+    *sheet.get_pixel(8 + n * 248 + 120, 8 + 120)
+}
+
+#[tokio::test]
+async fn timelapse_orders_plant_and_care_photos_chronologically() {
+    let (app, _dir) = common::test_app_with_uploads().await;
+    app.clone()
+        .oneshot(json_request(
+            "POST",
+            "/api/plants",
+            Some(r#"{"name":"Monstera"}"#),
+        ))
+        .await
+        .unwrap();
+
+    let resp = app
+        .clone()
+        .oneshot(json_request("GET", "/api/plants/1/timelapse", None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(body_json(resp).await["code"], "TIMELAPSE_NO_PHOTOS");
+
+    let resp = app
+        .clone()
+        .oneshot(upload(
+            "/api/plants/1/photos",
+            [220, 0, 0],
+            &[("taken_at", "2026-05-01")],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let sheet = fetch_sheet(&app).await;
+    assert_eq!(sheet.width(), 256);
+
+    // An older care event photo is added in front of the gallery photo
+    app.clone()
+        .oneshot(json_request(
+            "POST",
+            "/api/plants/1/care",
+            Some(r#"{"event_type":"repotted","occurred_at":"2026-03-01T10:00:00Z"}"#),
+        ))
+        .await
+        .unwrap();
+    let resp = app
+        .clone()
+        .oneshot(upload("/api/plants/1/care/1/photo", [0, 0, 220], &[]))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let sheet = fetch_sheet(&app).await;
+    assert_eq!(sheet.width(), 2 * 248 + 8);
+    let first = frame_color(&sheet, 0);
+    let second = frame_color(&sheet, 1);
+    assert!(
+        first[2] > 150 && first[0] < 80,
+        "first frame is blue: {first:?}"
+    );
+    assert!(
+        second[0] > 150 && second[2] < 80,
+        "second frame is red: {second:?}"
+    );
+
+    let resp = app
+        .clone()
+        .oneshot(json_request("GET", "/api/plants/99/timelapse", None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(body_json(resp).await["code"], "PLANT_NOT_FOUND");
+}
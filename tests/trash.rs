@@ -0,0 +1,193 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{body_json, json_request};
+use tower::ServiceExt;
+
+async fn send(app: &axum::Router, method: &str, uri: &str, body: Option<&str>) -> StatusCode {
+    app.clone()
+        .oneshot(json_request(method, uri, body))
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn get_json(app: &axum::Router, uri: &str) -> serde_json::Value {
+    let resp = app
+        .clone()
+        .oneshot(json_request("GET", uri, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_json(resp).await
+}
+
+async fn seed(app: &axum::Router) {
+    send(app, "POST", "/api/plants", Some(r#"{"name":"Fern"}"#)).await;
+    send(app, "POST", "/api/plants/1/water", None).await;
+    send(
+        app,
+        "POST",
+        "/api/plants/1/care",
+        Some(r#"{"event_type":"fertilized"}"#),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn deleted_plant_moves_to_trash_and_restores_with_journal() {
+    let (app, _dir) = common::test_app().await;
+    seed(&app).await;
+
+    assert_eq!(
+        send(&app, "DELETE", "/api/plants/1", None).await,
+        StatusCode::NO_CONTENT
+    );
+    assert_eq!(
+        send(&app, "GET", "/api/plants/1", None).await,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(get_json(&app, "/api/plants").await, serde_json::json!([]));
+    assert_eq!(get_json(&app, "/api/stats").await["care_event_count"], 0);
+    // Deleting again is a 404 -- the plant is already in the trash
+    assert_eq!(
+        send(&app, "DELETE", "/api/plants/1", None).await,
+        StatusCode::NOT_FOUND
+    );
+
+    let trash = get_json(&app, "/api/trash").await;
+    assert_eq!(trash["plants"][0]["id"], 1);
+    assert_eq!(trash["plants"][0]["name"], "Fern");
+    assert_eq!(trash["plants"][0]["care_event_count"], 2);
+    assert!(trash["plants"][0]["deleted_at"].is_string());
+    assert_eq!(trash["care_events"], serde_json::json!([]));
+
+    let resp = app
+        .clone()
+        .oneshot(json_request("POST", "/api/trash/plants/1/restore", None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let plant = body_json(resp).await;
+    assert_eq!(plant["name"], "Fern");
+    assert_eq!(plant["watering_status"], "ok");
+
+    let events = get_json(&app, "/api/plants/1/care").await;
+    assert_eq!(events.as_array().unwrap().len(), 2);
+    let trash = get_json(&app, "/api/trash").await;
+    assert_eq!(trash["plants"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn deleted_care_event_moves_to_trash_and_restores() {
+    let (app, _dir) = common::test_app().await;
+    seed(&app).await;
+
+    assert_eq!(
+        send(&app, "DELETE", "/api/plants/1/care/1", None).await,
+        StatusCode::NO_CONTENT
+    );
+    let plant = get_json(&app, "/api/plants/1").await;
+    assert_eq!(plant["last_watered"], serde_json::Value::Null);
+
+    let trash = get_json(&app, "/api/trash").await;
+    assert_eq!(trash["care_events"][0]["id"], 1);
+    assert_eq!(trash["care_events"][0]["plant_name"], "Fern");
+    assert_eq!(trash["care_events"][0]["event_type"], "watered");
+
+    assert_eq!(
+        send(&app, "POST", "/api/trash/care/1/restore", None).await,
+        StatusCode::OK
+    );
+    let plant = get_json(&app, "/api/plants/1").await;
+    assert!(plant["last_watered"].is_string());
+
+    let history = get_json(&app, "/api/plants/1/history").await;
+    assert_eq!(history[0]["action"], "restored");
+    assert_eq!(history[0]["entity_type"], "care_event");
+}
+
+#[tokio::test]
+async fn restore_unknown_items_returns_404() {
+    let (app, _dir) = common::test_app().await;
+    seed(&app).await;
+
+    // Live items are not in the trash
+    assert_eq!(
+        send(&app, "POST", "/api/trash/plants/1/restore", None).await,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        send(&app, "POST", "/api/trash/care/1/restore", None).await,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        send(&app, "DELETE", "/api/trash/plants/1", None).await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn purge_deletes_permanently() {
+    let (app, _dir) = common::test_app().await;
+    seed(&app).await;
+    send(&app, "DELETE", "/api/plants/1/care/2", None).await;
+
+    assert_eq!(
+        send(&app, "DELETE", "/api/trash/care/2", None).await,
+        StatusCode::NO_CONTENT
+    );
+    assert_eq!(
+        send(&app, "POST", "/api/trash/care/2/restore", None).await,
+        StatusCode::NOT_FOUND
+    );
+
+    send(&app, "DELETE", "/api/plants/1", None).await;
+    assert_eq!(
+        send(&app, "DELETE", "/api/trash", None).await,
+        StatusCode::NO_CONTENT
+    );
+    let trash = get_json(&app, "/api/trash").await;
+    assert_eq!(trash, serde_json::json!({"plants": [], "care_events": []}));
+    assert_eq!(
+        send(&app, "POST", "/api/trash/plants/1/restore", None).await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn purge_expired_keeps_recent_trash() {
+    let pool = common::test_pool().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = flowl::images::ImageStore::new(dir.path().to_path_buf());
+    std::fs::write(dir.path().join("old.jpg"), b"x").unwrap();
+
+    for (name, photo, deleted_at) in [
+        ("Old", Some("old.jpg"), Some("2026-01-01T00:00:00Z")),
+        ("Recent", None, Some("2026-03-01T00:00:00Z")),
+        ("Live", None, None),
+    ] {
+        sqlx::query(
+            "INSERT INTO plants (name, photo_path, deleted_at, created_at, updated_at) \
+             VALUES (?, ?, ?, '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z')",
+        )
+        .bind(name)
+        .bind(photo)
+        .bind(deleted_at)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let purged = flowl::trash::purge_trashed_before(&pool, &store, Some("2026-02-01T00:00:00Z"))
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM plants ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(names, vec!["Recent", "Live"]);
+    assert!(!dir.path().join("old.jpg").exists());
+}
@@ -10,6 +10,12 @@ import {
   fetchAllCareEvents,
   exportData,
   importData,
+  mergeData,
+  fetchBackups,
+  backupUrl,
+  restoreBackup,
+  plantsCsvUrl,
+  careCsvUrl,
 } from "./api";
 
 vi.mock("./stores/network", () => ({
@@ -47,6 +53,15 @@ describe("request helper (via public API functions)", () => {
     expect(fn).toHaveBeenCalledWith("/api/plants", { method: "GET" });
   });
 
+  it("passes plant list options as query parameters", async () => {
+    const fn = mockFetch({ ok: true, json: vi.fn().mockResolvedValue([]) });
+    await fetchPlants({ status: "overdue", sort: "next_due", limit: 20 });
+    expect(fn).toHaveBeenCalledWith(
+      "/api/plants?status=overdue&sort=next_due&limit=20",
+      { method: "GET" },
+    );
+  });
+
   it("sends POST request with JSON body", async () => {
     const plant = { id: 1, name: "Fern" };
     const fn = mockFetch({ ok: true, json: vi.fn().mockResolvedValue(plant) });
@@ -162,10 +177,10 @@ describe("API endpoint functions", () => {
       ok: true,
       json: vi.fn().mockResolvedValue({ events: [], has_more: false }),
     });
-    await fetchAllCareEvents(10, 5, ["watered"]);
+    await fetchAllCareEvents(10, "MjAyNi0wMi0xMHwz", ["watered"]);
     const url = fn.mock.calls[0][0] as string;
     expect(url).toContain("limit=10");
-    expect(url).toContain("before=5");
+    expect(url).toContain("cursor=MjAyNi0wMi0xMHwz");
     expect(url).toContain("type=watered");
   });
 
@@ -197,6 +212,23 @@ describe("API endpoint functions", () => {
     await fetchAllCareEvents(20, undefined, []);
     expect(fn).toHaveBeenCalledWith("/api/care?limit=20", { method: "GET" });
   });
+
+  it("fetchAllCareEvents adds journal filters", async () => {
+    const fn = mockFetch({
+      ok: true,
+      json: vi.fn().mockResolvedValue({ events: [], has_more: false }),
+    });
+    await fetchAllCareEvents(20, undefined, ["fertilized"], {
+      plant_ids: [1, 2],
+      from: "2026-03-01",
+      to: "2026-03-31",
+      has_photo: true,
+    });
+    const url = fn.mock.calls[0][0] as string;
+    expect(url).toContain("plant_id=1&plant_id=2");
+    expect(url).toContain("from=2026-03-01&to=2026-03-31");
+    expect(url).toContain("has_photo=true");
+  });
 });
 
 describe("importData", () => {
@@ -227,6 +259,44 @@ describe("importData", () => {
   });
 });
 
+describe("mergeData", () => {
+  it("sends POST with FormData in merge mode", async () => {
+    const fn = mockFetch({ ok: true });
+    const file = new File(["zip content"], "export.zip", {
+      type: "application/zip",
+    });
+    await mergeData(file);
+    const [url, init] = fn.mock.calls[0];
+    expect(url).toBe("/api/data/import?mode=merge");
+    expect(init.method).toBe("POST");
+    expect(init.body).toBeInstanceOf(FormData);
+  });
+});
+
+describe("stored backups", () => {
+  it("lists stored backups", async () => {
+    const fn = mockFetch({ ok: true });
+    await fetchBackups();
+    expect(fn.mock.calls[0][0]).toBe("/api/backups");
+  });
+
+  it("builds the download URL", () => {
+    expect(backupUrl("flowl-backup-20261019T033000Z.zip")).toBe(
+      "/api/backups/flowl-backup-20261019T033000Z.zip",
+    );
+  });
+
+  it("restores a backup in the given mode", async () => {
+    const fn = mockFetch({ ok: true });
+    await restoreBackup("flowl-backup-20261019T033000Z.zip", "merge");
+    const [url, init] = fn.mock.calls[0];
+    expect(url).toBe(
+      "/api/backups/flowl-backup-20261019T033000Z.zip/restore?mode=merge",
+    );
+    expect(init.method).toBe("POST");
+  });
+});
+
 describe("exportData", () => {
   it("downloads the export archive from the shared API helper", async () => {
     const blob = new Blob(["zip"], { type: "application/zip" });
@@ -271,3 +341,25 @@ describe("exportData", () => {
     await expect(exportData()).rejects.toThrow("Export unavailable");
   });
 });
+
+describe("CSV export URLs", () => {
+  it("builds the plant CSV URL with repeated plant ids", () => {
+    expect(plantsCsvUrl()).toBe("/api/data/export/plants.csv");
+    expect(plantsCsvUrl({ plantIds: [1, 2], from: "2026-01-01" })).toBe(
+      "/api/data/export/plants.csv?plant_id=1&plant_id=2",
+    );
+  });
+
+  it("builds the care journal CSV URL with range and filters", () => {
+    expect(
+      careCsvUrl({
+        from: "2026-01-01",
+        to: "2026-03-31",
+        plantIds: [3],
+        types: ["watered", "fertilized"],
+      }),
+    ).toBe(
+      "/api/data/export/care.csv?from=2026-01-01&to=2026-03-31&plant_id=3&type=watered&type=fertilized",
+    );
+  });
+});
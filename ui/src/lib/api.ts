@@ -5,11 +5,13 @@ export type PetSafety = "safe" | "caution" | "toxic";
 export type GrowthSpeed = "slow" | "moderate" | "fast";
 export type SoilType = "standard" | "cactus-mix" | "orchid-bark" | "peat-moss";
 export type SoilMoisture = "dry" | "moderate" | "moist";
+export type LifecycleStatus = "active" | "archived" | "gifted" | "deceased";
 export type EventType =
   | "watered"
   | "fertilized"
   | "repotted"
   | "pruned"
+  | "propagated"
   | "custom"
   | "ai-consultation";
 
@@ -19,6 +21,12 @@ export interface Location {
   plant_count: number;
 }
 
+export interface Tag {
+  id: number;
+  name: string;
+  plant_count: number;
+}
+
 export interface Plant {
   id: number;
   name: string;
@@ -38,6 +46,11 @@ export interface Plant {
   soil_type: SoilType | null;
   soil_moisture: SoilMoisture | null;
   notes: string | null;
+  lifecycle_status: LifecycleStatus;
+  lifecycle_date: string | null;
+  lifecycle_reason: string | null;
+  parent_id: number | null;
+  tags: string[];
   created_at: string;
   updated_at: string;
 }
@@ -55,6 +68,8 @@ export interface CreatePlant {
   soil_type?: SoilType | null;
   soil_moisture?: SoilMoisture | null;
   notes?: string;
+  parent_id?: number | null;
+  tags?: string[];
 }
 
 export interface UpdatePlant {
@@ -70,6 +85,23 @@ export interface UpdatePlant {
   soil_type?: SoilType | null;
   soil_moisture?: SoilMoisture | null;
   notes?: string;
+  lifecycle_status?: LifecycleStatus;
+  lifecycle_date?: string | null;
+  lifecycle_reason?: string | null;
+  parent_id?: number | null;
+  tags?: string[];
+}
+
+export interface BulkPlantChanges {
+  location_id?: number | null;
+  watering_interval_days?: number;
+  light_needs?: LightNeeds;
+  difficulty?: Difficulty | null;
+  pet_safety?: PetSafety | null;
+  growth_speed?: GrowthSpeed | null;
+  soil_type?: SoilType | null;
+  soil_moisture?: SoilMoisture | null;
+  tags?: string[];
 }
 
 export interface AppInfo {
@@ -85,6 +117,60 @@ export interface Stats {
   photo_count: number;
 }
 
+export interface StatsRange {
+  /** First day (YYYY-MM-DD), inclusive; defaults to one year before `to`. */
+  from?: string;
+  /** Last day (YYYY-MM-DD), inclusive; defaults to today. */
+  to?: string;
+}
+
+export interface WateringAdherence {
+  judged_waterings: number;
+  on_time_percent: number | null;
+  average_days_overdue: number | null;
+  longest_on_time_streak: number;
+  days_overdue_last_90: number;
+}
+
+export interface CareStats {
+  from: string;
+  to: string;
+  watering: WateringAdherence;
+  events_per_month: { month: string; count: number }[];
+  busiest_weekdays: { weekday: string; count: number }[];
+}
+
+export interface PlantAdherence extends WateringAdherence {
+  plant_id: number;
+  plant_name: string;
+}
+
+export interface CollectionCareStats extends CareStats {
+  plants: PlantAdherence[];
+}
+
+/** `to` defaults to today in the configured time zone, `from` to 364 days earlier. */
+export interface HeatmapFilter extends StatsRange {
+  types?: EventType[];
+  plantIds?: number[];
+}
+
+export interface HeatmapDay {
+  date: string;
+  count: number;
+  types: Partial<Record<EventType, number>>;
+}
+
+export interface CareHeatmap {
+  from: string;
+  to: string;
+  time_zone: string;
+  total: number;
+  max: number;
+  /** Days with at least one event, oldest first. */
+  days: HeatmapDay[];
+}
+
 export interface MqttStatus {
   status: "connected" | "disconnected" | "disabled";
   broker: string | null;
@@ -195,6 +281,40 @@ export function fetchStats(): Promise<Stats> {
   return request("GET", "/api/stats");
 }
 
+function rangeQuery(range: StatsRange): string {
+  const params = new URLSearchParams();
+  for (const [key, value] of Object.entries(range)) {
+    if (value !== undefined) params.set(key, value);
+  }
+  const qs = params.toString();
+  return qs ? `?${qs}` : "";
+}
+
+export function fetchCareStats(
+  range: StatsRange = {},
+): Promise<CollectionCareStats> {
+  return request("GET", `/api/stats/care${rangeQuery(range)}`);
+}
+
+export function fetchPlantStats(
+  plantId: number,
+  range: StatsRange = {},
+): Promise<CareStats> {
+  return request("GET", `/api/plants/${plantId}/stats${rangeQuery(range)}`);
+}
+
+export function fetchCareHeatmap(
+  filter: HeatmapFilter = {},
+): Promise<CareHeatmap> {
+  const params = new URLSearchParams();
+  if (filter.from !== undefined) params.set("from", filter.from);
+  if (filter.to !== undefined) params.set("to", filter.to);
+  for (const type of filter.types ?? []) params.append("type", type);
+  for (const id of filter.plantIds ?? []) params.append("plant_id", String(id));
+  const qs = params.toString();
+  return request("GET", `/api/care/heatmap${qs ? `?${qs}` : ""}`);
+}
+
 export function fetchMqttStatus(): Promise<MqttStatus> {
   return request("GET", "/api/mqtt/status");
 }
@@ -241,14 +361,52 @@ export function repairMqtt(): Promise<MqttRepairResult> {
   return request("POST", "/api/mqtt/repair");
 }
 
-export function fetchPlants(): Promise<Plant[]> {
-  return request("GET", "/api/plants");
+export interface PlantListOptions {
+  lifecycle?: LifecycleStatus | "all";
+  tag?: string;
+  location_id?: number;
+  status?: WateringStatus;
+  light_needs?: LightNeeds;
+  pet_safety?: PetSafety;
+  difficulty?: Difficulty;
+  sort?: "name" | "next_due" | "last_watered" | "created_at";
+  order?: "asc" | "desc";
+  limit?: number;
+  offset?: number;
+}
+
+export function fetchPlants(options: PlantListOptions = {}): Promise<Plant[]> {
+  const params = new URLSearchParams();
+  for (const [key, value] of Object.entries(options)) {
+    if (value !== undefined) params.set(key, String(value));
+  }
+  const qs = params.toString();
+  return request("GET", `/api/plants${qs ? `?${qs}` : ""}`);
 }
 
 export function fetchPlant(id: number): Promise<Plant> {
   return request("GET", `/api/plants/${id}`);
 }
 
+export interface LineagePlant {
+  id: number;
+  name: string;
+  species: string | null;
+  icon: string;
+  parent_id: number | null;
+  lifecycle_status: LifecycleStatus;
+  depth: number;
+}
+
+export interface Lineage {
+  ancestors: LineagePlant[];
+  descendants: LineagePlant[];
+}
+
+export function fetchLineage(id: number): Promise<Lineage> {
+  return request("GET", `/api/plants/${id}/lineage`);
+}
+
 export function createPlant(data: CreatePlant): Promise<Plant> {
   return request("POST", "/api/plants", data);
 }
@@ -257,6 +415,27 @@ export function updatePlant(id: number, data: UpdatePlant): Promise<Plant> {
   return request("PUT", `/api/plants/${id}`, data);
 }
 
+export function bulkUpdatePlants(
+  ids: number[],
+  changes: BulkPlantChanges,
+): Promise<Plant[]> {
+  return request("PATCH", "/api/plants", { ids, changes });
+}
+
+export interface DuplicatePlantOptions {
+  count?: number;
+  name?: string;
+  numbered?: boolean;
+  copy_photo?: boolean;
+}
+
+export function duplicatePlant(
+  id: number,
+  options: DuplicatePlantOptions = {},
+): Promise<Plant[]> {
+  return request("POST", `/api/plants/${id}/duplicate`, options);
+}
+
 export function deletePlant(id: number): Promise<void> {
   return request("DELETE", `/api/plants/${id}`);
 }
@@ -275,8 +454,104 @@ export function deletePlantPhoto(plantId: number): Promise<void> {
   return request("DELETE", `/api/plants/${plantId}/photo`);
 }
 
+// --- Photo gallery ---
+
+export interface PlantPhoto {
+  id: number;
+  plant_id: number;
+  url: string;
+  caption: string | null;
+  taken_at: string | null;
+  position: number;
+  is_cover: boolean;
+  created_at: string;
+}
+
+export interface PlantPhotoUpload {
+  caption?: string;
+  taken_at?: string;
+  cover?: boolean;
+}
+
+export function fetchPlantPhotos(plantId: number): Promise<PlantPhoto[]> {
+  return request("GET", `/api/plants/${plantId}/photos`);
+}
+
+export function addPlantPhoto(
+  plantId: number,
+  file: File,
+  options: PlantPhotoUpload = {},
+): Promise<PlantPhoto> {
+  const body = new FormData();
+  if (options.caption) body.append("caption", options.caption);
+  if (options.taken_at) body.append("taken_at", options.taken_at);
+  if (options.cover) body.append("cover", "true");
+  body.append("file", file);
+  return request("POST", `/api/plants/${plantId}/photos`, body);
+}
+
+export function updatePlantPhoto(
+  plantId: number,
+  photoId: number,
+  data: { caption?: string | null; taken_at?: string | null },
+): Promise<PlantPhoto> {
+  return request("PUT", `/api/plants/${plantId}/photos/${photoId}`, data);
+}
+
+export function deleteGalleryPhoto(
+  plantId: number,
+  photoId: number,
+): Promise<void> {
+  return request("DELETE", `/api/plants/${plantId}/photos/${photoId}`);
+}
+
+export function setCoverPhoto(plantId: number, photoId: number): Promise<Plant> {
+  return request("POST", `/api/plants/${plantId}/photos/${photoId}/cover`);
+}
+
+export function reorderPlantPhotos(
+  plantId: number,
+  ids: number[],
+): Promise<PlantPhoto[]> {
+  return request("PUT", `/api/plants/${plantId}/photos`, { ids });
+}
+
+/** URL of the plant's growth time-lapse: a JPEG contact sheet of all its photos, oldest first. */
+export function timelapseUrl(plantId: number): string {
+  return `/api/plants/${plantId}/timelapse`;
+}
+
 // --- Import/Export ---
 
+export interface CsvExportFilter {
+  /** First day (YYYY-MM-DD) of the care journal, inclusive. */
+  from?: string;
+  /** Last day (YYYY-MM-DD) of the care journal, inclusive. */
+  to?: string;
+  plantIds?: number[];
+  types?: EventType[];
+}
+
+function csvExportUrl(name: string, filter: CsvExportFilter): string {
+  const params = new URLSearchParams();
+  if (filter.from !== undefined) params.set("from", filter.from);
+  if (filter.to !== undefined) params.set("to", filter.to);
+  for (const id of filter.plantIds ?? []) params.append("plant_id", String(id));
+  for (const type of filter.types ?? []) params.append("type", type);
+  const qs = params.toString();
+  return `/api/data/export/${name}.csv${qs ? `?${qs}` : ""}`;
+}
+
+/** URL of the streamed plant CSV; only `plantIds` applies. */
+export function plantsCsvUrl(filter: CsvExportFilter = {}): string {
+  return csvExportUrl("plants", { plantIds: filter.plantIds });
+}
+
+/** URL of the streamed care journal CSV, oldest entry first. */
+export function careCsvUrl(filter: CsvExportFilter = {}): string {
+  return csvExportUrl("care", filter);
+}
+
 export interface ImportResult {
   locations: number;
   plants: number;
@@ -290,6 +565,132 @@ export function importData(file: File): Promise<ImportResult> {
   return request("POST", "/api/data/import", body);
 }
 
+export interface MergeCounts {
+  added: number;
+  skipped: number;
+  conflicted: number;
+}
+
+export interface MergeItem {
+  entity: "location" | "tag" | "plant" | "care_event" | "measurement";
+  archive_id: number;
+  id: number;
+  label: string;
+  outcome: "added" | "skipped" | "conflicted";
+  fields?: string[];
+}
+
+export interface MergeReport {
+  locations: MergeCounts;
+  tags: MergeCounts;
+  plants: MergeCounts;
+  care_events: MergeCounts;
+  measurements: MergeCounts;
+  photos: MergeCounts;
+  items: MergeItem[];
+}
+
+export function mergeData(file: File): Promise<MergeReport> {
+  const body = new FormData();
+  body.append("file", file);
+  return request("POST", "/api/data/import?mode=merge", body);
+}
+
+export interface StoredBackup {
+  name: string;
+  created_at: string;
+  size_bytes: number;
+}
+
+export interface BackupList {
+  schedule: string | null;
+  next_run: string | null;
+  retention: { daily: number; weekly: number; monthly: number };
+  backups: StoredBackup[];
+}
+
+export function fetchBackups(): Promise<BackupList> {
+  return request("GET", "/api/backups");
+}
+
+export function createBackup(): Promise<StoredBackup> {
+  return request("POST", "/api/backups");
+}
+
+/** URL of a stored backup archive. */
+export function backupUrl(name: string): string {
+  return `/api/backups/${encodeURIComponent(name)}`;
+}
+
+export function restoreBackup(
+  name: string,
+  mode: "replace" | "merge" = "replace",
+): Promise<ImportResult | MergeReport> {
+  return request(
+    "POST",
+    `/api/backups/${encodeURIComponent(name)}/restore?mode=${mode}`,
+  );
+}
+
+export type CsvImportField =
+  | "name"
+  | "species"
+  | "location"
+  | "watering_interval_days"
+  | "light_needs"
+  | "difficulty"
+  | "pet_safety"
+  | "growth_speed"
+  | "soil_type"
+  | "soil_moisture"
+  | "notes"
+  | "last_watered";
+
+export interface CsvImportOptions {
+  /** Validate and report without creating anything. */
+  dryRun?: boolean;
+  /** Import the valid rows even if others are invalid. */
+  skipInvalid?: boolean;
+  /** Column header per field; an empty string skips the field. */
+  mapping?: Partial<Record<CsvImportField, string>>;
+}
+
+export interface CsvImportRowError {
+  /** Spreadsheet row number; the header is row 1. */
+  row: number;
+  field: CsvImportField;
+  code: string;
+  message: string;
+}
+
+export interface CsvImportReport {
+  dry_run: boolean;
+  columns: Partial<Record<CsvImportField, string>>;
+  rows: number;
+  valid_rows: number;
+  new_locations: string[];
+  created_plant_ids: number[];
+  errors: CsvImportRowError[];
+}
+
+export function importPlantsCsv(
+  file: File,
+  options: CsvImportOptions = {},
+): Promise<CsvImportReport> {
+  const body = new FormData();
+  body.append("file", file);
+  if (options.mapping) body.append("mapping", JSON.stringify(options.mapping));
+  const params = new URLSearchParams();
+  if (options.dryRun) params.set("dry_run", "true");
+  if (options.skipInvalid) params.set("skip_invalid", "true");
+  const qs = params.toString();
+  return request(
+    "POST",
+    `/api/data/import/plants.csv${qs ? `?${qs}` : ""}`,
+    body,
+  );
+}
+
 export async function exportData(): Promise<void> {
   let resp: Response;
   try {
@@ -328,6 +729,8 @@ export async function exportData(): Promise<void> {
 export interface UserSettings {
   theme: string;
   locale: string;
+  /** IANA time zone (e.g. `Europe/Berlin`) care events are assigned to days in. */
+  time_zone: string;
 }
 
 export function fetchSettings(): Promise<UserSettings> {
@@ -349,36 +752,62 @@ export interface CareEvent {
   event_type: EventType;
   notes: string | null;
   photo_url: string | null;
+  photos: CareEventPhoto[];
+  child_plant_id: number | null;
   occurred_at: string;
   created_at: string;
 }
 
+export interface CareEventPhoto {
+  id: number;
+  url: string;
+  position: number;
+}
+
 export interface CreateCareEvent {
   event_type: EventType;
   notes?: string;
   occurred_at?: string;
+  child_name?: string;
 }
 
 export interface CareEventsPage {
   events: CareEvent[];
   has_more: boolean;
+  next_cursor: string | null;
 }
 
 export function fetchCareEvents(plantId: number): Promise<CareEvent[]> {
   return request("GET", `/api/plants/${plantId}/care`);
 }
 
+export interface CareJournalFilters {
+  tag?: string;
+  plant_ids?: number[];
+  location_id?: number;
+  from?: string;
+  to?: string;
+  has_photo?: boolean;
+  q?: string;
+}
+
 export function fetchAllCareEvents(
   limit?: number,
-  before?: number,
+  cursor?: string,
   types?: EventType[],
+  filters: CareJournalFilters = {},
 ): Promise<CareEventsPage> {
   const params = new URLSearchParams();
   if (limit !== undefined) params.set("limit", String(limit));
-  if (before !== undefined) params.set("before", String(before));
+  if (cursor !== undefined) params.set("cursor", cursor);
   if (types) {
     for (const t of types) params.append("type", t);
   }
+  const { plant_ids, ...rest } = filters;
+  for (const id of plant_ids ?? []) params.append("plant_id", String(id));
+  for (const [key, value] of Object.entries(rest)) {
+    if (value !== undefined) params.set(key, String(value));
+  }
   const qs = params.toString();
   return request("GET", `/api/care${qs ? `?${qs}` : ""}`);
 }
@@ -414,6 +843,150 @@ export function deleteCareEventPhoto(
   return request("DELETE", `/api/plants/${plantId}/care/${eventId}/photo`);
 }
 
+export function deleteCareEventPhotoById(
+  plantId: number,
+  eventId: number,
+  photoId: number,
+): Promise<void> {
+  return request(
+    "DELETE",
+    `/api/plants/${plantId}/care/${eventId}/photos/${photoId}`,
+  );
+}
+
+export function reorderCareEventPhotos(
+  plantId: number,
+  eventId: number,
+  ids: number[],
+): Promise<CareEvent> {
+  return request("PUT", `/api/plants/${plantId}/care/${eventId}/photos`, {
+    ids,
+  });
+}
+
+// --- Measurements ---
+
+export type MeasurementMetric = "height" | "width" | "leaf_count" | "pot_size";
+
+export interface Measurement {
+  id: number;
+  plant_id: number;
+  care_event_id: number | null;
+  metric: MeasurementMetric;
+  value: number;
+  unit: string;
+  notes: string | null;
+  measured_at: string;
+  created_at: string;
+}
+
+export interface CreateMeasurement {
+  metric: MeasurementMetric;
+  value: number;
+  measured_at?: string;
+  care_event_id?: number;
+  notes?: string;
+}
+
+export interface UpdateMeasurement {
+  metric?: MeasurementMetric;
+  value?: number;
+  measured_at?: string;
+  care_event_id?: number | null;
+  notes?: string | null;
+}
+
+export interface MeasurementSeries {
+  metric: MeasurementMetric;
+  unit: string;
+  points: { measured_at: string; value: number }[];
+}
+
+export function fetchMeasurements(
+  plantId: number,
+  metric?: MeasurementMetric,
+): Promise<Measurement[]> {
+  const query = metric ? `?metric=${metric}` : "";
+  return request("GET", `/api/plants/${plantId}/measurements${query}`);
+}
+
+export function createMeasurement(
+  plantId: number,
+  data: CreateMeasurement,
+): Promise<Measurement> {
+  return request("POST", `/api/plants/${plantId}/measurements`, data);
+}
+
+export function updateMeasurement(
+  plantId: number,
+  measurementId: number,
+  data: UpdateMeasurement,
+): Promise<Measurement> {
+  return request(
+    "PUT",
+    `/api/plants/${plantId}/measurements/${measurementId}`,
+    data,
+  );
+}
+
+export function deleteMeasurement(
+  plantId: number,
+  measurementId: number,
+): Promise<void> {
+  return request(
+    "DELETE",
+    `/api/plants/${plantId}/measurements/${measurementId}`,
+  );
+}
+
+export function fetchMeasurementSeries(
+  plantId: number,
+  params: { metric?: MeasurementMetric; from?: string; to?: string } = {},
+): Promise<MeasurementSeries[]> {
+  const query = new URLSearchParams();
+  for (const [key, value] of Object.entries(params)) {
+    if (value !== undefined) query.set(key, value);
+  }
+  const qs = query.toString();
+  return request(
+    "GET",
+    `/api/plants/${plantId}/measurements/series${qs ? `?${qs}` : ""}`,
+  );
+}
+
+// --- Search ---
+
+export interface PlantSearchHit {
+  id: number;
+  name: string;
+  species: string | null;
+  icon: string;
+  lifecycle_status: LifecycleStatus;
+  name_highlight: string;
+  species_highlight: string | null;
+  notes_snippet: string | null;
+}
+
+export interface CareEventSearchHit {
+  id: number;
+  plant_id: number;
+  plant_name: string;
+  event_type: EventType;
+  occurred_at: string;
+  notes_snippet: string;
+}
+
+export interface SearchResults {
+  plants: PlantSearchHit[];
+  care_events: CareEventSearchHit[];
+}
+
+export function search(q: string, limit?: number): Promise<SearchResults> {
+  const params = new URLSearchParams({ q });
+  if (limit !== undefined) params.set("limit", String(limit));
+  return request("GET", `/api/search?${params}`);
+}
+
 // --- Locations ---
 
 export function fetchLocations(): Promise<Location[]> {
@@ -432,6 +1005,24 @@ export function deleteLocation(id: number): Promise<void> {
   return request("DELETE", `/api/locations/${id}`);
 }
 
+// --- Tags ---
+
+export function fetchTags(): Promise<Tag[]> {
+  return request("GET", "/api/tags");
+}
+
+export function createTag(name: string): Promise<Tag> {
+  return request("POST", "/api/tags", { name });
+}
+
+export function updateTag(id: number, name: string): Promise<Tag> {
+  return request("PUT", `/api/tags/${id}`, { name });
+}
+
+export function deleteTag(id: number): Promise<void> {
+  return request("DELETE", `/api/tags/${id}`);
+}
+
 // --- AI Chat ---
 
 export async function* chatPlant(
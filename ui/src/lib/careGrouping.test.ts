@@ -10,6 +10,8 @@ function makeEvent(overrides: Partial<CareEvent> & { id: number }): CareEvent {
     event_type: "watered",
     notes: null,
     photo_url: null,
+    photos: [],
+    child_plant_id: null,
     occurred_at: "2026-03-14T10:00:00Z",
     created_at: "2026-03-14T10:00:00Z",
     ...overrides,
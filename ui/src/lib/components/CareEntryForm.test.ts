@@ -71,6 +71,8 @@ describe("CareEntryForm", () => {
       event_type: "watered",
       notes: null,
       photo_url: null,
+      photos: [],
+      child_plant_id: null,
       occurred_at: "2025-01-01T00:00:00Z",
       created_at: "2025-01-01T00:00:00Z",
     });
@@ -112,8 +112,7 @@ export const de: Translations = {
     editPlant: "Pflanze bearbeiten",
     addPlant: "Pflanze hinzufügen",
     deletePlant: "Pflanze löschen",
-    deleteConfirm:
-      '"{name}" löschen? Dies kann nicht rückgängig gemacht werden.',
+    deleteConfirm: '"{name}" in den Papierkorb verschieben?',
     wateringSection: "Bewässerung",
     careInfoSection: "Pflegeinfo",
     notesSection: "Notizen",
@@ -139,7 +138,7 @@ export const de: Translations = {
     openPhoto: "Foto öffnen",
     deleteLogEntry: "Eintrag löschen",
     deleteLogEntryConfirm:
-      "Diesen Pflegeeintrag löschen? Dies kann nicht rückgängig gemacht werden.",
+      "Diesen Pflegeeintrag in den Papierkorb verschieben?",
     lightDirect: "Direktes Sonnenlicht",
     lightLow: "Wenig Licht",
     lightIndirect: "Indirektes Licht",
@@ -168,6 +167,7 @@ export const de: Translations = {
     fertilized: "Gedüngt",
     repotted: "Umgetopft",
     pruned: "Geschnitten",
+    propagated: "Vermehrt",
     custom: "Eigenes",
     aiConsultation: "KI-Beratung",
     noCareEvents: "Noch keine Pflegeereignisse verzeichnet.",
@@ -234,6 +234,10 @@ export const de: Translations = {
     importing: "Importieren...",
     importBtn: "Importieren",
     exportBtn: "Exportieren",
+    spreadsheet: "Tabelle",
+    spreadsheetDesc: "Pflanzen oder das Pflegetagebuch als CSV herunterladen.",
+    plantsCsvBtn: "Pflanzen",
+    careCsvBtn: "Pflegetagebuch",
     importData: "Daten importieren",
     importConfirm:
       '"{name}" importieren? Alle vorhandenen Daten und Fotos werden ersetzt.',
@@ -402,6 +406,8 @@ export const de: Translations = {
     CARE_EVENT_NOT_FOUND: "Pflegeereignis nicht gefunden",
     CARE_EVENT_TYPE_REQUIRED: "Ereignistyp ist erforderlich",
     CARE_EVENT_INVALID_TYPE: "Ungültiger Ereignistyp",
+    MEASUREMENT_METRIC_REQUIRED: "Messgröße ist erforderlich",
+    MEASUREMENT_VALUE_REQUIRED: "Wert ist erforderlich",
     LOCATION_NOT_FOUND: "Standort nicht gefunden",
     LOCATION_NAME_REQUIRED: "Standortname ist erforderlich",
     LOCATION_ALREADY_EXISTS: "Ein Standort mit diesem Namen existiert bereits",
@@ -415,10 +421,24 @@ export const de: Translations = {
     IMPORT_NO_FILE: "Keine Datei angegeben",
     IMPORT_INVALID_ARCHIVE: "Ungültiges ZIP-Archiv",
     IMPORT_INVALID_DATA: "Ungültige Importdaten",
-    IMPORT_VERSION_MISMATCH: "Inkompatible Exportversion",
+    IMPORT_FORMAT_TOO_NEW:
+      "Sicherung stammt aus einer neueren Version; bitte zuerst aktualisieren",
     IMPORT_INVALID_FILENAME: "Ungültiger Dateiname im Archiv",
     IMPORT_FILE_TOO_LARGE: "Datei im Archiv ist zu groß",
     IMPORT_VALIDATION_FAILED: "Validierung der Importdaten fehlgeschlagen",
+    IMPORT_INVALID_MODE: "Importmodus muss „replace“ oder „merge“ sein",
+    BACKUP_NOT_FOUND: "Sicherung nicht gefunden",
+    BACKUP_FAILED: "Sicherung konnte nicht geschrieben werden",
+    IMPORT_CSV_INVALID: "Ungültige CSV-Datei",
+    IMPORT_CSV_TOO_MANY_ROWS: "Ein CSV-Import darf höchstens 1000 Zeilen haben",
+    IMPORT_CSV_INVALID_MAPPING:
+      "Die Spaltenzuordnung nennt ein unbekanntes Feld oder eine unbekannte Spalte",
+    IMPORT_CSV_NAME_COLUMN_REQUIRED:
+      "Keine Spalte ist dem Pflanzennamen zugeordnet",
+    IMPORT_CSV_INVALID_ROWS:
+      "Einige Zeilen sind ungültig; korrigiere oder überspringe sie",
+    IMPORT_CSV_INVALID_DATE:
+      "Zuletzt gegossen muss ein Datum (JJJJ-MM-TT) sein, nicht in der Zukunft",
     AI_NOT_CONFIGURED: "KI ist nicht eingerichtet",
     AI_PROVIDER_FAILED: "KI-Anfrage fehlgeschlagen",
     AI_STREAM_ERROR: "KI-Antwort abgebrochen",
@@ -110,7 +110,7 @@ const en = {
     editPlant: "Edit Plant",
     addPlant: "Add Plant",
     deletePlant: "Delete plant",
-    deleteConfirm: 'Delete "{name}"? This cannot be undone.',
+    deleteConfirm: 'Move "{name}" to the trash?',
     wateringSection: "Watering",
     careInfoSection: "Care Info",
     notesSection: "Notes",
@@ -135,7 +135,7 @@ const en = {
     notesOptional: "Notes (optional)",
     openPhoto: "Open photo",
     deleteLogEntry: "Delete log entry",
-    deleteLogEntryConfirm: "Delete this care entry? This cannot be undone.",
+    deleteLogEntryConfirm: "Move this care entry to the trash?",
     lightDirect: "Direct sunlight",
     lightLow: "Low light",
     lightIndirect: "Indirect light",
@@ -164,6 +164,7 @@ const en = {
     fertilized: "Fertilized",
     repotted: "Repotted",
     pruned: "Pruned",
+    propagated: "Propagated",
     custom: "Custom",
     aiConsultation: "AI Consultation",
     noCareEvents: "No care events recorded yet.",
@@ -228,6 +229,10 @@ const en = {
     importing: "Importing...",
     importBtn: "Import",
     exportBtn: "Export",
+    spreadsheet: "Spreadsheet",
+    spreadsheetDesc: "Download plants or the care journal as CSV.",
+    plantsCsvBtn: "Plants",
+    careCsvBtn: "Care journal",
     importData: "Import data",
     importConfirm:
       'Import "{name}"? All existing data and photos will be replaced.',
@@ -394,6 +399,8 @@ const en = {
     CARE_EVENT_NOT_FOUND: "Care event not found",
     CARE_EVENT_TYPE_REQUIRED: "Event type is required",
     CARE_EVENT_INVALID_TYPE: "Invalid event type",
+    MEASUREMENT_METRIC_REQUIRED: "Metric is required",
+    MEASUREMENT_VALUE_REQUIRED: "Value is required",
     LOCATION_NOT_FOUND: "Location not found",
     LOCATION_NAME_REQUIRED: "Location name is required",
     LOCATION_ALREADY_EXISTS: "A location with this name already exists",
@@ -407,10 +414,22 @@ const en = {
     IMPORT_NO_FILE: "No file provided",
     IMPORT_INVALID_ARCHIVE: "Invalid ZIP archive",
     IMPORT_INVALID_DATA: "Invalid import data",
-    IMPORT_VERSION_MISMATCH: "Incompatible export version",
+    IMPORT_FORMAT_TOO_NEW: "Backup is from a newer version; update to restore it",
     IMPORT_INVALID_FILENAME: "Invalid filename in archive",
     IMPORT_FILE_TOO_LARGE: "File in archive is too large",
     IMPORT_VALIDATION_FAILED: "Import data validation failed",
+    IMPORT_INVALID_MODE: "Import mode must be replace or merge",
+    BACKUP_NOT_FOUND: "Backup not found",
+    BACKUP_FAILED: "Backup could not be written",
+    IMPORT_CSV_INVALID: "Invalid CSV file",
+    IMPORT_CSV_TOO_MANY_ROWS: "A CSV import can have at most 1000 rows",
+    IMPORT_CSV_INVALID_MAPPING:
+      "Column mapping names an unknown field or column",
+    IMPORT_CSV_NAME_COLUMN_REQUIRED: "No column is mapped to the plant name",
+    IMPORT_CSV_INVALID_ROWS:
+      "Some rows are invalid; fix them or skip invalid rows",
+    IMPORT_CSV_INVALID_DATE:
+      "Last watered must be a YYYY-MM-DD date, not in the future",
     AI_NOT_CONFIGURED: "AI provider is not configured",
     AI_PROVIDER_FAILED: "AI provider request failed",
     AI_STREAM_ERROR: "AI response interrupted",
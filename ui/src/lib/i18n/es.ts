@@ -112,7 +112,7 @@ export const es: Translations = {
     editPlant: "Editar Planta",
     addPlant: "Añadir Planta",
     deletePlant: "Eliminar planta",
-    deleteConfirm: '¿Eliminar "{name}"? Esto no se puede deshacer.',
+    deleteConfirm: '¿Mover "{name}" a la papelera?',
     wateringSection: "Riego",
     careInfoSection: "Info de cuidado",
     notesSection: "Notas",
@@ -137,8 +137,7 @@ export const es: Translations = {
     notesOptional: "Notas (opcional)",
     openPhoto: "Abrir foto",
     deleteLogEntry: "Eliminar entrada",
-    deleteLogEntryConfirm:
-      "¿Eliminar esta entrada de cuidado? Esto no se puede deshacer.",
+    deleteLogEntryConfirm: "¿Mover esta entrada de cuidado a la papelera?",
     lightDirect: "Luz solar directa",
     lightLow: "Poca luz",
     lightIndirect: "Luz indirecta",
@@ -167,6 +166,7 @@ export const es: Translations = {
     fertilized: "Fertilizada",
     repotted: "Trasplantada",
     pruned: "Podada",
+    propagated: "Propagada",
     custom: "Personalizado",
     aiConsultation: "Consulta IA",
     noCareEvents: "Aún no hay eventos de cuidado registrados.",
@@ -232,6 +232,10 @@ export const es: Translations = {
     importing: "Importando...",
     importBtn: "Importar",
     exportBtn: "Exportar",
+    spreadsheet: "Hoja de cálculo",
+    spreadsheetDesc: "Descarga las plantas o el diario de cuidados como CSV.",
+    plantsCsvBtn: "Plantas",
+    careCsvBtn: "Diario de cuidados",
     importData: "Importar datos",
     importConfirm:
       '¿Importar "{name}"? Todos los datos y fotos existentes serán reemplazados.',
@@ -399,6 +403,8 @@ export const es: Translations = {
     CARE_EVENT_NOT_FOUND: "Evento de cuidado no encontrado",
     CARE_EVENT_TYPE_REQUIRED: "El tipo de evento es obligatorio",
     CARE_EVENT_INVALID_TYPE: "Tipo de evento no válido",
+    MEASUREMENT_METRIC_REQUIRED: "La métrica es obligatoria",
+    MEASUREMENT_VALUE_REQUIRED: "El valor es obligatorio",
     LOCATION_NOT_FOUND: "Ubicación no encontrada",
     LOCATION_NAME_REQUIRED: "El nombre de la ubicación es obligatorio",
     LOCATION_ALREADY_EXISTS: "Ya existe una ubicación con este nombre",
@@ -412,10 +418,25 @@ export const es: Translations = {
     IMPORT_NO_FILE: "No se proporcionó ningún archivo",
     IMPORT_INVALID_ARCHIVE: "Archivo ZIP no válido",
     IMPORT_INVALID_DATA: "Datos de importación no válidos",
-    IMPORT_VERSION_MISMATCH: "Versión de exportación incompatible",
+    IMPORT_FORMAT_TOO_NEW:
+      "La copia es de una versión más reciente; actualiza para restaurarla",
     IMPORT_INVALID_FILENAME: "Nombre de archivo no válido en el archivo",
     IMPORT_FILE_TOO_LARGE: "Archivo en el archivo es demasiado grande",
     IMPORT_VALIDATION_FAILED: "Validación de datos de importación fallida",
+    IMPORT_INVALID_MODE: "El modo de importación debe ser replace o merge",
+    BACKUP_NOT_FOUND: "Copia de seguridad no encontrada",
+    BACKUP_FAILED: "No se pudo escribir la copia de seguridad",
+    IMPORT_CSV_INVALID: "Archivo CSV no válido",
+    IMPORT_CSV_TOO_MANY_ROWS:
+      "Una importación CSV puede tener como máximo 1000 filas",
+    IMPORT_CSV_INVALID_MAPPING:
+      "La asignación de columnas nombra un campo o una columna desconocidos",
+    IMPORT_CSV_NAME_COLUMN_REQUIRED:
+      "Ninguna columna está asignada al nombre de la planta",
+    IMPORT_CSV_INVALID_ROWS:
+      "Algunas filas no son válidas; corrígelas u omite las filas no válidas",
+    IMPORT_CSV_INVALID_DATE:
+      "El último riego debe ser una fecha AAAA-MM-DD, no en el futuro",
     AI_NOT_CONFIGURED: "El proveedor de IA no está configurado",
     AI_PROVIDER_FAILED: "La solicitud al proveedor de IA falló",
     AI_STREAM_ERROR: "Respuesta de IA interrumpida",
@@ -28,6 +28,8 @@ const mockEvent: CareEvent = {
   event_type: "watered",
   notes: null,
   photo_url: null,
+  photos: [],
+  child_plant_id: null,
   occurred_at: "2025-01-10T10:00:00Z",
   created_at: "2025-01-10T10:00:00Z",
 };
@@ -0,0 +1,80 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import type { Plant } from "$lib/api";
+import { currentPlant } from "./plants";
+import { applyChange } from "./live";
+
+vi.mock("$lib/api", async (importOriginal) => {
+  const actual = await importOriginal<typeof import("$lib/api")>();
+  return {
+    ...actual,
+    fetchPlants: vi.fn(),
+    fetchPlant: vi.fn(),
+    fetchCareEvents: vi.fn(),
+  };
+});
+
+import * as api from "$lib/api";
+
+const mockPlant = { id: 1, name: "Fern" } as Plant;
+
+beforeEach(() => {
+  vi.resetAllMocks();
+  vi.mocked(api.fetchPlants).mockResolvedValue([mockPlant]);
+  vi.mocked(api.fetchPlant).mockResolvedValue(mockPlant);
+  vi.mocked(api.fetchCareEvents).mockResolvedValue([]);
+  currentPlant.set(null);
+});
+
+describe("applyChange", () => {
+  it("reloads the plant list for any change", async () => {
+    await applyChange({ type: "plant_created", plant_id: 2 });
+
+    expect(api.fetchPlants).toHaveBeenCalledOnce();
+    expect(api.fetchPlant).not.toHaveBeenCalled();
+  });
+
+  it("reloads the open plant when it changed", async () => {
+    currentPlant.set(mockPlant);
+
+    await applyChange({ type: "plant_updated", plant_id: 1 });
+
+    expect(api.fetchPlant).toHaveBeenCalledWith(1);
+    expect(api.fetchCareEvents).not.toHaveBeenCalled();
+  });
+
+  it("reloads the journal of the open plant on care event changes", async () => {
+    currentPlant.set(mockPlant);
+
+    await applyChange({
+      type: "care_event_created",
+      plant_id: 1,
+      event_id: 5,
+    });
+
+    expect(api.fetchPlant).toHaveBeenCalledWith(1);
+    expect(api.fetchCareEvents).toHaveBeenCalledWith(1);
+  });
+
+  it("ignores changes to other plants", async () => {
+    currentPlant.set(mockPlant);
+
+    await applyChange({
+      type: "care_event_deleted",
+      plant_id: 2,
+      event_id: 5,
+    });
+
+    expect(api.fetchPlant).not.toHaveBeenCalled();
+    expect(api.fetchCareEvents).not.toHaveBeenCalled();
+  });
+
+  it("reloads everything on resync", async () => {
+    currentPlant.set(mockPlant);
+
+    await applyChange({ type: "resync" });
+
+    expect(api.fetchPlants).toHaveBeenCalledOnce();
+    expect(api.fetchPlant).toHaveBeenCalledWith(1);
+    expect(api.fetchCareEvents).toHaveBeenCalledWith(1);
+  });
+});
@@ -0,0 +1,66 @@
+import { get } from "svelte/store";
+import { currentPlant, loadPlant, loadPlants } from "./plants";
+import { loadCareEvents } from "./care";
+
+export const EVENTS_URL = "/api/events";
+
+export interface ChangeEvent {
+  type: string;
+  plant_id?: number;
+  event_id?: number;
+  status?: string;
+}
+
+const CHANGE_EVENT_TYPES = [
+  "plant_created",
+  "plant_updated",
+  "plant_deleted",
+  "care_event_created",
+  "care_event_deleted",
+  "photo_changed",
+  "watering_status_changed",
+  "measurement_changed",
+  "resync",
+];
+
+/** Refresh the stores affected by a change broadcast from another client. */
+export async function applyChange(change: ChangeEvent): Promise<void> {
+  await loadPlants();
+
+  const current = get(currentPlant);
+  if (!current) return;
+
+  if (change.type === "resync") {
+    await Promise.all([loadPlant(current.id), loadCareEvents(current.id)]);
+    return;
+  }
+
+  if (change.plant_id !== current.id || change.type === "plant_deleted") {
+    return;
+  }
+
+  await loadPlant(current.id);
+  if (change.event_id !== undefined) {
+    await loadCareEvents(current.id);
+  }
+}
+
+/** Subscribe to the server change feed. Returns a function that closes the connection. */
+export function startLiveUpdates(): () => void {
+  if (typeof EventSource === "undefined") return () => {};
+
+  const source = new EventSource(EVENTS_URL);
+  const handler = (message: MessageEvent) => {
+    try {
+      void applyChange(JSON.parse(message.data) as ChangeEvent);
+    } catch {
+      // Ignore malformed payloads
+    }
+  };
+
+  for (const type of CHANGE_EVENT_TYPES) {
+    source.addEventListener(type, handler);
+  }
+
+  return () => source.close();
+}
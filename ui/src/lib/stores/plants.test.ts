@@ -52,6 +52,11 @@ const mockPlant: Plant = {
   growth_speed: null,
   soil_type: null,
   soil_moisture: null,
+  lifecycle_status: "active",
+  lifecycle_date: null,
+  lifecycle_reason: null,
+  parent_id: null,
+  tags: [],
   created_at: "2025-01-01T00:00:00Z",
   updated_at: "2025-01-01T00:00:00Z",
 };
@@ -50,6 +50,8 @@ function makeEvent(overrides: Partial<CareEvent> = {}): CareEvent {
     event_type: "watered",
     notes: null,
     photo_url: null,
+    photos: [],
+    child_plant_id: null,
     occurred_at: "2025-02-01T10:00:00Z",
     created_at: "2025-02-01T10:00:00Z",
     ...overrides,
@@ -216,6 +218,7 @@ describe("care journal filters", () => {
       "fertilized",
       "repotted",
       "pruned",
+      "propagated",
       "custom",
       "ai-consultation",
     ]) {
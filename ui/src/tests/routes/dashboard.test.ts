@@ -50,6 +50,11 @@ function makePlant(overrides: Partial<Plant> = {}): Plant {
     growth_speed: null,
     soil_type: null,
     soil_moisture: null,
+    lifecycle_status: "active",
+    lifecycle_date: null,
+    lifecycle_reason: null,
+    parent_id: null,
+    tags: [],
     created_at: "2025-01-01T00:00:00Z",
     updated_at: "2025-01-01T00:00:00Z",
     ...overrides,
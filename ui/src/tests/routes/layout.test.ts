@@ -87,7 +87,11 @@ describe("app layout pull-to-refresh", () => {
     vi.useFakeTimers();
     vi.clearAllMocks();
     mockUrl = new URL("http://localhost/");
-    mockFetchSettings.mockResolvedValue({ theme: "system", locale: "en" });
+    mockFetchSettings.mockResolvedValue({
+      theme: "system",
+      locale: "en",
+      time_zone: "UTC",
+    });
     mockMatchMedia({ standalone: true, coarsePointer: true });
     Object.defineProperty(window, "scrollY", { configurable: true, value: 0 });
     Object.defineProperty(window.navigator, "maxTouchPoints", {
@@ -141,7 +145,11 @@ describe("app layout offline indicator", () => {
     vi.clearAllMocks();
     isOffline.set(false);
     mockUrl = new URL("http://localhost/");
-    mockFetchSettings.mockResolvedValue({ theme: "system", locale: "en" });
+    mockFetchSettings.mockResolvedValue({
+      theme: "system",
+      locale: "en",
+      time_zone: "UTC",
+    });
     mockMatchMedia({ standalone: false, coarsePointer: false });
   });
 
@@ -210,7 +218,11 @@ describe("app layout service worker update notification", () => {
     cacheStore = new Map();
     isOffline.set(false);
     mockUrl = new URL("http://localhost/");
-    mockFetchSettings.mockResolvedValue({ theme: "system", locale: "en" });
+    mockFetchSettings.mockResolvedValue({
+      theme: "system",
+      locale: "en",
+      time_zone: "UTC",
+    });
     mockMatchMedia({ standalone: false, coarsePointer: false });
 
     // Mock Cache API (not available in jsdom)
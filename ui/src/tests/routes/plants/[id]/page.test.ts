@@ -126,6 +126,11 @@ function makePlant(overrides: Partial<Plant> = {}): Plant {
     growth_speed: null,
     soil_type: null,
     soil_moisture: null,
+    lifecycle_status: "active",
+    lifecycle_date: null,
+    lifecycle_reason: null,
+    parent_id: null,
+    tags: [],
     created_at: "2025-01-01T00:00:00Z",
     updated_at: "2025-01-01T00:00:00Z",
     ...overrides,
@@ -140,6 +145,8 @@ function makeCareEvent(overrides: Partial<CareEvent> = {}): CareEvent {
     event_type: "watered",
     notes: null,
     photo_url: null,
+    photos: [],
+    child_plant_id: null,
     occurred_at: "2025-01-01T10:00:00Z",
     created_at: "2025-01-01T10:00:00Z",
     ...overrides,
@@ -652,6 +659,8 @@ describe("chat drawer save note", () => {
       event_type: "ai-consultation",
       notes: "Healthy and growing well",
       photo_url: null,
+      photos: [],
+      child_plant_id: null,
       occurred_at: "2025-02-01T10:00:00Z",
       created_at: "2025-02-01T10:00:00Z",
     });
@@ -921,6 +930,8 @@ describe("log form photo upload", () => {
       event_type: "fertilized",
       notes: "",
       photo_url: null,
+      photos: [],
+      child_plant_id: null,
       occurred_at: "2025-02-01T10:00:00Z",
       created_at: "2025-02-01T10:00:00Z",
     };
@@ -1017,6 +1028,8 @@ describe("log form photo upload", () => {
       event_type: "watered",
       notes: "Watered a few days ago",
       photo_url: null,
+      photos: [],
+      child_plant_id: null,
       occurred_at: "2025-01-15T09:30:00Z",
       created_at: "2025-02-01T10:00:00Z",
     };
@@ -338,8 +338,8 @@ describe("settings data section export/import", () => {
     vi.spyOn(api, "importData").mockRejectedValue(
       new api.ApiError(
         400,
-        "IMPORT_VERSION_MISMATCH",
-        "Incompatible export version",
+        "IMPORT_FORMAT_TOO_NEW",
+        "Backup is from a newer version; update to restore it",
       ),
     );
 
@@ -373,7 +373,8 @@ describe("settings data section export/import", () => {
         expect.objectContaining({
           title: "Daten importieren",
           variant: "error",
-          message: "Inkompatible Exportversion",
+          message:
+            "Sicherung stammt aus einer neueren Version; bitte zuerst aktualisieren",
         }),
       );
     });